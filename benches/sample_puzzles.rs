@@ -1,19 +1,19 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 
-use typeshift_solver::Typeshift;
+use typeshift_solver::bench_support::{self, FAST, SLOWEST};
+use typeshift_solver::SolverConfig;
 
 fn bench_typeshift(input: &str) {
-    let typeshift = Typeshift::new(input);
-    let _ = typeshift.find_first_solution();
+    let _ = bench_support::solve(input, &SolverConfig::default());
 }
 
 fn criterion_benchmark(c: &mut Criterion) {
-    let nov_19 = include_str!("../files/puzzles/2023-11-19.txt");
+    let nov_19 = bench_support::input(SLOWEST);
     c.bench_function("Slowest Puzzle (Nov 19)", |b| {
         b.iter(|| bench_typeshift(black_box(nov_19)))
     });
 
-    let nov_16 = include_str!("../files/puzzles/2023-11-16.txt");
+    let nov_16 = bench_support::input(FAST);
     c.bench_function("Fast Puzzle (Nov 16)", |b| {
         b.iter(|| bench_typeshift(black_box(nov_16)))
     });
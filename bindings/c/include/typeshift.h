@@ -0,0 +1,87 @@
+#ifndef TYPESHIFT_H
+#define TYPESHIFT_H
+
+/* Generated by cbindgen from src/lib.rs; do not edit. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+typedef struct Typeshift Typeshift;
+
+/**
+ * The outcome of a solve
+ */
+typedef enum TypeshiftStatus {
+  TYPESHIFT_STATUS_OK = 0,
+  /**
+   * A required pointer argument was null
+   */
+  TYPESHIFT_STATUS_NULL_ARGUMENT = 1,
+  /**
+   * Some letter can't be used by any word
+   */
+  TYPESHIFT_STATUS_UNSOLVABLE = 2,
+  /**
+   * The search gave up at its step limit
+   */
+  TYPESHIFT_STATUS_TIMEOUT = 3,
+} TypeshiftStatus;
+
+/**
+ * An owned array of nul-terminated words
+ */
+typedef struct TypeshiftWords {
+  char **words;
+  size_t len;
+} TypeshiftWords;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Parses a puzzle, with one column per line, leftmost first.
+ * Returns null if the input is null, not utf-8, or not a valid puzzle.
+ *
+ * # Safety
+ * `input` must be null or a valid nul-terminated string.
+ */
+Typeshift *typeshift_new(const char *input);
+
+/**
+ * Finds the first solution the search finds; usually but not always minimal.
+ * Writes its words (in alphabetical order) to `out`.
+ * A `max_steps` of zero means no limit.
+ * On failure, `out` is left empty; it's always safe to free.
+ *
+ * # Safety
+ * `typeshift` must be null or a pointer from `typeshift_new`,
+ * and `out` must be null or valid for writes.
+ */
+enum TypeshiftStatus typeshift_solve(const Typeshift *typeshift,
+                                     size_t max_steps,
+                                     struct TypeshiftWords *out);
+
+/**
+ * Frees the words written by `typeshift_solve`, leaving `words` empty
+ *
+ * # Safety
+ * `words` must be null or point to words written by `typeshift_solve`.
+ */
+void typeshift_words_free(struct TypeshiftWords *words);
+
+/**
+ * Frees a puzzle from `typeshift_new`
+ *
+ * # Safety
+ * `typeshift` must be null or a pointer from `typeshift_new`, not already freed.
+ */
+void typeshift_free(Typeshift *typeshift);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* TYPESHIFT_H */
@@ -0,0 +1,161 @@
+//! A minimal C ABI for the solver, for embedding it in apps that can't link rust directly.
+//! The header is include/typeshift.h.
+//!
+//! Every pointer returned by these functions is owned by the caller,
+//! and must be released with the matching free function.
+
+use std::ffi::{c_char, CStr, CString};
+use std::ptr;
+
+use typeshift_solver::error::SolveError;
+use typeshift_solver::{SolverConfig, Typeshift};
+
+/// The outcome of a solve
+#[repr(C)]
+#[derive(Debug, PartialEq, Eq)]
+pub enum TypeshiftStatus {
+    Ok = 0,
+    /// A required pointer argument was null
+    NullArgument = 1,
+    /// Some letter can't be used by any word
+    Unsolvable = 2,
+    /// The search gave up at its step limit
+    Timeout = 3,
+}
+
+/// An owned array of nul-terminated words
+#[repr(C)]
+pub struct TypeshiftWords {
+    pub words: *mut *mut c_char,
+    pub len: usize,
+}
+
+impl TypeshiftWords {
+    fn empty() -> Self {
+        Self {
+            words: ptr::null_mut(),
+            len: 0,
+        }
+    }
+}
+
+/// Parses a puzzle, with one column per line, leftmost first.
+/// Returns null if the input is null, not utf-8, or not a valid puzzle.
+///
+/// # Safety
+/// `input` must be null or a valid nul-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn typeshift_new(input: *const c_char) -> *mut Typeshift {
+    if input.is_null() {
+        return ptr::null_mut();
+    }
+
+    let Ok(input) = CStr::from_ptr(input).to_str() else {
+        return ptr::null_mut();
+    };
+
+    match Typeshift::parse(input) {
+        Ok(typeshift) => Box::into_raw(Box::new(typeshift)),
+        Err(_) => ptr::null_mut(),
+    }
+}
+
+/// Finds the first solution the search finds; usually but not always minimal.
+/// Writes its words (in alphabetical order) to `out`.
+/// A `max_steps` of zero means no limit.
+/// On failure, `out` is left empty; it's always safe to free.
+///
+/// # Safety
+/// `typeshift` must be null or a pointer from `typeshift_new`,
+/// and `out` must be null or valid for writes.
+#[no_mangle]
+pub unsafe extern "C" fn typeshift_solve(
+    typeshift: *const Typeshift,
+    max_steps: usize,
+    out: *mut TypeshiftWords,
+) -> TypeshiftStatus {
+    if typeshift.is_null() || out.is_null() {
+        return TypeshiftStatus::NullArgument;
+    }
+    out.write(TypeshiftWords::empty());
+
+    let config = SolverConfig {
+        max_steps: (max_steps > 0).then_some(max_steps),
+        ..Default::default()
+    };
+
+    match (*typeshift).solve_with(&config) {
+        Ok((solution, _steps)) => {
+            let words: Box<[*mut c_char]> = solution
+                .iter()
+                .map(|word| CString::new(*word).unwrap().into_raw())
+                .collect();
+            let len = words.len();
+
+            out.write(TypeshiftWords {
+                words: Box::into_raw(words).cast(),
+                len,
+            });
+            TypeshiftStatus::Ok
+        }
+        Err(SolveError::Unsolvable) => TypeshiftStatus::Unsolvable,
+        Err(SolveError::Timeout { .. } | SolveError::Cancelled { .. }) => TypeshiftStatus::Timeout,
+    }
+}
+
+/// Frees the words written by `typeshift_solve`, leaving `words` empty
+///
+/// # Safety
+/// `words` must be null or point to words written by `typeshift_solve`.
+#[no_mangle]
+pub unsafe extern "C" fn typeshift_words_free(words: *mut TypeshiftWords) {
+    if words.is_null() || (*words).words.is_null() {
+        return;
+    }
+
+    let TypeshiftWords { words: ptrs, len } = words.replace(TypeshiftWords::empty());
+    let ptrs = Box::from_raw(ptr::slice_from_raw_parts_mut(ptrs, len));
+    for &word in ptrs.iter() {
+        drop(CString::from_raw(word));
+    }
+}
+
+/// Frees a puzzle from `typeshift_new`
+///
+/// # Safety
+/// `typeshift` must be null or a pointer from `typeshift_new`, not already freed.
+#[no_mangle]
+pub unsafe extern "C" fn typeshift_free(typeshift: *mut Typeshift) {
+    if !typeshift.is_null() {
+        drop(Box::from_raw(typeshift));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solve_through_the_c_abi() {
+        unsafe {
+            let input = CString::new("wsab\nhbta\noesu\ndpive\nlceys").unwrap();
+            let typeshift = typeshift_new(input.as_ptr());
+            assert!(!typeshift.is_null());
+
+            let mut out = TypeshiftWords::empty();
+            assert_eq!(typeshift_solve(typeshift, 0, &mut out), TypeshiftStatus::Ok);
+
+            let words: Vec<&str> = (0..out.len)
+                .map(|i| CStr::from_ptr(*out.words.add(i)).to_str().unwrap())
+                .collect();
+            assert_eq!(words, ["above", "basic", "study", "wheel", "whups"]);
+
+            typeshift_words_free(&mut out);
+            assert!(out.words.is_null());
+            typeshift_free(typeshift);
+
+            let invalid = CString::new("ABC").unwrap();
+            assert!(typeshift_new(invalid.as_ptr()).is_null());
+        }
+    }
+}
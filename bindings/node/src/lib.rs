@@ -0,0 +1,101 @@
+//! Node bindings for the solver, via N-API
+//!
+//! Puzzles are passed as arrays of columns, leftmost first.
+
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+use napi::{Error, Result, Status};
+use napi_derive::napi;
+
+use typeshift_solver::{hint, SolverConfig, Strategy, Typeshift};
+
+#[napi(object)]
+#[derive(Default)]
+pub struct SolveOptions {
+    /// Give up after touching this many partial solutions
+    pub max_steps: Option<u32>,
+
+    /// Give up after searching for this many milliseconds
+    pub timeout_ms: Option<u32>,
+
+    /// Use the fast greedy search, which more often finds a longer solution
+    pub greedy: Option<bool>,
+}
+
+#[napi(object)]
+pub struct Solution {
+    /// The words of the solution, in alphabetical order
+    pub words: Vec<String>,
+
+    /// The number of partial solutions touched along the way
+    pub steps: u32,
+}
+
+/// Finds the first solution the search finds; usually but not always minimal
+#[napi]
+pub fn solve(columns: Vec<String>, options: Option<SolveOptions>) -> Result<Solution> {
+    let typeshift = parse(&columns)?;
+    let (words, steps) = typeshift
+        .solve_with(&config(options))
+        .map_err(solve_error)?;
+
+    Ok(Solution {
+        words: to_strings(&words),
+        steps: steps as u32,
+    })
+}
+
+/// Finds the smallest solutions the search finds; usually but not always every minimal one.
+/// The greedy option is ignored
+#[napi]
+pub fn solve_all(columns: Vec<String>, options: Option<SolveOptions>) -> Result<Vec<Vec<String>>> {
+    let typeshift = parse(&columns)?;
+    let (solutions, _steps) = typeshift
+        .solve_all_with(&config(options))
+        .map_err(solve_error)?;
+
+    Ok(solutions.iter().map(to_strings).collect())
+}
+
+/// Suggests a word from the first solution the search finds that covers a letter the played words don't,
+/// or null if there's nothing left to cover
+#[napi]
+pub fn hint(columns: Vec<String>, played: Vec<String>) -> Result<Option<String>> {
+    let typeshift = parse(&columns)?;
+    let (solution, _steps) = typeshift
+        .solve_with(&SolverConfig::default())
+        .map_err(solve_error)?;
+    let played: Vec<&str> = played.iter().map(String::as_str).collect();
+
+    Ok(hint::suggest(&solution, &played).map(String::from))
+}
+
+fn parse(columns: &[String]) -> Result<Typeshift> {
+    Typeshift::parse(&columns.join("\n")).map_err(|e| Error::new(Status::InvalidArg, e.to_string()))
+}
+
+fn config(options: Option<SolveOptions>) -> SolverConfig {
+    let options = options.unwrap_or_default();
+    let strategy = match options.greedy {
+        Some(true) => Strategy::Greedy,
+        _ => Strategy::BestFirst,
+    };
+
+    SolverConfig {
+        strategy,
+        max_steps: options.max_steps.map(|steps| steps as usize),
+        timeout: options
+            .timeout_ms
+            .map(|ms| Duration::from_millis(ms.into())),
+        ..Default::default()
+    }
+}
+
+fn solve_error(e: typeshift_solver::error::SolveError) -> Error {
+    Error::new(Status::GenericFailure, e.to_string())
+}
+
+fn to_strings(words: &BTreeSet<&'static str>) -> Vec<String> {
+    words.iter().map(|w| w.to_string()).collect()
+}
@@ -0,0 +1,153 @@
+//! UniFFI bindings for Kotlin and Swift, covering constructing, solving, and hints
+
+use std::collections::BTreeSet;
+use std::time::Duration;
+
+use typeshift_solver::error::SolveError;
+use typeshift_solver::{hint, SolverConfig, Strategy, Typeshift};
+
+uniffi::setup_scaffolding!();
+
+#[derive(Debug, uniffi::Error)]
+pub enum TypeshiftError {
+    /// The puzzle input isn't lines of lowercase letters
+    Parse { message: String },
+    /// Some letter can't be used by any word
+    Unsolvable,
+    /// The search gave up at its step or time limit
+    Timeout { steps: u64 },
+}
+
+impl std::fmt::Display for TypeshiftError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TypeshiftError::Parse { message } => write!(f, "{message}"),
+            TypeshiftError::Unsolvable => write!(f, "the puzzle has no solution"),
+            TypeshiftError::Timeout { steps } => write!(f, "gave up after {steps} steps"),
+        }
+    }
+}
+
+impl From<SolveError> for TypeshiftError {
+    fn from(e: SolveError) -> Self {
+        match e {
+            SolveError::Unsolvable => TypeshiftError::Unsolvable,
+            SolveError::Timeout { steps } | SolveError::Cancelled { steps } => {
+                TypeshiftError::Timeout {
+                    steps: steps as u64,
+                }
+            }
+        }
+    }
+}
+
+#[derive(uniffi::Record)]
+pub struct SolveOptions {
+    /// Use the fast greedy search, which more often finds a longer solution
+    #[uniffi(default = false)]
+    pub greedy: bool,
+
+    /// Give up after touching this many partial solutions
+    #[uniffi(default = None)]
+    pub max_steps: Option<u64>,
+
+    /// Give up after searching for this many milliseconds
+    #[uniffi(default = None)]
+    pub timeout_ms: Option<u64>,
+}
+
+#[derive(uniffi::Record)]
+pub struct Solution {
+    /// The words of the solution, in alphabetical order
+    pub words: Vec<String>,
+
+    /// The number of partial solutions touched along the way
+    pub steps: u64,
+}
+
+/// A parsed puzzle
+#[derive(uniffi::Object)]
+pub struct Puzzle(Typeshift);
+
+#[uniffi::export]
+impl Puzzle {
+    /// Parses the puzzle columns, leftmost first
+    #[uniffi::constructor]
+    pub fn new(columns: Vec<String>) -> Result<Self, TypeshiftError> {
+        Typeshift::parse(&columns.join("\n"))
+            .map(Puzzle)
+            .map_err(|e| TypeshiftError::Parse {
+                message: e.to_string(),
+            })
+    }
+
+    /// The words spellable from the columns
+    pub fn possible_words(&self) -> Vec<String> {
+        self.0.words().iter().map(|w| w.to_string()).collect()
+    }
+
+    pub fn solve(&self, options: SolveOptions) -> Result<Solution, TypeshiftError> {
+        let strategy = if options.greedy {
+            Strategy::Greedy
+        } else {
+            Strategy::BestFirst
+        };
+        let config = SolverConfig {
+            strategy,
+            max_steps: options.max_steps.map(|steps| steps as usize),
+            timeout: options.timeout_ms.map(Duration::from_millis),
+            ..Default::default()
+        };
+
+        let (words, steps) = self.0.solve_with(&config)?;
+
+        Ok(Solution {
+            words: to_strings(&words),
+            steps: steps as u64,
+        })
+    }
+
+    /// Suggests a word from the first solution the search finds that covers a letter the played words don't,
+    /// or nothing if there's nothing left to cover
+    pub fn hint(&self, played: Vec<String>) -> Result<Option<String>, TypeshiftError> {
+        let (solution, _steps) = self.0.solve_with(&SolverConfig::default())?;
+        let played: Vec<&str> = played.iter().map(String::as_str).collect();
+
+        Ok(hint::suggest(&solution, &played).map(String::from))
+    }
+}
+
+fn to_strings(words: &BTreeSet<&'static str>) -> Vec<String> {
+    words.iter().map(|w| w.to_string()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn solve_and_hint() {
+        let columns = ["wsab", "hbta", "oesu", "dpive", "lceys"].map(String::from);
+        let puzzle = Puzzle::new(columns.to_vec()).unwrap();
+        let options = SolveOptions {
+            greedy: false,
+            max_steps: None,
+            timeout_ms: None,
+        };
+
+        let solution = puzzle.solve(options).unwrap();
+        assert_eq!(
+            solution.words,
+            ["above", "basic", "study", "wheel", "whups"]
+        );
+        assert_eq!(
+            puzzle.hint(vec!["above".to_string()]).unwrap().as_deref(),
+            Some("basic")
+        );
+
+        assert!(matches!(
+            Puzzle::new(vec!["ABC".to_string()]),
+            Err(TypeshiftError::Parse { .. })
+        ));
+    }
+}
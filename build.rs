@@ -0,0 +1,42 @@
+fn main() {
+    #[cfg(feature = "grpc")]
+    grpc::generate();
+}
+
+/// Generates the tonic service from a rust description of proto/typeshift.proto;
+/// the message types are written by hand in src/grpc.rs, so this needs no protoc
+#[cfg(feature = "grpc")]
+mod grpc {
+    use tonic_build::manual::{Builder, Method, Service};
+
+    const METHODS: [(&str, &str, &str, &str); 4] = [
+        ("solve", "Solve", "SolveRequest", "SolveResponse"),
+        ("solve_all", "SolveAll", "SolveRequest", "SolveAllResponse"),
+        ("hint", "Hint", "HintRequest", "HintResponse"),
+        (
+            "generate",
+            "Generate",
+            "GenerateRequest",
+            "GenerateResponse",
+        ),
+    ];
+
+    pub fn generate() {
+        println!("cargo:rerun-if-changed=build.rs");
+
+        let mut service = Service::builder().name("Solver").package("typeshift");
+        for (name, route_name, input, output) in METHODS {
+            service = service.method(
+                Method::builder()
+                    .name(name)
+                    .route_name(route_name)
+                    .input_type(format!("crate::grpc::{input}"))
+                    .output_type(format!("crate::grpc::{output}"))
+                    .codec_path("tonic_prost::ProstCodec")
+                    .build(),
+            );
+        }
+
+        Builder::new().compile(&[service.build()]);
+    }
+}
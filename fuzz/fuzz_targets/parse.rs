@@ -0,0 +1,21 @@
+//! Arbitrary text must parse or fail with an error, never panic,
+//! and parsed columns must be valid and parse again to themselves
+
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+use typeshift_solver::Typeshift;
+
+fuzz_target!(|input: &str| {
+    if let Ok(typeshift) = Typeshift::parse(input) {
+        let columns = typeshift.input_columns();
+        for column in columns {
+            assert!(!column.is_empty());
+            assert!(column.chars().all(|ch| ch.is_ascii_lowercase()));
+        }
+
+        let reparsed = Typeshift::parse_columns(&columns.join("\n")).unwrap();
+        assert_eq!(reparsed, columns);
+    }
+});
@@ -0,0 +1,71 @@
+//! Small arbitrary puzzles must solve or fail with an error, never panic,
+//! and every solution must use only puzzle words and cover every letter
+
+#![no_main]
+
+use std::collections::BTreeSet;
+
+use libfuzzer_sys::fuzz_target;
+
+use typeshift_solver::{SolverConfig, Strategy, Typeshift};
+
+/// Enough for any puzzle this small; the limit only keeps runs fast
+const MAX_STEPS: usize = 10_000;
+
+fuzz_target!(|columns: Vec<Vec<u8>>| {
+    // up to 6 columns of up to 6 letters
+    let columns: Vec<String> = columns
+        .iter()
+        .take(6)
+        .map(|column| {
+            column
+                .iter()
+                .take(6)
+                .map(|b| (b'a' + b % 26) as char)
+                .collect()
+        })
+        .collect();
+    let Ok(typeshift) = Typeshift::parse(&columns.join("\n")) else {
+        return;
+    };
+
+    for strategy in [Strategy::BestFirst, Strategy::Greedy] {
+        let config = SolverConfig {
+            strategy,
+            max_steps: Some(MAX_STEPS),
+            ..Default::default()
+        };
+        if let Ok((solution, _steps)) = typeshift.solve_with(&config) {
+            verify(&typeshift, &solution);
+        }
+    }
+
+    let config = SolverConfig {
+        max_steps: Some(MAX_STEPS),
+        ..Default::default()
+    };
+    if let Ok((solutions, _steps)) = typeshift.solve_all_with(&config) {
+        assert!(!solutions.is_empty());
+        for solution in &solutions {
+            verify(&typeshift, solution);
+        }
+    }
+});
+
+fn verify(typeshift: &Typeshift, solution: &BTreeSet<&'static str>) {
+    for word in solution {
+        assert!(
+            typeshift.words().contains(word),
+            "{word} isn't a puzzle word"
+        );
+    }
+
+    for (col, letters) in typeshift.input_columns().iter().enumerate() {
+        for ch in letters.chars() {
+            let covered = solution
+                .iter()
+                .any(|word| word.chars().nth(col) == Some(ch));
+            assert!(covered, "{ch} in column {} isn't covered", col + 1);
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! Plain-language descriptions of puzzles and solutions, for screen readers
+//!
+//! A grid read row by row is meaningless out loud, so the puzzle is described
+//! column by column, and each solution word is spelled out letter by letter.
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::grid::covering_words;
+
+/// Describes a puzzle's columns, then narrates the words of a solution (if any are given)
+/// and the letters more than one of them use. Columns are numbered from 1.
+pub fn render_accessible(columns: &[String], words: &[String]) -> String {
+    let mut out = format!("The puzzle has {} columns.\n", columns.len());
+    for (col, letters) in columns.iter().enumerate() {
+        out.push_str(&format!("Column {}: {}.\n", col + 1, spell(letters)));
+    }
+
+    if words.is_empty() {
+        return out;
+    }
+
+    out.push_str(&format!("The solution has {} words.\n", words.len()));
+    for (i, word) in words.iter().enumerate() {
+        out.push_str(&format!(
+            "Word {}: {word}, spelled {}.\n",
+            i + 1,
+            spell(word)
+        ));
+    }
+
+    let mut shared = Vec::new();
+    let mut unused = Vec::new();
+    for (col, letters) in columns.iter().enumerate() {
+        for ch in letters.chars() {
+            match covering_words(words, col, ch).as_slice() {
+                [] => unused.push(format!("{ch} in column {}", col + 1)),
+                [_] => {}
+                covering => {
+                    let covering: Vec<&str> = covering.iter().map(|&i| &*words[i]).collect();
+                    let covering = covering.join(" and ");
+                    shared.push(format!("{ch} in column {}, by {covering}", col + 1));
+                }
+            }
+        }
+    }
+
+    match shared.is_empty() {
+        true => out.push_str("No letter is used by more than one word.\n"),
+        false => out.push_str(&format!(
+            "Letters used by more than one word: {}.\n",
+            shared.join("; ")
+        )),
+    }
+    if !unused.is_empty() {
+        out.push_str(&format!("Letters not used: {}.\n", unused.join("; ")));
+    }
+
+    out
+}
+
+/// Separates letters with commas, so that they're read one at a time
+fn spell(letters: &str) -> String {
+    let letters: Vec<String> = letters.chars().map(|ch| ch.to_string()).collect();
+    letters.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn accessible_description() {
+        let columns: Vec<String> = ["wsab", "hbta", "oesu", "dpive", "lceys"]
+            .map(String::from)
+            .to_vec();
+        let words: Vec<String> = ["above", "basic", "study", "wheel", "whups"]
+            .map(String::from)
+            .to_vec();
+
+        let expected = "\
+The puzzle has 5 columns.
+Column 1: w, s, a, b.
+Column 2: h, b, t, a.
+Column 3: o, e, s, u.
+Column 4: d, p, i, v, e.
+Column 5: l, c, e, y, s.
+The solution has 5 words.
+Word 1: above, spelled a, b, o, v, e.
+Word 2: basic, spelled b, a, s, i, c.
+Word 3: study, spelled s, t, u, d, y.
+Word 4: wheel, spelled w, h, e, e, l.
+Word 5: whups, spelled w, h, u, p, s.
+Letters used by more than one word: w in column 1, by wheel and whups; h in column 2, by wheel and whups; u in column 3, by study and whups.
+";
+        assert_eq!(render_accessible(&columns, &words), expected);
+
+        let partial = render_accessible(&columns, &words[..1]);
+        assert!(partial.ends_with("Letters not used: w in column 1; s in column 1; b in column 1; h in column 2; t in column 2; a in column 2; e in column 3; s in column 3; u in column 3; d in column 4; p in column 4; i in column 4; e in column 4; l in column 5; c in column 5; y in column 5; s in column 5.\n"));
+        assert!(render_accessible(&columns, &[]).ends_with("Column 5: l, c, e, y, s.\n"));
+    }
+}
@@ -0,0 +1,1302 @@
+//! Analyses of puzzles and their solutions, beyond solving them
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+use core::fmt;
+use core::iter::zip;
+
+use crate::coverage::CoverageMatrix;
+use crate::dictionary;
+use crate::error::{PlayError, SolveError};
+use crate::game::GameState;
+use crate::{SolverConfig, Typeshift};
+
+/// How ambiguous a puzzle's minimal solutions are
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Ambiguity {
+    /// The number of minimal solutions
+    pub solutions: usize,
+
+    /// Words that appear in every minimal solution
+    pub common_words: BTreeSet<&'static str>,
+
+    /// Words that appear in some but not all minimal solutions
+    pub distinguishing_words: BTreeSet<&'static str>,
+
+    /// The minimal solutions grouped by the distinguishing words they share,
+    /// largest family first
+    pub families: Vec<SolutionFamily>,
+}
+
+/// Minimal solutions that share a pivot word
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SolutionFamily {
+    /// The distinguishing word every solution in the family uses, chosen as the one
+    /// in the most solutions not yet in a family; None for a lone solution
+    /// with no distinguishing words
+    pub pivot: Option<&'static str>,
+
+    /// The number of minimal solutions in the family
+    pub solutions: usize,
+
+    /// Distinguishing words in every solution of the family, including the pivot
+    pub shared_words: BTreeSet<&'static str>,
+
+    /// Words in some but not all solutions of the family
+    pub varying_words: BTreeSet<&'static str>,
+}
+
+impl Ambiguity {
+    /// Lists every minimal solution exactly, with `Typeshift::for_each_cover`,
+    /// and compares them
+    pub fn new(typeshift: &Typeshift) -> Self {
+        // without limits, only an unsolvable puzzle fails, and it has no solutions
+        let (_minimal_words, all_solutions) =
+            minimal_solutions(typeshift, &SolverConfig::default()).unwrap();
+        Self::from_solutions(&all_solutions)
+    }
+
+    pub fn from_solutions(all_solutions: &BTreeSet<BTreeSet<&'static str>>) -> Self {
+        let mut solutions = all_solutions.iter();
+        let mut common_words = solutions.next().cloned().unwrap_or_default();
+        for solution in solutions {
+            common_words.retain(|word| solution.contains(word));
+        }
+
+        let distinguishing_words = all_solutions
+            .iter()
+            .flatten()
+            .filter(|word| !common_words.contains(*word))
+            .copied()
+            .collect();
+
+        let families = SolutionFamily::group(all_solutions, &common_words);
+
+        Self {
+            solutions: all_solutions.len(),
+            common_words,
+            distinguishing_words,
+            families,
+        }
+    }
+
+    /// Whether there is more than one minimal solution
+    pub fn is_ambiguous(&self) -> bool {
+        self.solutions > 1
+    }
+}
+
+impl SolutionFamily {
+    /// Repeatedly takes the solutions with the word most of the rest use as a family,
+    /// ignoring the words in every solution
+    fn group(
+        all_solutions: &BTreeSet<BTreeSet<&'static str>>,
+        common_words: &BTreeSet<&'static str>,
+    ) -> Vec<Self> {
+        let mut remaining = Vec::from_iter(all_solutions);
+        let mut families = Vec::new();
+        while !remaining.is_empty() {
+            let mut uses: BTreeMap<&'static str, usize> = BTreeMap::new();
+            for word in remaining.iter().copied().flatten() {
+                if !common_words.contains(word) {
+                    *uses.entry(word).or_default() += 1;
+                }
+            }
+            // the alphabetically first of the most used words
+            let pivot = (uses.into_iter())
+                .max_by_key(|&(word, uses)| (uses, Reverse(word)))
+                .map(|(word, _)| word);
+
+            let (family, rest): (Vec<_>, Vec<_>) = remaining
+                .into_iter()
+                .partition(|solution| pivot.is_none_or(|pivot| solution.contains(pivot)));
+            remaining = rest;
+
+            let mut shared_words = family[0].clone();
+            for solution in &family {
+                shared_words.retain(|word| solution.contains(word));
+            }
+            let varying_words = (family.iter().copied().flatten())
+                .filter(|word| !shared_words.contains(*word))
+                .copied()
+                .collect();
+            shared_words.retain(|word| !common_words.contains(word));
+
+            families.push(Self {
+                pivot,
+                solutions: family.len(),
+                shared_words,
+                varying_words,
+            });
+        }
+
+        families.sort_by_key(|family| Reverse(family.solutions));
+        families
+    }
+}
+
+/// Letters used by at most this many words are bottlenecks, by default
+pub const BOTTLENECK_WORDS: usize = 2;
+
+/// How many words use each of a puzzle's letters. Letters that few words use are
+/// bottlenecks: every solution needs one of those words, which mostly decides how hard
+/// the puzzle is to solve.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LetterCoverage {
+    /// Each letter, leftmost column first and alphabetically within a column
+    pub letters: Vec<CoveredLetter>,
+}
+
+/// One letter of a puzzle, and the words that use it
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CoveredLetter {
+    /// The letter's column, counting from zero
+    pub column: usize,
+    pub letter: char,
+
+    /// The words using the letter, alphabetically
+    pub words: Vec<&'static str>,
+
+    /// True if few enough words use the letter to make it a bottleneck
+    pub bottleneck: bool,
+}
+
+impl LetterCoverage {
+    /// Flags letters used by at most `BOTTLENECK_WORDS` words
+    pub fn new(typeshift: &Typeshift) -> Self {
+        Self::with_threshold(typeshift, BOTTLENECK_WORDS)
+    }
+
+    /// Flags letters used by at most `max_words` words
+    pub fn with_threshold(typeshift: &Typeshift, max_words: usize) -> Self {
+        let matrix = CoverageMatrix::new(typeshift);
+        let letters = (matrix.letters.iter().enumerate())
+            .map(|(index, &(column, letter))| {
+                let words: Vec<_> = zip(&matrix.words, &matrix.rows)
+                    .filter(|(_, row)| row[index])
+                    .map(|(&word, _)| word)
+                    .collect();
+                let bottleneck = words.len() <= max_words;
+
+                CoveredLetter {
+                    column,
+                    letter,
+                    words,
+                    bottleneck,
+                }
+            })
+            .collect();
+
+        Self { letters }
+    }
+
+    /// The bottleneck letters, fewest words first
+    pub fn bottlenecks(&self) -> Vec<&CoveredLetter> {
+        let mut bottlenecks: Vec<_> = self.letters.iter().filter(|l| l.bottleneck).collect();
+        bottlenecks.sort_by_key(|letter| letter.words.len());
+        bottlenecks
+    }
+}
+
+/// Why a puzzle has no solution: the letters that no word uses. Any letters that words
+/// use can be covered together, so each of these is a minimal unsolvable core by itself.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct UnsolvableCore {
+    /// The unused letters, leftmost column first
+    pub cells: Vec<UncoveredCell>,
+}
+
+/// A letter of a puzzle that no word uses
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct UncoveredCell {
+    /// The letter's column, counting from zero
+    pub column: usize,
+    pub letter: char,
+
+    /// Words in the built-in dictionary with the letter in its column that differ from
+    /// the puzzle in one other column, alphabetically; the puzzle may expect one of them
+    /// with a letter changed
+    pub near_words: Vec<&'static str>,
+}
+
+impl UnsolvableCore {
+    /// None if every letter has a word, and the puzzle is solvable
+    pub fn new(typeshift: &Typeshift) -> Option<Self> {
+        let columns = typeshift.input_columns();
+        let cells: Vec<_> = (LetterCoverage::with_threshold(typeshift, 0)
+            .letters
+            .into_iter())
+        .filter(|letter| letter.words.is_empty())
+        .map(|CoveredLetter { column, letter, .. }| {
+            let near_words = (dictionary::words().iter())
+                .filter(|word| word.len() == columns.len())
+                .filter(|word| word.as_bytes()[column] == letter as u8)
+                .filter(|word| {
+                    let misses = zip(word.chars(), columns)
+                        .filter(|(letter, column)| !column.contains(*letter))
+                        .count();
+                    misses == 1
+                })
+                .copied()
+                .collect();
+
+            UncoveredCell {
+                column,
+                letter,
+                near_words,
+            }
+        })
+        .collect();
+
+        (!cells.is_empty()).then_some(Self { cells })
+    }
+}
+
+/// What makes a puzzle fun beyond how hard it is: how many words lead nowhere,
+/// how many word sets almost solve it, and how much solution words resemble each other
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Interest {
+    /// Words spellable from the puzzle that no minimal solution uses
+    pub decoy_words: usize,
+
+    /// Sets of as many words as a minimal solution that use every letter but one
+    pub near_solutions: u64,
+
+    /// The most columns in which two words of a minimal solution have the same letter,
+    /// averaged over the minimal solutions. Each column's letters fix how many letters a
+    /// solution repeats there, but not which words repeat them: solutions with their
+    /// repeats spread across different pairs of words are harder to find by varying
+    /// one word, like "wheel" to "whups".
+    pub most_shared_columns: f64,
+}
+
+impl Interest {
+    /// Counts and lists every minimal solution; respects the configuration's limits
+    pub fn new(typeshift: &Typeshift, config: &SolverConfig) -> Result<Self, SolveError> {
+        let count = typeshift.count_solutions(config)?;
+        let minimal_words = count.minimal_words;
+
+        let mut used_words = BTreeSet::new();
+        let mut most_shared_columns = 0;
+        typeshift.for_each_cover(minimal_words, config, |solution| {
+            used_words.extend(solution.iter().copied());
+
+            let words = Vec::from_iter(solution.iter().copied());
+            let most_shared = (words.iter().enumerate())
+                .flat_map(|(i, a)| words[i + 1..].iter().map(move |b| (a, b)))
+                .map(|(a, b)| zip(a.bytes(), b.bytes()).filter(|(a, b)| a == b).count())
+                .max()
+                .unwrap_or(0);
+            most_shared_columns += most_shared;
+        })?;
+
+        Ok(Self {
+            decoy_words: typeshift.size() - used_words.len(),
+            near_solutions: typeshift.count_near_solutions(minimal_words, config)?,
+            most_shared_columns: most_shared_columns as f64 / count.solutions as f64,
+        })
+    }
+}
+
+/// A hypothetical change to a puzzle, for `WhatIf`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum PuzzleChange {
+    /// Take a letter out of a column, counting columns from zero
+    RemoveLetter { column: usize, letter: char },
+
+    /// Leave a word out of the dictionary
+    ExcludeWord(String),
+}
+
+/// How a puzzle's minimal solutions change with a `PuzzleChange`, to debug
+/// unwanted alternative answers
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct WhatIf {
+    pub change: PuzzleChange,
+
+    /// The number of words in a minimal solution before the change,
+    /// or None if the puzzle was unsolvable
+    pub minimal_words_before: Option<usize>,
+
+    /// The number of words in a minimal solution after the change,
+    /// or None if it leaves the puzzle unsolvable
+    pub minimal_words_after: Option<usize>,
+
+    /// Minimal solutions before the change that aren't minimal solutions after it
+    pub lost: BTreeSet<BTreeSet<&'static str>>,
+
+    /// Minimal solutions after the change that weren't minimal solutions before it
+    pub gained: BTreeSet<BTreeSet<&'static str>>,
+
+    /// The number of minimal solutions before and after the change
+    pub kept: usize,
+}
+
+impl WhatIf {
+    /// Lists every minimal solution of the puzzle with and without the change;
+    /// respects the configuration's limits for each
+    pub fn new(
+        typeshift: &Typeshift,
+        change: PuzzleChange,
+        config: &SolverConfig,
+    ) -> Result<Self, SolveError> {
+        let (minimal_words_before, before) = minimal_solutions(typeshift, config)?;
+
+        let mut columns = typeshift.input_columns().to_vec();
+        let mut words = typeshift.words().to_vec();
+        match &change {
+            PuzzleChange::RemoveLetter { column, letter } => {
+                if let Some(column) = columns.get_mut(*column) {
+                    column.retain(|l| l != *letter);
+                }
+            }
+            PuzzleChange::ExcludeWord(excluded) => words.retain(|word| word != excluded),
+        }
+        let changed = Typeshift::with_dictionary(columns, &words);
+
+        let (minimal_words_after, after) = minimal_solutions(&changed, config)?;
+
+        Ok(Self {
+            change,
+            minimal_words_before,
+            minimal_words_after,
+            lost: before.difference(&after).cloned().collect(),
+            gained: after.difference(&before).cloned().collect(),
+            kept: before.intersection(&after).count(),
+        })
+    }
+}
+
+/// The number of words in a minimal solution (None if the puzzle is unsolvable),
+/// and every minimal solution
+fn minimal_solutions(
+    typeshift: &Typeshift,
+    config: &SolverConfig,
+) -> Result<(Option<usize>, BTreeSet<BTreeSet<&'static str>>), SolveError> {
+    let minimal_words = match typeshift.count_solutions(config) {
+        Ok(count) => count.minimal_words,
+        Err(SolveError::Unsolvable) => return Ok((None, BTreeSet::new())),
+        Err(e) => return Err(e),
+    };
+
+    let mut solutions = BTreeSet::new();
+    typeshift.for_each_cover(minimal_words, config, |solution| {
+        solutions.insert(solution.clone());
+    })?;
+
+    Ok((Some(minimal_words), solutions))
+}
+
+/// The minimal solution closest to a given one, for showing that there was another way
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct NearestAlternative {
+    pub solution: BTreeSet<&'static str>,
+
+    /// The words of the given solution this one does without
+    pub removed: BTreeSet<&'static str>,
+
+    /// The words this one uses instead
+    pub added: BTreeSet<&'static str>,
+}
+
+impl NearestAlternative {
+    /// Finds the minimal solution other than the given one that shares the most words with it;
+    /// the alphabetically first of those tied. None if the given solution is the only
+    /// minimal one.
+    pub fn new(
+        typeshift: &Typeshift,
+        solution: &BTreeSet<&'static str>,
+        config: &SolverConfig,
+    ) -> Result<Option<Self>, SolveError> {
+        let minimal_words = typeshift.count_solutions(config)?.minimal_words;
+
+        let mut nearest: Option<(usize, BTreeSet<&'static str>)> = None;
+        typeshift.for_each_cover(minimal_words, config, |other| {
+            if other == solution {
+                return;
+            }
+
+            let difference = other.symmetric_difference(solution).count();
+            let nearer = nearest
+                .as_ref()
+                .is_none_or(|(nearest_difference, nearest)| {
+                    (difference, other) < (*nearest_difference, nearest)
+                });
+            if nearer {
+                nearest = Some((difference, other.clone()));
+            }
+        })?;
+
+        Ok(nearest.map(|(_, other)| NearestAlternative {
+            removed: solution.difference(&other).copied().collect(),
+            added: other.difference(solution).copied().collect(),
+            solution: other,
+        }))
+    }
+}
+
+impl fmt::Display for NearestAlternative {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (removed, added) = (list_words(&self.removed), list_words(&self.added));
+        match self.added.is_empty() {
+            // only when the given words weren't minimal
+            true => write!(f, "drop {removed}"),
+            false => write!(f, "swap {removed} for {added}"),
+        }
+    }
+}
+
+/// Lists words for a sentence, like "a, b and c"
+fn list_words(words: &BTreeSet<&'static str>) -> String {
+    let words = Vec::from_iter(words.iter().copied());
+    match words.split_last() {
+        Some((last, rest)) if !rest.is_empty() => format!("{} and {last}", rest.join(", ")),
+        _ => words.concat(),
+    }
+}
+
+/// How a played word affects the fewest words a game can finish in, for learning from play
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PlayFeedback {
+    pub word: String,
+
+    /// Whether any minimal solution of the puzzle uses the word
+    pub in_minimal_solution: bool,
+
+    /// The fewest words a finished game could use before the word was played,
+    /// counting the words played already
+    pub best_total_before: usize,
+
+    /// The fewest words a finished game could use after the word was played
+    pub best_total_after: usize,
+}
+
+impl PlayFeedback {
+    /// Judges playing the word after the earlier words; respects the configuration's limits
+    /// for each of the games it finishes
+    pub fn new(
+        typeshift: &Typeshift,
+        earlier: &[&str],
+        word: &str,
+        config: &SolverConfig,
+    ) -> Result<Self, PlayError> {
+        // finishing a game from the played words gives an upper bound,
+        // and the covers with fewer words than that are few enough to check
+        let best_total = |played: &[&str]| -> Result<usize, PlayError> {
+            let (remaining, _steps) = typeshift.complete_with(played, config)?;
+            let mut best = played.len() + remaining.len();
+            typeshift.for_each_cover(best - 1, config, |cover| {
+                if played.iter().all(|word| cover.contains(word)) {
+                    best = best.min(cover.len());
+                }
+            })?;
+            Ok(best)
+        };
+
+        let mut played = earlier.to_vec();
+        played.push(word);
+
+        Ok(Self {
+            word: String::from(word),
+            in_minimal_solution: best_total(&[word])? == best_total(&[])?,
+            best_total_before: best_total(earlier)?,
+            best_total_after: best_total(&played)?,
+        })
+    }
+
+    /// How many more words the best finished game needs because the word was played
+    pub fn extra_words(&self) -> usize {
+        self.best_total_after - self.best_total_before
+    }
+}
+
+impl fmt::Display for PlayFeedback {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let word = &self.word;
+        match self.in_minimal_solution {
+            true => write!(f, "{word} is in a minimal solution")?,
+            false => write!(f, "{word} isn't in any minimal solution")?,
+        }
+
+        match self.extra_words() {
+            0 => write!(
+                f,
+                "; you can still finish in {} words",
+                self.best_total_after
+            ),
+            _ => write!(
+                f,
+                "; it raised the fewest words you can finish in from {} to {}",
+                self.best_total_before, self.best_total_after
+            ),
+        }
+    }
+}
+
+/// How a finished game compares to a minimal solution, and how to close the gap
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct MistakeReport {
+    /// The number of words played
+    pub words_used: usize,
+
+    /// The number of words in a minimal solution
+    pub minimal_words: usize,
+
+    /// Played words whose letters the other played words all cover,
+    /// so that each could have been left out on its own
+    pub redundant: Vec<&'static str>,
+
+    /// The minimal solution that keeps the most played words,
+    /// or None if the game was already minimal
+    pub improvement: Option<NearestAlternative>,
+}
+
+impl MistakeReport {
+    /// Compares a finished game's words to the puzzle's minimal solutions;
+    /// respects the configuration's limits. Fails with `PlayError::Unfinished`
+    /// if the words don't cover every letter.
+    pub fn new(
+        typeshift: &Typeshift,
+        played: &[&str],
+        config: &SolverConfig,
+    ) -> Result<Self, PlayError> {
+        let mut game = GameState::new(typeshift);
+        for word in played {
+            game.play(word)?;
+        }
+        let uncovered = game.uncovered().len();
+        if uncovered > 0 {
+            return Err(PlayError::Unfinished { uncovered });
+        }
+
+        let redundant = (game.played().iter().copied())
+            .filter(|word| {
+                (word.chars().enumerate()).all(|(column, letter)| game.usage(column, letter) > 1)
+            })
+            .collect();
+
+        let minimal_words = typeshift.count_solutions(config)?.minimal_words;
+        let words_used = played.len();
+        let improvement = match words_used > minimal_words {
+            true => {
+                let played = BTreeSet::from_iter(game.played().iter().copied());
+                NearestAlternative::new(typeshift, &played, config)?
+            }
+            false => None,
+        };
+
+        Ok(Self {
+            words_used,
+            minimal_words,
+            redundant,
+            improvement,
+        })
+    }
+
+    /// How many more words the game used than a minimal solution
+    pub fn extra_words(&self) -> usize {
+        self.words_used - self.minimal_words
+    }
+}
+
+impl fmt::Display for MistakeReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (used, minimal) = (self.words_used, self.minimal_words);
+        match self.extra_words() {
+            0 => return writeln!(f, "{used} words, as few as possible"),
+            extra => writeln!(
+                f,
+                "{used} words, {extra} more than the fewest possible, {minimal}"
+            )?,
+        }
+
+        for word in &self.redundant {
+            writeln!(f, "the other words cover every letter of {word}")?;
+        }
+        if let Some(improvement) = &self.improvement {
+            let saved = self.extra_words();
+            let s = if saved == 1 { "" } else { "s" };
+            writeln!(f, "{improvement} to save {saved} word{s}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Why a solution uses one of its words, in terms a puzzle write-up can quote
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct WordExplanation {
+    pub word: &'static str,
+
+    /// The letters no other word of the solution uses, as (column, letter) with columns
+    /// counting from zero; empty if the solution doesn't need the word
+    pub covers: Vec<(usize, char)>,
+
+    /// Of the covered letters, the one the fewest of the puzzle's words use
+    pub rarest: Option<CoveredLetter>,
+
+    /// Other words of the puzzle that use every covered letter, alphabetically;
+    /// each could replace the word in the solution
+    pub alternatives: Vec<&'static str>,
+}
+
+impl WordExplanation {
+    /// Explains each word of a solution, alphabetically
+    pub fn for_solution(typeshift: &Typeshift, solution: &BTreeSet<&'static str>) -> Vec<Self> {
+        let coverage = LetterCoverage::with_threshold(typeshift, 0);
+        let uses =
+            |word: &str, column: usize, letter: char| word.as_bytes()[column] == letter as u8;
+
+        (solution.iter())
+            .map(|&word| {
+                let covered: Vec<&CoveredLetter> = (coverage.letters.iter())
+                    .filter(|covered| {
+                        let has_letter = |other: &str| uses(other, covered.column, covered.letter);
+                        has_letter(word)
+                            && !(solution.iter()).any(|&other| other != word && has_letter(other))
+                    })
+                    .collect();
+
+                let rarest = (covered.iter())
+                    .min_by_key(|covered| covered.words.len())
+                    .map(|&covered| covered.clone());
+                let alternatives = match covered.first() {
+                    Some(first) => (first.words.iter().copied())
+                        .filter(|&other| other != word && !solution.contains(other))
+                        .filter(|&other| {
+                            (covered.iter())
+                                .all(|covered| uses(other, covered.column, covered.letter))
+                        })
+                        .collect(),
+                    None => Vec::new(),
+                };
+
+                WordExplanation {
+                    word,
+                    covers: (covered.iter())
+                        .map(|covered| (covered.column, covered.letter))
+                        .collect(),
+                    rarest,
+                    alternatives,
+                }
+            })
+            .collect()
+    }
+}
+
+impl fmt::Display for WordExplanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.covers.is_empty() {
+            return write!(
+                f,
+                "{} isn't needed; the other words cover its letters",
+                self.word
+            );
+        }
+
+        let covers: Vec<_> = (self.covers.iter())
+            .map(|(column, letter)| format!("{letter} in column {}", column + 1))
+            .collect();
+        write!(
+            f,
+            "{} is the only word in the solution with {}",
+            self.word,
+            covers.join(", ")
+        )?;
+
+        if let Some(rarest) = &self.rarest {
+            let (letter, column) = (rarest.letter, rarest.column + 1);
+            write!(f, "; the rarest is {letter} in column {column}, ")?;
+            match rarest.words.len() {
+                1 => write!(f, "which no other word has")?,
+                words => write!(f, "which {words} of the puzzle's words have")?,
+            }
+        }
+
+        match self.alternatives.as_slice() {
+            [] => write!(f, "; nothing else could replace it"),
+            alternatives => write!(f, "; it could be swapped for {}", alternatives.join(" or ")),
+        }
+    }
+}
+
+/// Summary statistics over a collection of puzzles, like an archive
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CorpusStats {
+    pub puzzles: usize,
+
+    /// The number of columns in each puzzle
+    pub columns: Distribution,
+
+    /// The number of different letters in each puzzle, summed over its columns
+    pub letters: Distribution,
+
+    /// The number of words spellable from each puzzle
+    pub candidate_words: Distribution,
+
+    /// The steps the search for each puzzle's first solution took
+    pub steps: Distribution,
+
+    /// The number of words in each puzzle's minimal solutions
+    pub minimal_words: Distribution,
+
+    /// The number of minimal solutions of each puzzle
+    pub solutions: Distribution,
+
+    /// The number of columns, across every puzzle, with each letter
+    pub letter_columns: BTreeMap<char, usize>,
+}
+
+/// The spread of one measure over a corpus; all zero for an empty corpus
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Distribution {
+    pub min: u64,
+    pub median: f64,
+    pub mean: f64,
+    pub max: u64,
+}
+
+impl Distribution {
+    pub fn new(mut values: Vec<u64>) -> Self {
+        values.sort_unstable();
+        let (Some(&min), Some(&max)) = (values.first(), values.last()) else {
+            return Self {
+                min: 0,
+                median: 0.0,
+                mean: 0.0,
+                max: 0,
+            };
+        };
+
+        let middle = values.len() / 2;
+        let median = if values.len().is_multiple_of(2) {
+            (values[middle - 1] + values[middle]) as f64 / 2.0
+        } else {
+            values[middle] as f64
+        };
+        let mean = values.iter().sum::<u64>() as f64 / values.len() as f64;
+
+        Self {
+            min,
+            median,
+            mean,
+            max,
+        }
+    }
+}
+
+impl CorpusStats {
+    /// Solves each puzzle once and counts its minimal solutions;
+    /// respects the configuration's limits for each puzzle
+    pub fn new(puzzles: &[Typeshift], config: &SolverConfig) -> Result<Self, SolveError> {
+        let mut columns = Vec::new();
+        let mut letters = Vec::new();
+        let mut candidate_words = Vec::new();
+        let mut steps = Vec::new();
+        let mut minimal_words = Vec::new();
+        let mut solutions = Vec::new();
+        let mut letter_columns = BTreeMap::new();
+
+        for typeshift in puzzles {
+            let (_solution, search_steps) = typeshift.solve_with(config)?;
+            let count = typeshift.count_solutions(config)?;
+
+            let mut puzzle_letters = 0;
+            for column in typeshift.input_columns() {
+                let column: BTreeSet<char> = column.chars().collect();
+                puzzle_letters += column.len();
+                for letter in column {
+                    *letter_columns.entry(letter).or_insert(0) += 1;
+                }
+            }
+
+            columns.push(typeshift.input_columns().len() as u64);
+            letters.push(puzzle_letters as u64);
+            candidate_words.push(typeshift.size() as u64);
+            steps.push(search_steps as u64);
+            minimal_words.push(count.minimal_words as u64);
+            solutions.push(count.solutions);
+        }
+
+        Ok(Self {
+            puzzles: puzzles.len(),
+            columns: Distribution::new(columns),
+            letters: Distribution::new(letters),
+            candidate_words: Distribution::new(candidate_words),
+            steps: Distribution::new(steps),
+            minimal_words: Distribution::new(minimal_words),
+            solutions: Distribution::new(solutions),
+            letter_columns,
+        })
+    }
+}
+
+/// How hard a puzzle is, as a score and the measures it combines
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Difficulty {
+    /// The steps a best-first search is expected to take, from the other measures
+    /// and the default `DifficultyWeights`; higher is harder
+    pub score: f64,
+
+    /// The number of words spellable from the puzzle
+    pub candidate_words: usize,
+
+    /// The number of first words tied for best
+    pub root_branching: usize,
+
+    /// The number of words in a minimal solution
+    pub minimal_words: usize,
+
+    /// The number of minimal solutions
+    pub solutions: usize,
+}
+
+/// How a difficulty score weighs a puzzle's measures: the score is the exponent of
+/// their weighted sum, taking the log of every count but the minimal solution's words
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DifficultyWeights {
+    pub intercept: f64,
+    pub ln_candidate_words: f64,
+    pub ln_root_branching: f64,
+    pub minimal_words: f64,
+    pub ln_solutions: f64,
+}
+
+/// Predicts the steps to a first solution.
+/// The weights are a least-squares fit over the embedded puzzle corpus, where the
+/// predictions rank the puzzles with a Spearman correlation of about 0.68.
+#[cfg(feature = "std")]
+impl Default for DifficultyWeights {
+    fn default() -> Self {
+        Self {
+            intercept: 0.014,
+            ln_candidate_words: 0.220,
+            ln_root_branching: 0.454,
+            minimal_words: 0.312,
+            ln_solutions: 0.085,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl DifficultyWeights {
+    /// The number of weights, counting the intercept
+    pub const LEN: usize = 5;
+
+    /// Scores a rated puzzle with these weights
+    pub fn score(&self, difficulty: &Difficulty) -> f64 {
+        zip(self.to_array(), difficulty.features())
+            .map(|(weight, feature)| weight * feature)
+            .sum::<f64>()
+            .exp()
+    }
+
+    pub fn to_array(self) -> [f64; Self::LEN] {
+        [
+            self.intercept,
+            self.ln_candidate_words,
+            self.ln_root_branching,
+            self.minimal_words,
+            self.ln_solutions,
+        ]
+    }
+
+    pub fn from_array(weights: [f64; Self::LEN]) -> Self {
+        let [intercept, ln_candidate_words, ln_root_branching, minimal_words, ln_solutions] =
+            weights;
+        Self {
+            intercept,
+            ln_candidate_words,
+            ln_root_branching,
+            minimal_words,
+            ln_solutions,
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Difficulty {
+    /// The measures each weight multiplies, in the order of `DifficultyWeights::to_array`
+    pub fn features(&self) -> [f64; DifficultyWeights::LEN] {
+        [
+            1.0,
+            (self.candidate_words as f64).ln(),
+            (self.root_branching as f64).ln(),
+            self.minimal_words as f64,
+            (self.solutions as f64).ln(),
+        ]
+    }
+}
+
+#[cfg(feature = "std")]
+impl Typeshift {
+    /// Rates how hard the puzzle is; counts every minimal solution to do it
+    pub fn difficulty(&self) -> Result<Difficulty, SolveError> {
+        self.difficulty_with(&SolverConfig::default())
+    }
+
+    /// Like `difficulty`, but respecting the configuration's limits
+    pub fn difficulty_with(&self, config: &SolverConfig) -> Result<Difficulty, SolveError> {
+        let count = self.count_solutions(config)?;
+
+        let mut difficulty = Difficulty {
+            score: 0.0,
+            candidate_words: self.size(),
+            root_branching: self.root_branching(),
+            minimal_words: count.minimal_words,
+            solutions: count.solutions as usize,
+        };
+        difficulty.score = DifficultyWeights::default().score(&difficulty);
+
+        Ok(difficulty)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn ambiguity_smoke() {
+        let all_solutions = BTreeSet::from([
+            BTreeSet::from(["above", "basic", "study"]),
+            BTreeSet::from(["above", "basic", "sturdy"]),
+        ]);
+        let ambiguity = Ambiguity::from_solutions(&all_solutions);
+
+        assert!(ambiguity.is_ambiguous());
+        assert_eq!(ambiguity.common_words, BTreeSet::from(["above", "basic"]));
+        assert_eq!(
+            ambiguity.distinguishing_words,
+            BTreeSet::from(["study", "sturdy"])
+        );
+    }
+
+    #[test]
+    fn solution_families() {
+        let all_solutions = BTreeSet::from([
+            BTreeSet::from(["above", "basic", "study"]),
+            BTreeSet::from(["above", "basic", "sturdy"]),
+            BTreeSet::from(["above", "bask", "study"]),
+            BTreeSet::from(["above", "bask", "sturdy"]),
+            BTreeSet::from(["above", "bask", "stud"]),
+        ]);
+        let families = Ambiguity::from_solutions(&all_solutions).families;
+
+        let bask = SolutionFamily {
+            pivot: Some("bask"),
+            solutions: 3,
+            shared_words: BTreeSet::from(["bask"]),
+            varying_words: BTreeSet::from(["stud", "study", "sturdy"]),
+        };
+        let basic = SolutionFamily {
+            pivot: Some("basic"),
+            solutions: 2,
+            shared_words: BTreeSet::from(["basic"]),
+            varying_words: BTreeSet::from(["study", "sturdy"]),
+        };
+        assert_eq!(families, vec![bask, basic]);
+
+        let lone = BTreeSet::from([BTreeSet::from(["above", "basic"])]);
+        let families = Ambiguity::from_solutions(&lone).families;
+        assert_eq!((families[0].pivot, families[0].solutions), (None, 1));
+    }
+
+    #[test]
+    fn bottleneck_letters() {
+        let typeshift = Typeshift::new(include_str!("../files/puzzles/2023-11-16.txt"));
+        let coverage = LetterCoverage::new(&typeshift);
+
+        assert_eq!(coverage.letters.len(), 22);
+        let bottlenecks: Vec<_> = (coverage.bottlenecks().into_iter())
+            .map(|letter| (letter.column, letter.letter, letter.words.clone()))
+            .collect();
+        assert_eq!(bottlenecks, vec![(4, 'c', vec!["basic", "stoic"])]);
+
+        let unusable = LetterCoverage::with_threshold(&Typeshift::new("b\nao\nr\nex"), 0);
+        let bottlenecks = Vec::from_iter(unusable.bottlenecks().iter().map(|l| l.letter));
+        assert_eq!(bottlenecks, vec!['x']);
+    }
+
+    #[test]
+    fn unsolvable_core() {
+        let solvable = Typeshift::new(include_str!("../files/puzzles/2023-11-16.txt"));
+        assert_eq!(UnsolvableCore::new(&solvable), None);
+
+        let unsolvable = Typeshift::new("wsab\nhbta\noesu\ndpive\nlceysx");
+        let expected = UncoveredCell {
+            column: 4,
+            letter: 'x',
+            near_words: vec!["salix"],
+        };
+        assert_eq!(
+            UnsolvableCore::new(&unsolvable).unwrap().cells,
+            vec![expected]
+        );
+    }
+
+    #[test]
+    fn interest() {
+        let typeshift = Typeshift::new(include_str!("../files/puzzles/2023-11-16.txt"));
+        let interest = Interest::new(&typeshift, &SolverConfig::default()).unwrap();
+
+        let expected = Interest {
+            decoy_words: 14,
+            near_solutions: 135,
+            most_shared_columns: 1.6875,
+        };
+        assert_eq!(interest, expected);
+    }
+
+    #[test]
+    fn what_if() {
+        let typeshift = Typeshift::new(include_str!("../files/puzzles/2023-11-16.txt"));
+        let config = SolverConfig::default();
+
+        let change = PuzzleChange::ExcludeWord("stoic".to_string());
+        let what_if = WhatIf::new(&typeshift, change, &config).unwrap();
+        assert_eq!(what_if.minimal_words_after, Some(5));
+        assert_eq!(
+            (what_if.lost.len(), what_if.gained.len(), what_if.kept),
+            (2, 0, 14)
+        );
+        assert!(what_if
+            .lost
+            .iter()
+            .all(|solution| solution.contains("stoic")));
+
+        let change = PuzzleChange::RemoveLetter {
+            column: 4,
+            letter: 'c',
+        };
+        let what_if = WhatIf::new(&typeshift, change, &config).unwrap();
+        assert_eq!(
+            (what_if.lost.len(), what_if.gained.len(), what_if.kept),
+            (16, 37, 0)
+        );
+
+        let unsolvable = Typeshift::new("b\nao\nr\nex");
+        let change = PuzzleChange::RemoveLetter {
+            column: 3,
+            letter: 'x',
+        };
+        let what_if = WhatIf::new(&unsolvable, change, &config).unwrap();
+        let expected = BTreeSet::from([BTreeSet::from(["bare", "bore"])]);
+        assert_eq!(what_if.minimal_words_before, None);
+        assert_eq!(
+            (what_if.minimal_words_after, what_if.gained),
+            (Some(2), expected)
+        );
+    }
+
+    #[test]
+    fn corpus_stats() {
+        let puzzles = [
+            Typeshift::new(include_str!("../files/puzzles/2023-11-16.txt")),
+            Typeshift::new(include_str!("../files/puzzles/2023-11-19.txt")),
+        ];
+        let stats = CorpusStats::new(&puzzles, &SolverConfig::default()).unwrap();
+
+        assert_eq!(stats.puzzles, 2);
+        assert_eq!((stats.columns.min, stats.columns.max), (5, 6));
+        assert_eq!((stats.letters.min, stats.letters.max), (22, 25));
+        assert_eq!(
+            (stats.candidate_words.min, stats.candidate_words.max),
+            (31, 70)
+        );
+        assert_eq!((stats.steps.min, stats.steps.max), (6, 61));
+        assert_eq!((stats.solutions.min, stats.solutions.max), (1, 16));
+        assert_eq!(stats.minimal_words.mean, 5.0);
+        assert_eq!(stats.letter_columns.get(&'e'), Some(&5));
+        assert_eq!(stats.letter_columns.values().sum::<usize>(), 47);
+
+        let empty = CorpusStats::new(&[], &SolverConfig::default()).unwrap();
+        assert_eq!(
+            (empty.puzzles, empty.steps.max, empty.steps.mean),
+            (0, 0, 0.0)
+        );
+    }
+
+    #[test]
+    fn nearest_alternatives() {
+        let typeshift = Typeshift::new(include_str!("../files/puzzles/2023-11-16.txt"));
+        let config = SolverConfig::default();
+        let solution = BTreeSet::from(["above", "basic", "study", "wheel", "whups"]);
+
+        let nearest = NearestAlternative::new(&typeshift, &solution, &config).unwrap();
+        assert_eq!(
+            nearest,
+            Some(NearestAlternative {
+                solution: BTreeSet::from(["above", "basic", "sheel", "study", "whups"]),
+                removed: BTreeSet::from(["wheel"]),
+                added: BTreeSet::from(["sheel"]),
+            })
+        );
+        assert_eq!(nearest.unwrap().to_string(), "swap wheel for sheel");
+
+        let unique = Typeshift::new(include_str!("../files/puzzles/2023-11-19.txt"));
+        let (solution, _steps) = unique.solve_with(&config).unwrap();
+        assert_eq!(
+            NearestAlternative::new(&unique, &solution, &config),
+            Ok(None)
+        );
+    }
+
+    #[test]
+    fn play_feedback() {
+        let typeshift = Typeshift::new(include_str!("../files/puzzles/2023-11-16.txt"));
+        let config = SolverConfig::default();
+
+        let feedback = PlayFeedback::new(&typeshift, &["above"], "stoic", &config).unwrap();
+        assert_eq!(
+            feedback,
+            PlayFeedback {
+                word: "stoic".to_string(),
+                in_minimal_solution: true,
+                best_total_before: 5,
+                best_total_after: 5,
+            }
+        );
+        assert_eq!(
+            feedback.to_string(),
+            "stoic is in a minimal solution; you can still finish in 5 words"
+        );
+
+        let feedback = PlayFeedback::new(&typeshift, &["above"], "abode", &config).unwrap();
+        assert_eq!(
+            (feedback.in_minimal_solution, feedback.extra_words()),
+            (false, 1)
+        );
+        assert_eq!(
+            feedback.to_string(),
+            "abode isn't in any minimal solution; it raised the fewest words you can finish in from 5 to 6"
+        );
+
+        // each word is in a minimal solution, but not the same one
+        let feedback = PlayFeedback::new(&typeshift, &["stoic"], "wasps", &config).unwrap();
+        assert_eq!(
+            (feedback.in_minimal_solution, feedback.extra_words()),
+            (true, 1)
+        );
+
+        assert_eq!(
+            PlayFeedback::new(&typeshift, &["above"], "above", &config),
+            Err(PlayError::AlreadyPlayed("above".into()))
+        );
+    }
+
+    #[test]
+    fn mistake_reports() {
+        let typeshift = Typeshift::new(include_str!("../files/puzzles/2023-11-16.txt"));
+        let config = SolverConfig::default();
+
+        let played = ["above", "stoic", "basic", "study", "wheel", "whups"];
+        let report = MistakeReport::new(&typeshift, &played, &config).unwrap();
+        assert_eq!(report.redundant, ["stoic"]);
+        assert_eq!(
+            report.to_string(),
+            "\
+6 words, 1 more than the fewest possible, 5
+the other words cover every letter of stoic
+drop stoic to save 1 word
+"
+        );
+
+        let played = ["abode", "above", "atopy", "basic", "sheel", "whups"];
+        let report = MistakeReport::new(&typeshift, &played, &config).unwrap();
+        assert_eq!(report.redundant, Vec::<&str>::new());
+        assert_eq!(
+            report.improvement.unwrap().to_string(),
+            "swap abode and atopy for study"
+        );
+
+        let minimal = ["above", "basic", "study", "wheel", "whups"];
+        let report = MistakeReport::new(&typeshift, &minimal, &config).unwrap();
+        assert_eq!((report.extra_words(), report.improvement), (0, None));
+        assert_eq!(
+            MistakeReport::new(&typeshift, &minimal[..4], &config),
+            Err(PlayError::Unfinished { uncovered: 2 })
+        );
+    }
+
+    #[test]
+    fn explaining_solutions() {
+        let typeshift = Typeshift::new(include_str!("../files/puzzles/2023-11-16.txt"));
+        let solution = BTreeSet::from(["above", "basic", "study", "wheel", "whups"]);
+
+        let explanations = WordExplanation::for_solution(&typeshift, &solution);
+        let whups = &explanations[4];
+        assert_eq!(whups.covers, [(3, 'p'), (4, 's')]);
+        assert_eq!(
+            whups.alternatives,
+            ["shops", "steps", "stops", "wasps", "whops"]
+        );
+        assert_eq!(
+            explanations[1].to_string(),
+            "basic is the only word in the solution with b in column 1, a in column 2, \
+             s in column 3, i in column 4, c in column 5; the rarest is c in column 5, \
+             which 2 of the puzzle's words have; nothing else could replace it"
+        );
+        assert_eq!(
+            explanations[3].to_string(),
+            "wheel is the only word in the solution with e in column 3, e in column 4, \
+             l in column 5; the rarest is l in column 5, which 5 of the puzzle's words have; \
+             it could be swapped for sheel or steel"
+        );
+
+        let padded = BTreeSet::from(["above", "basic", "stoic", "study", "wheel", "whups"]);
+        let stoic = &WordExplanation::for_solution(&typeshift, &padded)[2];
+        assert_eq!((stoic.covers.len(), stoic.rarest.as_ref()), (0, None));
+        assert_eq!(
+            stoic.to_string(),
+            "stoic isn't needed; the other words cover its letters"
+        );
+    }
+
+    #[test]
+    fn distributions() {
+        let odd = Distribution::new(vec![9, 1, 2]);
+        assert_eq!((odd.min, odd.median, odd.mean, odd.max), (1, 2.0, 4.0, 9));
+
+        let even = Distribution::new(vec![4, 1, 2, 9]);
+        assert_eq!((even.median, even.mean), (3.0, 4.0));
+    }
+
+    #[test]
+    fn difficulty_orders_puzzles() {
+        let harder = Typeshift::new(include_str!("../files/puzzles/2023-11-19.txt"));
+        let easier = Typeshift::new(include_str!("../files/puzzles/2023-11-26.txt"));
+
+        let harder = harder.difficulty().unwrap();
+        let easier = easier.difficulty().unwrap();
+        assert_eq!((harder.candidate_words, harder.root_branching), (70, 6));
+        assert_eq!((harder.minimal_words, harder.solutions), (5, 1));
+        assert!(harder.score > easier.score);
+        assert_eq!(harder.score.round(), 28.0);
+        assert_eq!(easier.score.round(), 7.0);
+    }
+}
@@ -0,0 +1,115 @@
+//! Text frames of the grid filling in, for terminal playback and GIFs
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::grid::render_grid;
+use crate::trace::{Trace, TraceEvent};
+
+/// One still of an animation: what changed, and the grid as `render_grid` draws it
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct AnimationFrame {
+    pub caption: String,
+    pub grid: String,
+}
+
+impl fmt::Display for AnimationFrame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.caption)?;
+        write!(f, "{}", self.grid)
+    }
+}
+
+/// Frames of the grid as each solution word is added in order, starting from the empty grid
+pub fn solution_frames(
+    columns: &[String],
+    solution: &[String],
+    color: bool,
+) -> Vec<AnimationFrame> {
+    (0..=solution.len())
+        .map(|played| {
+            let caption = match played {
+                0 => "start".to_string(),
+                _ => format!("+ {}", solution[played - 1]),
+            };
+            let grid = render_grid(columns, &solution[..played], color);
+
+            AnimationFrame { caption, grid }
+        })
+        .collect()
+}
+
+/// Frames of the grid for each partial solution a recorded search expanded, in order;
+/// the last one expanded is the solution
+pub fn search_frames(trace: &Trace, color: bool) -> Vec<AnimationFrame> {
+    (trace.events.iter())
+        .filter_map(|event| match event {
+            TraceEvent::Popped { step, words, .. } => {
+                let words: Vec<String> = words.iter().map(|word| word.to_string()).collect();
+                let caption = match words.is_empty() {
+                    true => format!("step {step}: (empty)"),
+                    false => format!("step {step}: {}", words.join(" ")),
+                };
+                let grid = render_grid(&trace.columns, &words, color);
+
+                Some(AnimationFrame { caption, grid })
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::Typeshift;
+
+    #[test]
+    fn solution_animation() {
+        let columns: Vec<String> = ["wsab", "hbta", "oesu", "dpive", "lceys"]
+            .map(String::from)
+            .to_vec();
+        let solution: Vec<String> = ["above", "basic", "study", "wheel", "whups"]
+            .map(String::from)
+            .to_vec();
+
+        let frames = solution_frames(&columns, &solution, false);
+        assert_eq!(frames.len(), 6);
+        assert_eq!(
+            frames[1].to_string(),
+            "\
++ above
+w h O d l
+s B e p c
+A t s i E
+b a u V y
+      e s
+"
+        );
+        assert_eq!(frames[5].grid, render_grid(&columns, &solution, false));
+    }
+
+    #[test]
+    fn search_animation() {
+        let typeshift = Typeshift::new(include_str!("../files/puzzles/2023-11-16.txt"));
+        let mut trace = Trace::new(&typeshift);
+        let (solution, steps) = typeshift
+            .solve_observed(&Default::default(), &mut trace)
+            .unwrap();
+
+        let frames = search_frames(&trace, false);
+        assert_eq!(frames.len(), steps);
+        assert_eq!(frames[0].caption, "step 1: (empty)");
+        let solution: Vec<String> = solution.iter().map(|word| word.to_string()).collect();
+        assert_eq!(
+            frames.last().unwrap().caption,
+            format!("step {steps}: {}", solution.join(" "))
+        );
+    }
+}
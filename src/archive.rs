@@ -0,0 +1,302 @@
+//! Collections of dated puzzles, either embedded in the crate or stored in a directory
+//!
+//! A puzzle directory holds one `<date>.txt` file per puzzle in the plain input format,
+//! with optional `<date>.meta` files of `key: value` lines alongside.
+
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+#[cfg(feature = "std")]
+use std::io;
+#[cfg(feature = "std")]
+use std::path::Path;
+
+use crate::date::Date;
+use crate::puzzle;
+
+/// The puzzles shipped with the crate, from `files/puzzles`
+pub(crate) static EMBEDDED: [(&str, &str); 17] = [
+    (
+        "2023-11-16",
+        include_str!("../files/puzzles/2023-11-16.txt"),
+    ),
+    (
+        "2023-11-17",
+        include_str!("../files/puzzles/2023-11-17.txt"),
+    ),
+    (
+        "2023-11-18",
+        include_str!("../files/puzzles/2023-11-18.txt"),
+    ),
+    (
+        "2023-11-19",
+        include_str!("../files/puzzles/2023-11-19.txt"),
+    ),
+    (
+        "2023-11-20",
+        include_str!("../files/puzzles/2023-11-20.txt"),
+    ),
+    (
+        "2023-11-21",
+        include_str!("../files/puzzles/2023-11-21.txt"),
+    ),
+    (
+        "2023-11-22",
+        include_str!("../files/puzzles/2023-11-22.txt"),
+    ),
+    (
+        "2023-11-23",
+        include_str!("../files/puzzles/2023-11-23.txt"),
+    ),
+    (
+        "2023-11-24",
+        include_str!("../files/puzzles/2023-11-24.txt"),
+    ),
+    (
+        "2023-11-25",
+        include_str!("../files/puzzles/2023-11-25.txt"),
+    ),
+    (
+        "2023-11-26",
+        include_str!("../files/puzzles/2023-11-26.txt"),
+    ),
+    (
+        "2023-11-27",
+        include_str!("../files/puzzles/2023-11-27.txt"),
+    ),
+    (
+        "2023-11-28",
+        include_str!("../files/puzzles/2023-11-28.txt"),
+    ),
+    (
+        "2023-11-29",
+        include_str!("../files/puzzles/2023-11-29.txt"),
+    ),
+    (
+        "2023-11-30",
+        include_str!("../files/puzzles/2023-11-30.txt"),
+    ),
+    (
+        "2023-12-01",
+        include_str!("../files/puzzles/2023-12-01.txt"),
+    ),
+    (
+        "2023-12-05",
+        include_str!("../files/puzzles/2023-12-05.txt"),
+    ),
+];
+
+/// A puzzle and the date it was published
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ArchivedPuzzle {
+    pub date: Date,
+
+    /// The puzzle in the plain input format
+    pub input: String,
+
+    /// Free-form metadata, like the puzzle's source or notes
+    pub metadata: BTreeMap<String, String>,
+}
+
+impl ArchivedPuzzle {
+    /// Identifies the puzzle regardless of its date; see `puzzle::canonical_hash`
+    pub fn canonical_hash(&self) -> u64 {
+        puzzle::canonical_hash(self.input.trim_end().lines().map(str::trim))
+    }
+}
+
+/// A set of puzzles keyed by date
+#[derive(Debug, Clone, Default)]
+pub struct PuzzleArchive {
+    puzzles: BTreeMap<Date, ArchivedPuzzle>,
+}
+
+impl PuzzleArchive {
+    /// The puzzles shipped with the crate
+    pub fn embedded() -> Self {
+        let puzzles = EMBEDDED.iter().map(|&(date, input)| {
+            let date = date.parse().unwrap();
+            let input = input.to_string();
+            let metadata = BTreeMap::new();
+
+            ArchivedPuzzle {
+                date,
+                input,
+                metadata,
+            }
+        });
+
+        Self::from_iter(puzzles)
+    }
+
+    /// Loads every `<date>.txt` puzzle in a directory, along with any `<date>.meta` files
+    #[cfg(feature = "std")]
+    pub fn load_dir(dir: impl AsRef<Path>) -> io::Result<Self> {
+        let mut archive = Self::default();
+
+        for entry in std::fs::read_dir(dir)? {
+            let path = entry?.path();
+            if path.extension().is_none_or(|ext| ext != "txt") {
+                continue;
+            }
+
+            let stem = path.file_stem().unwrap().to_string_lossy();
+            let date = stem
+                .parse()
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            let input = std::fs::read_to_string(&path)?;
+
+            let meta_path = path.with_extension("meta");
+            let metadata = match std::fs::read_to_string(meta_path) {
+                Ok(meta) => parse_metadata(&meta),
+                Err(e) if e.kind() == io::ErrorKind::NotFound => BTreeMap::new(),
+                Err(e) => return Err(e),
+            };
+
+            archive.insert(ArchivedPuzzle {
+                date,
+                input,
+                metadata,
+            });
+        }
+
+        Ok(archive)
+    }
+
+    /// Writes a puzzle (and its metadata, if any) into a puzzle directory
+    #[cfg(feature = "std")]
+    pub fn save_to_dir(puzzle: &ArchivedPuzzle, dir: impl AsRef<Path>) -> io::Result<()> {
+        let path = dir.as_ref().join(format!("{}.txt", puzzle.date));
+        std::fs::write(&path, &puzzle.input)?;
+
+        if !puzzle.metadata.is_empty() {
+            let meta: String = puzzle
+                .metadata
+                .iter()
+                .map(|(key, value)| format!("{key}: {value}\n"))
+                .collect();
+            std::fs::write(path.with_extension("meta"), meta)?;
+        }
+
+        Ok(())
+    }
+
+    /// Adds a puzzle, replacing any existing puzzle for the same date
+    pub fn insert(&mut self, puzzle: ArchivedPuzzle) {
+        self.puzzles.insert(puzzle.date, puzzle);
+    }
+
+    /// The puzzle published on a date, if any
+    pub fn get(&self, date: Date) -> Option<&ArchivedPuzzle> {
+        self.puzzles.get(&date)
+    }
+
+    /// A puzzle of the day for any date: the one published on it if archived, otherwise one
+    /// picked by rotating through the archive, oldest first, a puzzle a day, the same for
+    /// everyone with the same archive. A picked puzzle keeps its own date, which tells the
+    /// two apart. None only if the archive is empty.
+    pub fn for_date(&self, date: Date) -> Option<&ArchivedPuzzle> {
+        if let Some(puzzle) = self.get(date) {
+            return Some(puzzle);
+        }
+        if self.is_empty() {
+            return None;
+        }
+
+        let index = date.to_days().rem_euclid(self.len() as i64) as usize;
+        self.iter().nth(index)
+    }
+
+    /// The oldest archived puzzle with a canonical hash, to recognize a puzzle
+    /// published again under another date
+    pub fn find_by_hash(&self, canonical_hash: u64) -> Option<&ArchivedPuzzle> {
+        self.iter()
+            .find(|puzzle| puzzle.canonical_hash() == canonical_hash)
+    }
+
+    /// Iterates over puzzles, oldest first
+    pub fn iter(&self) -> impl Iterator<Item = &ArchivedPuzzle> + '_ {
+        self.puzzles.values()
+    }
+
+    pub fn len(&self) -> usize {
+        self.puzzles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.puzzles.is_empty()
+    }
+}
+
+impl FromIterator<ArchivedPuzzle> for PuzzleArchive {
+    fn from_iter<I: IntoIterator<Item = ArchivedPuzzle>>(iter: I) -> Self {
+        let mut archive = Self::default();
+        for puzzle in iter {
+            archive.insert(puzzle);
+        }
+
+        archive
+    }
+}
+
+#[cfg(feature = "std")]
+fn parse_metadata(meta: &str) -> BTreeMap<String, String> {
+    meta.lines()
+        .filter_map(|line| line.split_once(':'))
+        .map(|(key, value)| (key.trim().to_string(), value.trim().to_string()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::collections::BTreeSet;
+    use pretty_assertions::assert_eq;
+
+    use crate::Typeshift;
+
+    #[test]
+    fn embedded_matches_puzzle_dir() {
+        let embedded = PuzzleArchive::embedded();
+        let local = PuzzleArchive::load_dir("./files/puzzles").unwrap();
+
+        let embedded: Vec<_> = embedded.iter().collect();
+        let local: Vec<_> = local.iter().collect();
+        assert_eq!(embedded, local);
+    }
+
+    #[test]
+    fn finds_puzzles_by_hash() {
+        let archive = PuzzleArchive::embedded();
+        let hashes: BTreeSet<u64> = archive.iter().map(|p| p.canonical_hash()).collect();
+        assert_eq!(hashes.len(), archive.len());
+
+        let reordered = Typeshift::new("bwsa\nhtab\nuoes\nvdpei\nlceys");
+        let found = archive.find_by_hash(reordered.canonical_hash());
+        assert_eq!(found.map(|p| p.date), Some("2023-11-16".parse().unwrap()));
+    }
+
+    #[test]
+    fn puzzles_for_any_date() {
+        let archive = PuzzleArchive::embedded();
+        let date = |date: &str| date.parse::<Date>().unwrap();
+
+        let published = archive.for_date(date("2023-11-19")).unwrap();
+        assert_eq!(published.date, date("2023-11-19"));
+
+        // dates without a puzzle rotate through the archive, one a day
+        let missing = archive.for_date(date("2024-06-01")).unwrap();
+        assert!(archive.get(date("2024-06-01")).is_none());
+        assert_eq!(archive.for_date(date("2024-06-01")), Some(missing));
+        let next = archive.for_date(date("2024-06-02")).unwrap();
+        let position = |puzzle: &ArchivedPuzzle| archive.iter().position(|p| p == puzzle);
+        assert_eq!(
+            position(next),
+            position(missing).map(|i| (i + 1) % archive.len())
+        );
+        assert_eq!(missing.date, date("2023-11-18"));
+
+        assert!(archive.for_date(date("1969-07-20")).is_some());
+        assert_eq!(PuzzleArchive::default().for_date(date("2024-06-01")), None);
+    }
+}
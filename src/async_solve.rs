@@ -0,0 +1,78 @@
+//! Async wrappers that run searches on tokio's blocking threads
+//!
+//! Dropping a returned future before it completes cancels its search,
+//! so a server whose client disconnects doesn't keep searching for nobody.
+
+use std::collections::BTreeSet;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::error::SolveError;
+use crate::{SolverConfig, Typeshift};
+
+/// Like `Typeshift::solve_with`, without blocking the async runtime
+pub async fn solve_async(
+    typeshift: Arc<Typeshift>,
+    config: SolverConfig,
+) -> Result<(BTreeSet<&'static str>, usize), SolveError> {
+    run_blocking(move |cancel| typeshift.solve_cancellable(&config, cancel)).await
+}
+
+/// Like `Typeshift::solve_all_with`, without blocking the async runtime
+pub async fn solve_all_async(
+    typeshift: Arc<Typeshift>,
+    config: SolverConfig,
+) -> Result<(BTreeSet<BTreeSet<&'static str>>, usize), SolveError> {
+    run_blocking(move |cancel| typeshift.solve_all_cancellable(&config, cancel)).await
+}
+
+/// Sets the cancellation flag when dropped, whether or not the search finished
+struct CancelOnDrop(Arc<AtomicBool>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+async fn run_blocking<T: Send + 'static>(
+    search: impl FnOnce(&AtomicBool) -> T + Send + 'static,
+) -> T {
+    let cancel = Arc::new(AtomicBool::new(false));
+    let guard = CancelOnDrop(cancel.clone());
+
+    let result = tokio::task::spawn_blocking(move || search(&cancel)).await;
+    drop(guard);
+
+    match result {
+        Ok(result) => result,
+        Err(e) => std::panic::resume_unwind(e.into_panic()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn solves_off_the_runtime() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let input = include_str!("../files/puzzles/2023-11-16.txt");
+        let typeshift = Arc::new(Typeshift::new(input));
+
+        let (solution, steps) = runtime
+            .block_on(solve_async(typeshift.clone(), SolverConfig::default()))
+            .unwrap();
+        assert_eq!(steps, 6);
+        assert_eq!(solution, typeshift.find_first_solution().0);
+
+        let (all, _steps) = runtime
+            .block_on(solve_all_async(typeshift, SolverConfig::default()))
+            .unwrap();
+        assert_eq!(all.len(), 4);
+    }
+}
@@ -0,0 +1,133 @@
+//! A shared harness for timing the solver over the standard puzzle corpus,
+//! used by the criterion benches and available to downstream experiments
+//!
+//! ```no_run
+//! use typeshift_solver::bench_support::{corpus, time_corpus};
+//! use typeshift_solver::{SolverConfig, Strategy};
+//!
+//! let greedy = SolverConfig { strategy: Strategy::Greedy, ..Default::default() };
+//! for timing in time_corpus(&corpus(), &greedy, 10) {
+//!     println!("{}: {:?} steps, {:?}", timing.date, timing.steps, timing.median);
+//! }
+//! ```
+
+use std::time::{Duration, Instant};
+
+use crate::archive::EMBEDDED;
+use crate::date::Date;
+use crate::error::SolveError;
+use crate::{SolverConfig, Typeshift};
+
+/// The puzzle that takes the most steps to solve
+pub const SLOWEST: &str = "2023-11-19";
+
+/// A puzzle that solves in few steps
+pub const FAST: &str = "2023-11-16";
+
+/// A puzzle from the corpus, with its input for timing setup as well as searches
+#[derive(Debug, Clone)]
+pub struct CorpusPuzzle {
+    pub date: Date,
+    pub input: &'static str,
+}
+
+/// The standard corpus: every puzzle embedded in the crate, oldest first
+pub fn corpus() -> Vec<CorpusPuzzle> {
+    EMBEDDED
+        .iter()
+        .map(|&(date, input)| CorpusPuzzle {
+            date: date.parse().unwrap(),
+            input,
+        })
+        .collect()
+}
+
+/// The input of a corpus puzzle by its date, like `SLOWEST`.
+///
+/// Panics if the corpus has no puzzle for the date.
+pub fn input(date: &str) -> &'static str {
+    EMBEDDED
+        .iter()
+        .find(|&&(embedded, _input)| embedded == date)
+        .map(|&(_date, input)| input)
+        .unwrap_or_else(|| panic!("no corpus puzzle for {date}"))
+}
+
+/// Builds a puzzle and finds its first solution, as a bench iteration
+pub fn solve(input: &str, config: &SolverConfig) -> Result<usize, SolveError> {
+    let typeshift = Typeshift::new(input);
+    let (_solution, steps) = typeshift.solve_with(config)?;
+
+    Ok(steps)
+}
+
+/// Timings of repeated solves of one puzzle
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Timing {
+    pub date: Date,
+
+    /// The steps each solve took, or the error if it failed
+    pub steps: Result<usize, SolveError>,
+
+    pub min: Duration,
+    pub median: Duration,
+    pub max: Duration,
+}
+
+/// Times `iterations` solves (at least one) of a puzzle with a configuration,
+/// including building the puzzle
+pub fn time_puzzle(puzzle: &CorpusPuzzle, config: &SolverConfig, iterations: usize) -> Timing {
+    let mut durations = Vec::with_capacity(iterations.max(1));
+    let mut steps = Err(SolveError::Unsolvable);
+
+    for _ in 0..iterations.max(1) {
+        let start = Instant::now();
+        steps = solve(puzzle.input, config);
+        durations.push(start.elapsed());
+    }
+    durations.sort();
+
+    Timing {
+        date: puzzle.date,
+        steps,
+        min: durations[0],
+        median: durations[durations.len() / 2],
+        max: durations[durations.len() - 1],
+    }
+}
+
+/// Times every puzzle in a corpus with a configuration
+pub fn time_corpus(
+    corpus: &[CorpusPuzzle],
+    config: &SolverConfig,
+    iterations: usize,
+) -> Vec<Timing> {
+    corpus
+        .iter()
+        .map(|puzzle| time_puzzle(puzzle, config, iterations))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn times_the_corpus() {
+        let corpus = corpus();
+        assert_eq!(corpus.len(), 17);
+        assert_eq!(
+            input(SLOWEST),
+            include_str!("../files/puzzles/2023-11-19.txt")
+        );
+
+        let timings = time_corpus(&corpus[..2], &SolverConfig::default(), 3);
+        let steps: Vec<_> = timings.iter().map(|timing| timing.steps.clone()).collect();
+        assert_eq!(steps, [Ok(6), Ok(9)]);
+        assert!(timings
+            .iter()
+            .all(|t| t.min <= t.median && t.median <= t.max));
+    }
+}
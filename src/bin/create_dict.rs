@@ -1,19 +1,367 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
 use std::fs::File;
 use std::io::{BufWriter, Write};
+use std::path::PathBuf;
 
-const MIN_WORD_LEN: usize = 4;
-const MAX_WORD_LEN: usize = 7;
+use clap::{Parser, Subcommand, ValueEnum};
+use serde::Serialize;
+
+use typeshift_solver::archive::PuzzleArchive;
+use typeshift_solver::date::Date;
+use typeshift_solver::dictionary;
+use typeshift_solver::error::SolveError;
+use typeshift_solver::typeshift::SolutionCount;
+use typeshift_solver::Typeshift;
 
 /// Writes a length-filtered wordnik dictionary as a rust module,
 /// which avoids file io in the main binary.
+#[derive(Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Option<GenCommand>,
+
+    /// Word lists to read, one word per line, optionally in double quotes
+    #[arg(
+        long = "source",
+        global = true,
+        default_value = "./files/wordlist-20210729.txt"
+    )]
+    sources: Vec<PathBuf>,
+
+    /// Word lists of words to leave out, in the same format as sources
+    #[arg(long = "exclude", global = true)]
+    exclusions: Vec<PathBuf>,
+
+    #[arg(long, global = true, default_value_t = 4)]
+    min_word_len: usize,
+
+    #[arg(long, global = true, default_value_t = 7)]
+    max_word_len: usize,
+
+    /// The rust module to write or check
+    #[arg(long, global = true, default_value = "./src/dict.rs")]
+    output: PathBuf,
+}
+
+#[derive(Subcommand, Default)]
+enum GenCommand {
+    /// Write the dictionary module (the default)
+    #[default]
+    Write,
+
+    /// Export the dictionary as data for non-rust tooling
+    Export {
+        /// The file to write
+        path: PathBuf,
+
+        #[arg(long, value_enum, default_value_t = ExportFormat::Json)]
+        format: ExportFormat,
+
+        /// A word list ordered most common first, for frequency ranks
+        #[arg(long)]
+        frequency_list: Option<PathBuf>,
+    },
+
+    /// Write the front-coded dictionary embedded by the compact-dict feature
+    Compact {
+        #[arg(default_value = "./src/dict.bin")]
+        path: PathBuf,
+
+        /// Keep only words that are also in this list, to shrink the dictionary further
+        #[arg(long)]
+        common: Option<PathBuf>,
+    },
+
+    /// Check that the dictionary module on disk, the dictionary compiled into this binary,
+    /// and the sources all agree, without writing anything
+    Check,
+
+    /// Re-solve the archived puzzles with the dictionary from the sources, reporting how
+    /// each changes from the compiled dictionary, without writing anything
+    Impact {
+        /// The puzzle directory
+        #[arg(long, default_value = "files/puzzles")]
+        puzzles: PathBuf,
+    },
+}
+
+#[derive(Clone, Copy, ValueEnum)]
+enum ExportFormat {
+    Json,
+    Csv,
+}
+
+/// An exported dictionary entry
+#[derive(Serialize)]
+struct WordEntry<'a> {
+    word: &'a str,
+    length: usize,
+    letter_counts: BTreeMap<char, usize>,
+    /// The word's 1-based position in the frequency list, if it's there
+    frequency_rank: Option<usize>,
+}
+
+impl<'a> WordEntry<'a> {
+    fn new(word: &'a str, frequency_ranks: &HashMap<String, usize>) -> Self {
+        let mut letter_counts = BTreeMap::new();
+        for ch in word.chars() {
+            *letter_counts.entry(ch).or_default() += 1;
+        }
+
+        Self {
+            word,
+            length: word.len(),
+            letter_counts,
+            frequency_rank: frequency_ranks.get(word).copied(),
+        }
+    }
+
+    fn csv_row(&self) -> String {
+        let letter_counts: Vec<String> = self
+            .letter_counts
+            .iter()
+            .map(|(ch, count)| format!("{ch}={count}"))
+            .collect();
+        let frequency_rank = self
+            .frequency_rank
+            .map(|rank| rank.to_string())
+            .unwrap_or_default();
+
+        format!(
+            "{},{},{},{frequency_rank}\n",
+            self.word,
+            self.length,
+            letter_counts.join(";")
+        )
+    }
+}
+
 fn main() {
-    let dict = load_dictionary();
+    let cli = Cli::parse();
+    let dict = load_dictionary(&cli);
+    let module = render_module(&dict);
+
+    match cli.command.unwrap_or_default() {
+        GenCommand::Write => {
+            let file = File::create(&cli.output).unwrap();
+            let mut file = BufWriter::new(file);
+
+            file.write_all(module.as_bytes()).unwrap();
+        }
+
+        GenCommand::Export {
+            path,
+            format,
+            frequency_list,
+        } => {
+            let frequency_ranks: HashMap<String, usize> = frequency_list
+                .as_ref()
+                .map(read_word_list)
+                .unwrap_or_default()
+                .into_iter()
+                .enumerate()
+                .map(|(i, word)| (word, i + 1))
+                .rev() // keeps the best rank for repeated words
+                .collect();
+            let entries = dict
+                .iter()
+                .map(|word| WordEntry::new(word, &frequency_ranks));
+
+            let out = match format {
+                ExportFormat::Json => {
+                    let entries: Vec<_> = entries.collect();
+                    serde_json::to_string_pretty(&entries).unwrap() + "\n"
+                }
+                ExportFormat::Csv => {
+                    let header = "word,length,letter_counts,frequency_rank\n".to_string();
+                    header + &entries.map(|entry| entry.csv_row()).collect::<String>()
+                }
+            };
+
+            std::fs::write(path, out).unwrap();
+        }
 
+        GenCommand::Compact { path, common } => {
+            let common: Option<BTreeSet<String>> =
+                common.map(|common| read_word_list(&common).into_iter().collect());
+            let words: Vec<&String> = dict
+                .iter()
+                .filter(|word| common.as_ref().is_none_or(|common| common.contains(*word)))
+                .collect();
+
+            std::fs::write(path, dictionary::encode_compact(&words)).unwrap();
+        }
+
+        GenCommand::Check => {
+            let mut problems = Vec::new();
+
+            let on_disk = std::fs::read_to_string(&cli.output).unwrap_or_default();
+            if on_disk != module {
+                problems.push(format!(
+                    "{} is out of date with the sources",
+                    cli.output.display()
+                ));
+            }
+
+            let compiled: BTreeSet<String> =
+                dictionary::words().iter().map(|w| w.to_string()).collect();
+            if compiled != dict {
+                let missing = dict.difference(&compiled).count();
+                let extra = compiled.difference(&dict).count();
+                problems.push(format!(
+                    "the compiled dictionary differs from the sources: {missing} missing, {extra} extra"
+                ));
+            }
+
+            if problems.is_empty() {
+                println!("ok: {} words", dict.len());
+            } else {
+                for problem in problems {
+                    eprintln!("{problem}");
+                }
+                std::process::exit(1);
+            }
+        }
+
+        GenCommand::Impact { puzzles } => {
+            // the candidate dictionary lives as long as the process, like the compiled one
+            let candidate: Vec<&'static str> = dict.into_iter().map(|word| &*word.leak()).collect();
+
+            let archive = PuzzleArchive::load_dir(&puzzles).unwrap();
+            let impacts: Vec<_> = archive
+                .iter()
+                .map(|puzzle| PuzzleImpact::new(puzzle.date, &puzzle.input, &candidate))
+                .filter(PuzzleImpact::changed)
+                .collect();
+
+            for impact in &impacts {
+                println!("{}", impact.report());
+            }
+            println!("{} of {} puzzles changed", impacts.len(), archive.len());
+        }
+    }
+}
+
+/// How a puzzle's words and solutions change with the candidate dictionary
+struct PuzzleImpact {
+    date: Date,
+    words_gained: Vec<&'static str>,
+    words_lost: Vec<&'static str>,
+    before: Result<Solved, SolveError>,
+    after: Result<Solved, SolveError>,
+}
+
+/// A puzzle's first solution, the steps to it, and its count of minimal solutions
+#[derive(PartialEq)]
+struct Solved {
+    answer: BTreeSet<&'static str>,
+    steps: usize,
+    count: SolutionCount,
+}
+
+impl Solved {
+    fn new(typeshift: &Typeshift) -> Result<Self, SolveError> {
+        let config = Default::default();
+        let (answer, steps) = typeshift.solve_with(&config)?;
+        let count = typeshift.count_solutions(&config)?;
+
+        Ok(Self {
+            answer,
+            steps,
+            count,
+        })
+    }
+}
+
+impl PuzzleImpact {
+    fn new(date: Date, input: &str, candidate: &[&'static str]) -> Self {
+        let columns = Typeshift::parse_columns(input).unwrap();
+        let before = Typeshift::from_columns(columns.clone());
+        let after = Typeshift::with_dictionary(columns, candidate);
+
+        let before_words: BTreeSet<_> = before.words().iter().copied().collect();
+        let after_words: BTreeSet<_> = after.words().iter().copied().collect();
+
+        Self {
+            date,
+            words_gained: after_words.difference(&before_words).copied().collect(),
+            words_lost: before_words.difference(&after_words).copied().collect(),
+            before: Solved::new(&before),
+            after: Solved::new(&after),
+        }
+    }
+
+    fn changed(&self) -> bool {
+        !self.words_gained.is_empty() || !self.words_lost.is_empty() || self.before != self.after
+    }
+
+    fn report(&self) -> String {
+        let join = |words: &mut dyn Iterator<Item = &&'static str>| {
+            let words = Vec::from_iter(words.copied());
+            if words.is_empty() {
+                "none".to_string()
+            } else {
+                words.join(" ")
+            }
+        };
+        let mut lines = vec![format!(
+            "{}: words gained: {}; lost: {}",
+            self.date,
+            join(&mut self.words_gained.iter()),
+            join(&mut self.words_lost.iter()),
+        )];
+
+        match (&self.before, &self.after) {
+            (Ok(before), Ok(after)) => {
+                if before.answer != after.answer {
+                    lines.push(format!(
+                        "  answer gained: {}; lost: {}",
+                        join(&mut after.answer.difference(&before.answer)),
+                        join(&mut before.answer.difference(&after.answer)),
+                    ));
+                }
+                if before.steps != after.steps {
+                    let delta = after.steps as isize - before.steps as isize;
+                    lines.push(format!(
+                        "  steps: {} -> {} ({delta:+})",
+                        before.steps, after.steps
+                    ));
+                }
+                if before.count != after.count {
+                    lines.push(format!(
+                        "  minimal solutions: {} of {} words -> {} of {} words",
+                        before.count.solutions,
+                        before.count.minimal_words,
+                        after.count.solutions,
+                        after.count.minimal_words
+                    ));
+                }
+            }
+            (before, after) => {
+                let describe = |solved: &Result<Solved, SolveError>| match solved {
+                    Ok(solved) => join(&mut solved.answer.iter()),
+                    Err(e) => e.to_string(),
+                };
+                lines.push(format!(
+                    "  answer: {} -> {}",
+                    describe(before),
+                    describe(after)
+                ));
+            }
+        }
+
+        lines.join("\n")
+    }
+}
+
+/// Renders the dictionary as a rust module
+fn render_module(dict: &BTreeSet<String>) -> String {
     let mut buf = String::new();
     buf.push_str("//! THIS IS A GENERATED FILE\n");
     buf.push_str("//! Do not edit it directly; see src/bin/create_dict.rs\n\n");
 
-    buf.push_str("/// The reduced wordnik dictionary\n");
+    // sorted and deduplicated by the BTreeSet; lookups binary search on this
+    buf.push_str("/// The reduced wordnik dictionary, sorted and deduplicated\n");
     buf.push_str("pub static DICT: [&str; ");
     buf.push_str(&dict.len().to_string());
     buf.push_str("] = [\n");
@@ -23,18 +371,29 @@ fn main() {
     }
     buf.push_str("];\n");
 
-    let file = File::create("./src/dict.rs").unwrap();
-    let mut file = BufWriter::new(file);
+    buf
+}
+
+/// Merges the sources, minus exclusions, keeping only words within the length bounds
+fn load_dictionary(cli: &Cli) -> BTreeSet<String> {
+    let excluded: BTreeSet<String> = cli.exclusions.iter().flat_map(read_word_list).collect();
 
-    file.write_all(buf.as_bytes()).unwrap();
+    cli.sources
+        .iter()
+        .flat_map(read_word_list)
+        .filter(|w| w.len() >= cli.min_word_len && w.len() <= cli.max_word_len)
+        .filter(|w| !excluded.contains(w))
+        .collect()
 }
 
-fn load_dictionary() -> Vec<&'static str> {
-    let file = include_str!("../../files/wordlist-20210729.txt");
+fn read_word_list(path: &PathBuf) -> Vec<String> {
+    let file = std::fs::read_to_string(path).unwrap();
 
     file.lines()
-        .map(|l| l.strip_prefix('"').unwrap())
-        .map(|l| l.strip_suffix('"').unwrap())
-        .filter(|w| w.len() >= MIN_WORD_LEN && w.len() <= MAX_WORD_LEN)
+        .map(|l| l.trim())
+        .map(|l| l.strip_prefix('"').unwrap_or(l))
+        .map(|l| l.strip_suffix('"').unwrap_or(l))
+        .filter(|l| !l.is_empty())
+        .map(String::from)
         .collect()
 }
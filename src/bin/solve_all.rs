@@ -1,25 +1,201 @@
+use std::fmt;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use clap::Parser;
+use serde::{Deserialize, Serialize};
+
+use typeshift_solver::archive::PuzzleArchive;
+use typeshift_solver::report::OutputFormat;
 use typeshift_solver::*;
 
 /// A helper for looking at backtracking performance over all snapshots
-fn main() {
-    let dir = std::fs::read_dir("./files/puzzles").unwrap();
+#[derive(Parser)]
+struct Cli {
+    #[arg(long, value_enum, default_value_t)]
+    format: OutputFormat,
+
+    /// Write the results as a JSON baseline for later comparison
+    #[arg(long, value_name = "PATH")]
+    write_baseline: Option<PathBuf>,
+
+    /// Compare the results against a previously written baseline
+    #[arg(long, value_name = "PATH")]
+    compare: Option<PathBuf>,
+
+    /// The percentage increase in steps over the baseline tolerated before flagging a regression
+    #[arg(long, default_value_t = 0.0, requires = "compare")]
+    threshold: f64,
+}
+
+/// The summary of one puzzle in the corpus
+#[derive(Serialize, Deserialize)]
+struct PuzzleSummary {
+    name: String,
+    size: usize,
+    steps: usize,
+    solution: Vec<String>,
+    solutions: usize,
+    solve_ms: f64,
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(transparent)]
+struct CorpusSummary(Vec<PuzzleSummary>);
+
+impl fmt::Display for CorpusSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for puzzle in &self.0 {
+            let PuzzleSummary {
+                name,
+                size,
+                steps,
+                solutions,
+                ..
+            } = puzzle;
+
+            writeln!(
+                f,
+                "{name}\n  size: {size}\n  steps: {steps}\n  solutions: {solutions}"
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// A puzzle whose results differ from the baseline
+#[derive(Serialize)]
+struct Difference {
+    name: String,
+    baseline_steps: usize,
+    steps: usize,
+    /// Whether the step increase exceeds the threshold
+    regressed: bool,
+    solution_changed: bool,
+}
+
+#[derive(Serialize)]
+struct Comparison {
+    differences: Vec<Difference>,
+    /// Puzzles in the baseline that were not solved in this run
+    missing: Vec<String>,
+    baseline_total_steps: usize,
+    total_steps: usize,
+}
 
-    let mut puzzles = Vec::new();
-    for entry in dir {
-        let path = entry.unwrap().path();
-        let input = std::fs::read_to_string(&path).unwrap();
-        let file_name = path.file_stem().unwrap().to_string_lossy().to_string();
+impl Comparison {
+    fn new(baseline: &CorpusSummary, current: &CorpusSummary, threshold: f64) -> Self {
+        let mut differences = Vec::new();
+        let mut missing = Vec::new();
 
-        puzzles.push((file_name, input));
+        for old in &baseline.0 {
+            let Some(new) = current.0.iter().find(|p| p.name == old.name) else {
+                missing.push(old.name.clone());
+                continue;
+            };
+
+            let solution_changed = old.solution != new.solution;
+            if old.steps == new.steps && !solution_changed {
+                continue;
+            }
+
+            let allowed = old.steps as f64 * (1.0 + threshold / 100.0);
+            differences.push(Difference {
+                name: new.name.clone(),
+                baseline_steps: old.steps,
+                steps: new.steps,
+                regressed: new.steps as f64 > allowed,
+                solution_changed,
+            });
+        }
+
+        Self {
+            differences,
+            missing,
+            baseline_total_steps: baseline.0.iter().map(|p| p.steps).sum(),
+            total_steps: current.0.iter().map(|p| p.steps).sum(),
+        }
+    }
+
+    fn regressed(&self) -> bool {
+        self.differences.iter().any(|d| d.regressed)
     }
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for diff in &self.differences {
+            let flag = if diff.regressed {
+                "REGRESSED"
+            } else {
+                "changed"
+            };
+            write!(
+                f,
+                "{}: {flag} steps {} -> {}",
+                diff.name, diff.baseline_steps, diff.steps
+            )?;
+            if diff.solution_changed {
+                write!(f, " (different first solution)")?;
+            }
+            writeln!(f)?;
+        }
+
+        for name in &self.missing {
+            writeln!(f, "{name}: missing from this run")?;
+        }
 
-    for (name, input) in puzzles {
-        let typeshift = Typeshift::new(&input);
+        writeln!(
+            f,
+            "total steps: {} -> {}",
+            self.baseline_total_steps, self.total_steps
+        )
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let archive = PuzzleArchive::load_dir("./files/puzzles").unwrap();
+
+    let mut summaries = Vec::new();
+    for puzzle in archive.iter() {
+        let name = puzzle.date.to_string();
+        let start = Instant::now();
+        let typeshift = Typeshift::new(&puzzle.input);
         let size = typeshift.size();
-        let (_first_solution, steps) = typeshift.find_first_solution();
-        let (all_solutions, _all_steps) = typeshift.find_all_solutions();
-        let total_solutions = all_solutions.len();
+        let (first_solution, steps) = typeshift.find_first_solution();
+        let solve_ms = start.elapsed().as_secs_f64() * 1000.0;
+        let count = typeshift.count_solutions(&Default::default()).unwrap();
+        let solutions = count.solutions as usize;
+
+        summaries.push(PuzzleSummary {
+            name,
+            size,
+            steps,
+            solution: first_solution.iter().map(|w| w.to_string()).collect(),
+            solutions,
+            solve_ms,
+        });
+    }
+    let summary = CorpusSummary(summaries);
+
+    if let Some(path) = &cli.write_baseline {
+        let json = serde_json::to_string_pretty(&summary).unwrap();
+        std::fs::write(path, json).unwrap();
+    }
+
+    let Some(path) = &cli.compare else {
+        print!("{}", cli.format.render(&summary));
+        return;
+    };
+
+    let baseline = std::fs::read_to_string(path).unwrap();
+    let baseline: CorpusSummary = serde_json::from_str(&baseline).unwrap();
+    let comparison = Comparison::new(&baseline, &summary, cli.threshold);
+    print!("{}", cli.format.render(&comparison));
 
-        println!("{name}\n  size: {size}\n  steps: {steps}\n  solutions: {total_solutions}");
+    if comparison.regressed() {
+        std::process::exit(1);
     }
 }
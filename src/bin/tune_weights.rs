@@ -0,0 +1,163 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use clap::Parser;
+use serde::Serialize;
+
+use typeshift_solver::archive::PuzzleArchive;
+use typeshift_solver::report::OutputFormat;
+use typeshift_solver::*;
+
+/// A grid search over weights for the parts of the solver's rankings,
+/// for the weight set that solves every archived puzzle in the fewest total steps
+/// without finding a longer solution than the usual ranking
+#[derive(Parser)]
+struct Cli {
+    #[arg(long, value_enum, default_value_t)]
+    format: OutputFormat,
+
+    /// The puzzle directory
+    #[arg(long, default_value = "files/puzzles")]
+    dir: PathBuf,
+
+    /// The weights to try for each new letter a word covers
+    #[arg(long, value_delimiter = ',', default_values_t = [1, 10, 100, 1000])]
+    new_letters: Vec<i64>,
+
+    /// The weights to try for the count of a word's rarest letter
+    #[arg(long, value_delimiter = ',', default_values_t = [0, 1, 10])]
+    min_char_freq: Vec<i64>,
+
+    /// The weights to try for each letter a partial solution uses more than once
+    #[arg(long, value_delimiter = ',', default_values_t = [1, 10, 100])]
+    overlaps: Vec<i64>,
+
+    /// The weights to try for each word in a partial solution
+    #[arg(long, value_delimiter = ',', default_values_t = [0, 1, 10])]
+    depth: Vec<i64>,
+
+    /// Give up on a weight set once a puzzle takes this many times
+    /// the steps it takes with the usual ranking
+    #[arg(long, default_value_t = 10)]
+    max_factor: usize,
+}
+
+/// The solutions' sizes and steps with the usual ranking
+struct Baseline(Vec<(Typeshift, usize, usize)>);
+
+impl Baseline {
+    fn new(archive: &PuzzleArchive) -> Self {
+        let puzzles = (archive.iter())
+            .map(|puzzle| {
+                let typeshift = Typeshift::new(&puzzle.input);
+                let (solution, steps) = typeshift.find_first_solution();
+                (typeshift, solution.len(), steps)
+            })
+            .collect();
+
+        Self(puzzles)
+    }
+
+    fn total_steps(&self) -> usize {
+        self.0.iter().map(|(_, _, steps)| steps).sum()
+    }
+
+    /// The total steps with a weight set, or None if it took too many steps
+    /// or found a longer solution
+    fn total_steps_with(&self, weights: RankWeights, max_factor: usize) -> Option<usize> {
+        let mut total_steps = 0;
+        for (typeshift, words, steps) in &self.0 {
+            let config = SolverConfig {
+                max_steps: Some(steps * max_factor),
+                weights: Some(weights),
+                ..Default::default()
+            };
+            let (solution, steps) = typeshift.solve_with(&config).ok()?;
+            if solution.len() > *words {
+                return None;
+            }
+            total_steps += steps;
+        }
+
+        Some(total_steps)
+    }
+}
+
+#[derive(Serialize)]
+struct Tuning {
+    default_steps: usize,
+    best: Option<TunedWeights>,
+    tried: usize,
+    rejected: usize,
+}
+
+#[derive(Serialize)]
+struct TunedWeights {
+    weights: RankWeights,
+    steps: usize,
+}
+
+impl fmt::Display for Tuning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "usual ranking: {} steps", self.default_steps)?;
+        match &self.best {
+            Some(TunedWeights { weights, steps }) => {
+                let RankWeights {
+                    new_letters,
+                    min_char_freq,
+                    overlaps,
+                    depth,
+                } = weights;
+                writeln!(
+                    f,
+                    "best weights: new_letters {new_letters}, min_char_freq {min_char_freq}, \
+                     overlaps {overlaps}, depth {depth}: {steps} steps"
+                )?;
+            }
+            None => writeln!(f, "no weight set solved every puzzle")?,
+        }
+        writeln!(
+            f,
+            "tried {} weight sets; {} gave up or found longer solutions",
+            self.tried, self.rejected
+        )
+    }
+}
+
+fn main() {
+    let cli = Cli::parse();
+    let archive = PuzzleArchive::load_dir(&cli.dir).unwrap();
+    let baseline = Baseline::new(&archive);
+
+    let mut tuning = Tuning {
+        default_steps: baseline.total_steps(),
+        best: None,
+        tried: 0,
+        rejected: 0,
+    };
+    for &new_letters in &cli.new_letters {
+        for &min_char_freq in &cli.min_char_freq {
+            for &overlaps in &cli.overlaps {
+                for &depth in &cli.depth {
+                    let weights = RankWeights {
+                        new_letters,
+                        min_char_freq,
+                        overlaps,
+                        depth,
+                    };
+                    tuning.tried += 1;
+
+                    let Some(steps) = baseline.total_steps_with(weights, cli.max_factor) else {
+                        tuning.rejected += 1;
+                        continue;
+                    };
+                    if tuning.best.as_ref().is_none_or(|best| steps < best.steps) {
+                        tuning.best = Some(TunedWeights { weights, steps });
+                    }
+                }
+            }
+        }
+    }
+
+    print!("{}", cli.format.render(&tuning));
+}
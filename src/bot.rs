@@ -0,0 +1,144 @@
+//! Computer opponents that play a puzzle word by word, for versus modes
+//!
+//! The solver finds a short solution at once; a bot instead plays one word at a time,
+//! from the solver's ranking of next words, and can be made to play worse by passing over
+//! the words it likes best and by looking less far ahead.
+
+use alloc::vec::Vec;
+
+use crate::error::{PlayError, SolveError};
+use crate::game::GameState;
+use crate::random::SplitMix64;
+use crate::Typeshift;
+
+/// How well a bot plays. The default never passes over its favorite word
+/// and plays the solver's ranking as it is.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BotSkill {
+    /// The chance, from 0 to 1, of passing over the word it likes best for the next one down;
+    /// it can pass over several in a row
+    pub mistake_chance: f64,
+
+    /// How many more words it follows the solver's ranking for after each word it considers,
+    /// preferring the words that leave the fewest letters uncovered after those;
+    /// with 0 it goes by the ranking alone
+    pub lookahead: usize,
+}
+
+/// A computer opponent of some skill, playing repeatably for the same seed
+pub struct Bot {
+    skill: BotSkill,
+    rng: SplitMix64,
+}
+
+impl Bot {
+    pub fn new(skill: BotSkill, seed: u64) -> Self {
+        Self {
+            skill,
+            rng: SplitMix64(seed),
+        }
+    }
+
+    /// Picks the word to play after the played words,
+    /// or None once they cover every letter;
+    /// fails as unsolvable when no word covers anything new
+    pub fn next_word(
+        &mut self,
+        typeshift: &Typeshift,
+        played: &[&str],
+    ) -> Result<Option<&'static str>, PlayError> {
+        let mut game = GameState::new(typeshift);
+        for word in played {
+            game.play(word)?;
+        }
+        if game.is_solved() {
+            return Ok(None);
+        }
+
+        // the words that cover something new, with the letters left after looking ahead;
+        // the sort is stable, so ties keep the solver's order
+        let mut candidates = Vec::new();
+        for word in typeshift.ranked_words(game.played())? {
+            let mut ahead = game.clone();
+            if ahead.play(word)? == 0 {
+                continue;
+            }
+            for _ in 0..self.skill.lookahead {
+                let Some(&next) = typeshift.ranked_words(ahead.played())?.first() else {
+                    break;
+                };
+                if ahead.is_solved() {
+                    break;
+                }
+                ahead.play(next)?;
+            }
+            candidates.push((word, ahead.uncovered().len()));
+        }
+        if candidates.is_empty() {
+            return Err(PlayError::Solve(SolveError::Unsolvable));
+        }
+        candidates.sort_by_key(|&(_word, uncovered)| uncovered);
+
+        let mut choice = 0;
+        while choice + 1 < candidates.len() && self.rng.unit() < self.skill.mistake_chance {
+            choice += 1;
+        }
+
+        Ok(Some(candidates[choice].0))
+    }
+
+    /// Plays a whole game of the puzzle, returning the words in the order played
+    pub fn play(&mut self, typeshift: &Typeshift) -> Result<Vec<&'static str>, PlayError> {
+        let mut played = Vec::new();
+        while let Some(word) = self.next_word(typeshift, &played)? {
+            played.push(word);
+        }
+
+        Ok(played)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn bot_games() {
+        let typeshift = Typeshift::new(include_str!("../files/puzzles/2023-11-16.txt"));
+
+        let played = Bot::new(BotSkill::default(), 0).play(&typeshift).unwrap();
+        assert_eq!(played, ["basic", "above", "study", "wheel", "whups"]);
+
+        let skill = BotSkill {
+            mistake_chance: 0.5,
+            lookahead: 0,
+        };
+        let sloppy = Bot::new(skill, 7).play(&typeshift).unwrap();
+        assert_eq!(
+            sloppy,
+            ["above", "basic", "steds", "wheel", "atopy", "study"]
+        );
+        assert_eq!(Bot::new(skill, 7).play(&typeshift).unwrap(), sloppy);
+
+        let skill = BotSkill {
+            mistake_chance: 0.0,
+            lookahead: 2,
+        };
+        let careful = Bot::new(skill, 0).play(&typeshift).unwrap();
+        assert_eq!(careful, played);
+
+        let finished = Bot::new(skill, 0).next_word(&typeshift, &played).unwrap();
+        assert_eq!(finished, None);
+    }
+
+    #[test]
+    fn unsolvable_bot_game() {
+        let typeshift = Typeshift::new("wsabq\nhbta\noesu\ndpive\nlceys");
+
+        let played = Bot::new(BotSkill::default(), 0).play(&typeshift);
+        assert_eq!(played, Err(PlayError::Solve(SolveError::Unsolvable)));
+    }
+}
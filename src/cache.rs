@@ -0,0 +1,158 @@
+//! Caches of first solutions, so repeat solves of a puzzle skip the search
+//!
+//! A cache is consulted by `Typeshift::solve_with` and `solve_cancellable`
+//! when set on the `SolverConfig`. The memory and file caches need the `std` feature.
+//! The file cache has one tab-separated line per solve:
+//! the key, the step count, and the solution's words separated by spaces.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+#[cfg(feature = "std")]
+use std::fs::OpenOptions;
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+#[cfg(feature = "std")]
+use std::path::{Path, PathBuf};
+#[cfg(feature = "std")]
+use std::sync::Mutex;
+
+/// A solution as stored in a cache
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CachedSolution {
+    pub words: Vec<String>,
+
+    /// The steps the original search took
+    pub steps: usize,
+}
+
+/// A store of first solutions, keyed by `Typeshift::cache_key`.
+/// Shared between threads, so implementations synchronize internally.
+pub trait SolutionCache: Send + Sync + fmt::Debug {
+    fn get(&self, key: &str) -> Option<CachedSolution>;
+
+    fn put(&self, key: &str, solution: CachedSolution);
+}
+
+/// A cache for the life of the process
+#[cfg(feature = "std")]
+#[derive(Debug, Default)]
+pub struct MemoryCache {
+    solutions: Mutex<HashMap<String, CachedSolution>>,
+}
+
+#[cfg(feature = "std")]
+impl MemoryCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+#[cfg(feature = "std")]
+impl SolutionCache for MemoryCache {
+    fn get(&self, key: &str) -> Option<CachedSolution> {
+        self.solutions.lock().unwrap().get(key).cloned()
+    }
+
+    fn put(&self, key: &str, solution: CachedSolution) {
+        self.solutions
+            .lock()
+            .unwrap()
+            .insert(key.to_string(), solution);
+    }
+}
+
+/// A cache that persists to a file, for sharing solutions between processes.
+/// The file is read once when opened; new solutions are appended as they're put.
+#[cfg(feature = "std")]
+#[derive(Debug)]
+pub struct FileCache {
+    path: PathBuf,
+    memory: MemoryCache,
+}
+
+#[cfg(feature = "std")]
+impl FileCache {
+    /// Opens a cache file, which doesn't need to exist yet.
+    /// Malformed lines are skipped.
+    pub fn open(path: impl AsRef<Path>) -> io::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let memory = MemoryCache::new();
+
+        match std::fs::read_to_string(&path) {
+            Ok(contents) => {
+                for (key, solution) in contents.lines().filter_map(parse_line) {
+                    memory.put(key, solution);
+                }
+            }
+            Err(e) if e.kind() == io::ErrorKind::NotFound => {}
+            Err(e) => return Err(e),
+        }
+
+        Ok(Self { path, memory })
+    }
+
+    fn append(&self, key: &str, solution: &CachedSolution) -> io::Result<()> {
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+
+        writeln!(
+            file,
+            "{key}\t{}\t{}",
+            solution.steps,
+            solution.words.join(" ")
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl SolutionCache for FileCache {
+    fn get(&self, key: &str) -> Option<CachedSolution> {
+        self.memory.get(key)
+    }
+
+    /// Failing to write the file only loses the solution for other processes,
+    /// so write errors are ignored.
+    fn put(&self, key: &str, solution: CachedSolution) {
+        let _ = self.append(key, &solution);
+        self.memory.put(key, solution);
+    }
+}
+
+#[cfg(feature = "std")]
+fn parse_line(line: &str) -> Option<(&str, CachedSolution)> {
+    let mut fields = line.split('\t');
+    let key = fields.next()?;
+    let steps = fields.next()?.parse().ok()?;
+    let words = fields.next()?.split(' ').map(String::from).collect();
+
+    Some((key, CachedSolution { words, steps }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn file_cache_round_trip() {
+        let path = std::env::temp_dir().join(format!("typeshift-cache-{}", std::process::id()));
+        let solution = CachedSolution {
+            words: vec!["above".to_string(), "basic".to_string()],
+            steps: 8,
+        };
+
+        let cache = FileCache::open(&path).unwrap();
+        assert_eq!(cache.get("key"), None);
+        cache.put("key", solution.clone());
+
+        let reopened = FileCache::open(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(reopened.get("key"), Some(solution));
+    }
+}
@@ -0,0 +1,192 @@
+//! Fitting the difficulty score to how hard people find puzzles
+//!
+//! Recorded human results are CSV lines of the puzzle's hash (as in history files),
+//! the seconds the solve took, and the number of words used.
+
+use std::collections::hash_map::{Entry, HashMap};
+use std::io;
+use std::path::Path;
+
+use crate::analysis::{Difficulty, DifficultyWeights};
+use crate::error::CalibrateError;
+use crate::history;
+use crate::{SolverConfig, Typeshift};
+
+/// One person's solve of a puzzle
+#[derive(Debug, Clone, PartialEq)]
+pub struct HumanResult {
+    /// Identifies the puzzle, as `history::puzzle_hash` does
+    pub puzzle_hash: String,
+
+    /// How long the solve took
+    pub seconds: f64,
+
+    /// The number of words the person used
+    pub words_used: usize,
+}
+
+impl HumanResult {
+    fn parse_line(line: &str) -> Option<Self> {
+        let mut fields = line.split(',').map(str::trim);
+        let puzzle_hash = fields.next()?.to_lowercase();
+        let seconds = fields.next()?.parse().ok().filter(|&s: &f64| s > 0.0)?;
+        let words_used = fields.next()?.parse().ok().filter(|&n| n > 0)?;
+
+        Some(Self {
+            puzzle_hash,
+            seconds,
+            words_used,
+        })
+    }
+
+    /// How hard the person found the puzzle: the log of the time they took,
+    /// as if it grew in proportion to the words they used beyond the fewest possible
+    fn ln_difficulty(&self, minimal_words: usize) -> f64 {
+        (self.seconds * self.words_used as f64 / minimal_words as f64).ln()
+    }
+}
+
+/// Reads recorded results, one per line; malformed lines, like a header, are skipped
+pub fn parse_results(csv: &str) -> Vec<HumanResult> {
+    csv.lines().filter_map(HumanResult::parse_line).collect()
+}
+
+/// Reads a file of recorded results; see `parse_results`
+pub fn load_results(path: impl AsRef<Path>) -> io::Result<Vec<HumanResult>> {
+    Ok(parse_results(&std::fs::read_to_string(path)?))
+}
+
+/// Fits difficulty weights so that scores predict how long people take,
+/// in seconds, from the results for the given puzzles.
+/// Results for other puzzles are ignored.
+pub fn calibrate(
+    results: &[HumanResult],
+    puzzles: &[Typeshift],
+    config: &SolverConfig,
+) -> Result<DifficultyWeights, CalibrateError> {
+    let puzzles: HashMap<String, &Typeshift> = (puzzles.iter())
+        .map(|typeshift| (history::puzzle_hash(typeshift.input_columns()), typeshift))
+        .collect();
+
+    let mut rated: HashMap<&str, Difficulty> = HashMap::new();
+    let mut rows = Vec::new();
+    for result in results {
+        let Some(typeshift) = puzzles.get(&result.puzzle_hash) else {
+            continue;
+        };
+        let difficulty = match rated.entry(&result.puzzle_hash) {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(typeshift.difficulty_with(config)?),
+        };
+        let target = result.ln_difficulty(difficulty.minimal_words);
+        rows.push((difficulty.features(), target));
+    }
+
+    if rows.len() < DifficultyWeights::LEN {
+        return Err(CalibrateError::TooFewResults {
+            matched: rows.len(),
+        });
+    }
+
+    least_squares(&rows)
+        .map(DifficultyWeights::from_array)
+        .ok_or(CalibrateError::Underdetermined)
+}
+
+const N: usize = DifficultyWeights::LEN;
+
+/// Solves the normal equations for the weights that best predict each row's target,
+/// or None if they have no single solution
+fn least_squares(rows: &[([f64; N], f64)]) -> Option<[f64; N]> {
+    // the augmented matrix [XᵀX | Xᵀy]
+    let mut matrix = [[0.0; N + 1]; N];
+    for (features, target) in rows {
+        for i in 0..N {
+            for j in 0..N {
+                matrix[i][j] += features[i] * features[j];
+            }
+            matrix[i][N] += features[i] * target;
+        }
+    }
+
+    // Gaussian elimination with partial pivoting
+    for col in 0..N {
+        let pivot =
+            (col..N).max_by(|&a, &b| matrix[a][col].abs().total_cmp(&matrix[b][col].abs()))?;
+        if matrix[pivot][col].abs() < 1e-9 {
+            return None;
+        }
+        matrix.swap(col, pivot);
+
+        let pivot_row = matrix[col];
+        for (index, row) in matrix.iter_mut().enumerate() {
+            if index != col {
+                let factor = row[col] / pivot_row[col];
+                for (value, pivot_value) in row.iter_mut().zip(pivot_row) {
+                    *value -= factor * pivot_value;
+                }
+            }
+        }
+    }
+
+    Some(std::array::from_fn(|i| matrix[i][N] / matrix[i][i]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::archive::PuzzleArchive;
+
+    #[test]
+    fn parsing_results() {
+        let csv = "puzzle_hash,seconds,words_used\nABC123, 90.5, 6\nabc123,0,5\nabc123,60\n";
+
+        assert_eq!(
+            parse_results(csv),
+            [HumanResult {
+                puzzle_hash: "abc123".to_string(),
+                seconds: 90.5,
+                words_used: 6,
+            }]
+        );
+    }
+
+    #[test]
+    fn calibrating_weights() {
+        let puzzles: Vec<_> = (PuzzleArchive::embedded().iter())
+            .map(|puzzle| Typeshift::new(&puzzle.input))
+            .collect();
+        let config = SolverConfig::default();
+
+        // people who take exactly as long as some weights predict, when using the fewest words
+        let expected = DifficultyWeights::from_array([2.0, 0.5, 0.25, 0.1, -0.2]);
+        let results: Vec<_> = (puzzles.iter())
+            .map(|typeshift| {
+                let difficulty = typeshift.difficulty_with(&config).unwrap();
+                HumanResult {
+                    puzzle_hash: history::puzzle_hash(typeshift.input_columns()),
+                    seconds: expected.score(&difficulty),
+                    words_used: difficulty.minimal_words,
+                }
+            })
+            .collect();
+
+        let fitted = calibrate(&results, &puzzles, &config).unwrap();
+        for (fitted, expected) in fitted.to_array().into_iter().zip(expected.to_array()) {
+            assert!((fitted - expected).abs() < 1e-6, "{fitted} != {expected}");
+        }
+
+        assert_eq!(
+            calibrate(&results[..3], &puzzles, &config),
+            Err(CalibrateError::TooFewResults { matched: 3 })
+        );
+        let one_puzzle = vec![results[0].clone(); 5];
+        assert_eq!(
+            calibrate(&one_puzzle, &puzzles, &config),
+            Err(CalibrateError::Underdetermined)
+        );
+    }
+}
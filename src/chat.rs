@@ -0,0 +1,104 @@
+//! Rendering solutions for chat apps like Discord and Slack
+//!
+//! The puzzle grid goes in a code block so it stays monospaced,
+//! the answer words are spoiler-tagged, and an emoji grid shows how the
+//! solution covers the puzzle without giving the words away.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::grid::{covering_words, render_grid};
+
+/// A letter covered by exactly one word
+const COVERED: &str = "🟩";
+
+/// A letter covered by more than one word
+const OVERLAPPED: &str = "🟨";
+
+/// A letter not covered by any word
+const UNUSED: &str = "⬛";
+
+/// A gap below a column shorter than others
+const EMPTY: &str = "⬜";
+
+/// Renders a puzzle and its solution as a chat message in Markdown,
+/// with each word in Discord's `||spoiler||` tags.
+pub fn render_chat(columns: &[String], words: &[String]) -> String {
+    let letters: usize = columns.iter().map(|col| col.len()).sum();
+    let mut covered = 0;
+    let mut overlapped = 0;
+    let mut coverage = String::new();
+
+    let height = columns.iter().map(|col| col.len()).max().unwrap_or(0);
+    for row in 0..height {
+        let mut cells = Vec::with_capacity(columns.len());
+        for (col, letters) in columns.iter().enumerate() {
+            let Some(ch) = letters.chars().nth(row) else {
+                cells.push(EMPTY);
+                continue;
+            };
+
+            let cell = match covering_words(words, col, ch).len() {
+                0 => UNUSED,
+                1 => COVERED,
+                _ => OVERLAPPED,
+            };
+            covered += usize::from(cell != UNUSED);
+            overlapped += usize::from(cell == OVERLAPPED);
+            cells.push(cell);
+        }
+
+        coverage.push_str(&cells.concat());
+        coverage.push('\n');
+    }
+
+    let grid = render_grid(columns, &[], false).to_uppercase();
+    let spoilers: Vec<String> = words.iter().map(|word| format!("||{word}||")).collect();
+
+    let mut out = format!("**Typeshift** in {} words\n", words.len());
+    out.push_str(&format!("```\n{grid}```\n"));
+    out.push_str(&format!("{}\n", spoilers.join(" ")));
+    out.push_str(&coverage);
+    out.push_str(&format!(
+        "{covered}/{letters} letters covered, {overlapped} more than once\n"
+    ));
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn chat_message() {
+        let columns: Vec<String> = ["wsab", "hbta", "oesu", "dpive", "lceys"]
+            .map(String::from)
+            .to_vec();
+        let words: Vec<String> = ["above", "basic", "study", "wheel", "whups"]
+            .map(String::from)
+            .to_vec();
+
+        let expected = "\
+**Typeshift** in 5 words
+```
+W H O D L
+S B E P C
+A T S I E
+B A U V Y
+      E S
+```
+||above|| ||basic|| ||study|| ||wheel|| ||whups||
+🟨🟨🟩🟩🟩
+🟩🟩🟩🟩🟩
+🟩🟩🟩🟩🟩
+🟩🟩🟨🟩🟩
+⬜⬜⬜🟩🟩
+22/22 letters covered, 3 more than once
+";
+        assert_eq!(render_chat(&columns, &words), expected);
+    }
+}
@@ -0,0 +1,184 @@
+//! Subcommands of the main binary
+
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+use serde::Serialize;
+
+use typeshift_solver::error::{CalibrateError, GenerateError, ParseError, PlayError, SolveError};
+use typeshift_solver::puzzle::PuzzleDefinition;
+use typeshift_solver::report::OutputFormat;
+
+pub mod animate;
+pub mod archive;
+pub mod assist;
+pub mod batch;
+pub mod bot;
+pub mod compare;
+pub mod coop;
+pub mod coverage;
+pub mod decisions;
+#[cfg(feature = "fetch")]
+pub mod fetch;
+pub mod generate;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+#[cfg(feature = "serve")]
+pub mod metrics;
+#[cfg(feature = "tui")]
+pub mod play;
+pub mod profile;
+pub mod replay;
+pub mod review;
+pub mod score;
+pub mod selftest;
+#[cfg(feature = "serve")]
+pub mod serve;
+pub mod shrink;
+pub mod solve;
+pub mod stats;
+pub mod watch;
+pub mod week;
+pub mod worker;
+
+/// Reads a puzzle file, or stdin if the path is '-', in the plain input format.
+/// Files in the JSON puzzle format are converted to it.
+pub fn read_puzzle(path: &Path) -> Result<String, CliError> {
+    let input = if path.as_os_str() == "-" {
+        io::read_to_string(io::stdin())
+    } else {
+        std::fs::read_to_string(path)
+    };
+    let input =
+        input.map_err(|e| CliError::new(ErrorKind::Io, format!("{}: {e}", path.display())))?;
+
+    match serde_json::from_str::<PuzzleDefinition>(&input) {
+        Ok(puzzle) => Ok(puzzle.to_input()),
+        Err(_) => Ok(input),
+    }
+}
+
+/// The kinds of failure a wrapper script might want to branch on
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorKind {
+    /// The arguments don't make sense together
+    Usage,
+    /// A puzzle input is malformed
+    Parse,
+    /// A puzzle has no solution
+    Unsolvable,
+    /// A search hit its step or time limit
+    Timeout,
+    /// A file or network operation failed
+    Io,
+    /// The built-in self-test found a problem
+    SelfTest,
+    /// A generator ran out of attempts before finding a puzzle with the asked-for property
+    GaveUp,
+}
+
+impl ErrorKind {
+    /// The process exit code for this kind of failure.
+    /// Usage errors share clap's exit code for bad arguments.
+    pub fn exit_code(self) -> i32 {
+        match self {
+            ErrorKind::Usage => 2,
+            ErrorKind::Parse => 3,
+            ErrorKind::Unsolvable => 4,
+            ErrorKind::Timeout => 5,
+            ErrorKind::Io => 6,
+            ErrorKind::SelfTest => 7,
+            ErrorKind::GaveUp => 8,
+        }
+    }
+}
+
+/// A failed command
+#[derive(Debug, Serialize)]
+pub struct CliError {
+    pub kind: ErrorKind,
+    pub message: String,
+}
+
+impl CliError {
+    pub fn new(kind: ErrorKind, message: impl Into<String>) -> Self {
+        let message = message.into();
+        Self { kind, message }
+    }
+
+    /// Prints the error; plain errors go to stderr, structured errors to stdout
+    pub fn report(&self, format: OutputFormat) {
+        #[derive(Serialize)]
+        struct ErrorOutput<'a> {
+            error: &'a CliError,
+        }
+
+        impl fmt::Display for ErrorOutput<'_> {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                writeln!(f, "error: {}", self.error.message)
+            }
+        }
+
+        let rendered = format.render(&ErrorOutput { error: self });
+        match format {
+            OutputFormat::Plain | OutputFormat::Accessible => eprint!("{rendered}"),
+            _ => print!("{rendered}"),
+        }
+    }
+}
+
+impl From<ParseError> for CliError {
+    fn from(e: ParseError) -> Self {
+        Self::new(ErrorKind::Parse, e.to_string())
+    }
+}
+
+impl From<SolveError> for CliError {
+    fn from(e: SolveError) -> Self {
+        let kind = match e {
+            SolveError::Unsolvable => ErrorKind::Unsolvable,
+            SolveError::Timeout { .. } | SolveError::Cancelled { .. } => ErrorKind::Timeout,
+        };
+
+        Self::new(kind, e.to_string())
+    }
+}
+
+impl From<GenerateError> for CliError {
+    fn from(e: GenerateError) -> Self {
+        match e {
+            GenerateError::Solve(e) => e.into(),
+            GenerateError::NotUnique { .. }
+            | GenerateError::OutsideBand { .. }
+            | GenerateError::NotRequired { .. } => Self::new(ErrorKind::GaveUp, e.to_string()),
+            GenerateError::ShapeUnfilled => Self::new(ErrorKind::Unsolvable, e.to_string()),
+            e => Self::new(ErrorKind::Usage, e.to_string()),
+        }
+    }
+}
+
+impl From<CalibrateError> for CliError {
+    fn from(e: CalibrateError) -> Self {
+        match e {
+            CalibrateError::Solve(e) => e.into(),
+            e => Self::new(ErrorKind::Usage, e.to_string()),
+        }
+    }
+}
+
+impl From<PlayError> for CliError {
+    fn from(e: PlayError) -> Self {
+        match e {
+            PlayError::Solve(e) => e.into(),
+            e => Self::new(ErrorKind::Usage, e.to_string()),
+        }
+    }
+}
+
+impl From<io::Error> for CliError {
+    fn from(e: io::Error) -> Self {
+        Self::new(ErrorKind::Io, e.to_string())
+    }
+}
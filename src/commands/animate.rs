@@ -0,0 +1,89 @@
+use std::fmt;
+use std::io::{self, IsTerminal, Write};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::Args;
+use serde::Serialize;
+
+use typeshift_solver::animation::{self, AnimationFrame};
+use typeshift_solver::report::OutputFormat;
+use typeshift_solver::trace::Trace;
+use typeshift_solver::Typeshift;
+
+use super::{read_puzzle, CliError};
+
+#[derive(Args)]
+pub struct AnimateArgs {
+    /// A puzzle file with one column per line, leftmost first; '-' reads stdin
+    puzzle: PathBuf,
+
+    /// Show every partial solution the search expands, not just the solution's words
+    #[arg(long)]
+    search: bool,
+
+    /// How long to show each frame when playing in the terminal, in milliseconds
+    #[arg(long, default_value_t = 500)]
+    delay_ms: u64,
+
+    /// Write each frame to this directory as `frame-0001.txt` and so on,
+    /// for converting to a GIF, instead of playing them
+    #[arg(long, value_name = "DIR")]
+    out: Option<PathBuf>,
+}
+
+#[derive(Serialize)]
+#[serde(transparent)]
+struct Frames(Vec<AnimationFrame>);
+
+impl fmt::Display for Frames {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for frame in &self.0 {
+            writeln!(f, "{frame}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Shows the grid filling in as the solution's words are added, or as the search explores.
+/// Plays in a terminal; otherwise prints every frame.
+pub fn run(args: AnimateArgs, format: OutputFormat) -> Result<(), CliError> {
+    let typeshift = Typeshift::parse(&read_puzzle(&args.puzzle)?)?;
+    let columns = typeshift.input_columns();
+
+    let play = args.out.is_none() && format == OutputFormat::Plain && io::stdout().is_terminal();
+    let color = play && std::env::var_os("NO_COLOR").is_none();
+    let frames = if args.search {
+        let mut trace = Trace::new(&typeshift);
+        typeshift.solve_observed(&Default::default(), &mut trace)?;
+        animation::search_frames(&trace, color)
+    } else {
+        let (solution, _steps) = typeshift.solve_with(&Default::default())?;
+        let solution: Vec<String> = solution.into_iter().map(String::from).collect();
+        animation::solution_frames(columns, &solution, color)
+    };
+
+    if let Some(dir) = &args.out {
+        std::fs::create_dir_all(dir)?;
+        for (i, frame) in frames.iter().enumerate() {
+            std::fs::write(
+                dir.join(format!("frame-{:04}.txt", i + 1)),
+                frame.to_string(),
+            )?;
+        }
+        eprintln!("wrote {} frames to {}", frames.len(), dir.display());
+    } else if play {
+        let mut stdout = io::stdout().lock();
+        for frame in &frames {
+            // clear the screen and move to the top left before each frame
+            write!(stdout, "\x1b[2J\x1b[H{frame}")?;
+            stdout.flush()?;
+            std::thread::sleep(Duration::from_millis(args.delay_ms));
+        }
+    } else {
+        print!("{}", format.render(&Frames(frames)));
+    }
+
+    Ok(())
+}
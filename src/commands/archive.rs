@@ -0,0 +1,549 @@
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::{Path, PathBuf};
+
+use clap::{Args, Subcommand};
+use serde::Serialize;
+
+use typeshift_solver::analysis::{
+    Ambiguity, CorpusStats, Difficulty, DifficultyWeights, Distribution,
+};
+use typeshift_solver::archive::{ArchivedPuzzle, PuzzleArchive};
+use typeshift_solver::calibrate;
+use typeshift_solver::dataset::{self, DatasetRow};
+use typeshift_solver::date::Date;
+use typeshift_solver::flashcards;
+use typeshift_solver::grid::render_grid;
+use typeshift_solver::report::{OutputFormat, SolutionReport};
+use typeshift_solver::Typeshift;
+
+use super::{read_puzzle, CliError, ErrorKind};
+
+#[derive(Args)]
+pub struct ArchiveArgs {
+    #[command(subcommand)]
+    command: ArchiveCommand,
+
+    /// The puzzle directory
+    #[arg(long, global = true, default_value = "files/puzzles")]
+    dir: PathBuf,
+
+    /// Read the puzzles embedded in the binary instead of a directory
+    #[arg(long, global = true)]
+    embedded: bool,
+}
+
+#[derive(Subcommand)]
+enum ArchiveCommand {
+    /// List archived puzzles, oldest first
+    List,
+
+    /// Show the puzzle for a date
+    Show { date: Date },
+
+    /// Show a puzzle of the day: the one for the date if archived,
+    /// otherwise one picked from the archive by the date
+    Today {
+        /// The day; today if not given
+        date: Option<Date>,
+    },
+
+    /// Add a puzzle file to the puzzle directory
+    Add {
+        date: Date,
+
+        /// A puzzle file with one column per line, leftmost first; '-' reads stdin
+        puzzle: PathBuf,
+
+        /// Metadata to store alongside the puzzle, as key=value
+        #[arg(long = "meta", value_parser = parse_key_value)]
+        metadata: Vec<(String, String)>,
+    },
+
+    /// Report puzzles with more than one minimal solution, most ambiguous first
+    Ambiguity,
+
+    /// Rate every archived puzzle's difficulty, hardest first
+    Difficulty {
+        /// Score with weights written by `archive calibrate`, predicting human solve times
+        #[arg(long, value_name = "PATH")]
+        weights: Option<PathBuf>,
+    },
+
+    /// Fit the difficulty score to recorded human solves of archived puzzles,
+    /// printing the weights
+    Calibrate {
+        /// A CSV file of results: each line a puzzle hash (as in history files),
+        /// the seconds the solve took, and the words used
+        results: PathBuf,
+
+        /// Also write the weights to this file, for `archive difficulty --weights`
+        #[arg(long)]
+        out: Option<PathBuf>,
+    },
+
+    /// Summarize the archive: distributions of puzzle sizes, search steps, and
+    /// solution counts, and how often each letter appears
+    Stats,
+
+    /// Solve every archived puzzle
+    Solve {
+        /// Also find the smallest solutions the search finds
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// Solve every archived puzzle, writing one row per puzzle for data analysis.
+    /// Writes Parquet if the path ends in .parquet (with the parquet feature), or CSV
+    Export {
+        /// Where to write the dataset; '-' writes CSV to stdout
+        out: PathBuf,
+    },
+
+    /// Solve every archived puzzle, writing the uncommon solution words as flashcards
+    /// that Anki can import: the word, the puzzle, and a link to a definition
+    Flashcards {
+        /// Where to write the cards, as TSV; '-' writes to stdout
+        out: PathBuf,
+
+        /// A word list ordered most common first; words in its first `--max-rank` words
+        /// are common enough to leave out
+        #[arg(long)]
+        frequency_list: PathBuf,
+
+        /// How far down the frequency list words count as common
+        #[arg(long, default_value_t = 10_000)]
+        max_rank: usize,
+    },
+}
+
+pub fn run(args: ArchiveArgs, format: OutputFormat) -> Result<(), CliError> {
+    let archive = || -> Result<PuzzleArchive, CliError> {
+        if args.embedded {
+            Ok(PuzzleArchive::embedded())
+        } else {
+            Ok(PuzzleArchive::load_dir(&args.dir)?)
+        }
+    };
+
+    match args.command {
+        ArchiveCommand::List => {
+            let listing = Listing(archive()?.iter().map(ListedPuzzle::new).collect());
+            print!("{}", format.render(&listing));
+        }
+
+        ArchiveCommand::Show { date } => {
+            let archive = archive()?;
+            let puzzle = archive.get(date).ok_or_else(|| {
+                CliError::new(ErrorKind::Io, format!("no puzzle archived for {date}"))
+            })?;
+            print!("{}", format.render(&ShownPuzzle::new(puzzle)));
+        }
+
+        ArchiveCommand::Today { date } => {
+            let archive = archive()?;
+            let date = date.unwrap_or_else(Date::today);
+            let puzzle = archive
+                .for_date(date)
+                .ok_or_else(|| CliError::new(ErrorKind::Io, "the archive has no puzzles"))?;
+            print!("{}", format.render(&ShownPuzzle::new(puzzle)));
+        }
+
+        ArchiveCommand::Add {
+            date,
+            puzzle,
+            metadata,
+        } => {
+            if args.embedded {
+                let message = "the embedded archive is read-only";
+                return Err(CliError::new(ErrorKind::Usage, message));
+            }
+
+            let input = read_puzzle(&puzzle)?;
+            let typeshift = Typeshift::parse(&input)?;
+
+            let existing = archive()?;
+            if let Some(same) = existing.find_by_hash(typeshift.canonical_hash()) {
+                if same.date != date {
+                    let message = format!("this puzzle is already archived as {}", same.date);
+                    return Err(CliError::new(ErrorKind::Usage, message));
+                }
+            }
+
+            let puzzle = ArchivedPuzzle {
+                date,
+                input,
+                metadata: metadata.into_iter().collect(),
+            };
+            PuzzleArchive::save_to_dir(&puzzle, &args.dir)?;
+            eprintln!("added {date} to {}", args.dir.display());
+        }
+
+        ArchiveCommand::Ambiguity => {
+            let mut puzzles = Vec::new();
+            for puzzle in archive()?.iter() {
+                let typeshift = Typeshift::parse(&puzzle.input)?;
+                let ambiguity = Ambiguity::new(&typeshift);
+                if ambiguity.is_ambiguous() {
+                    let date = puzzle.date;
+                    puzzles.push(AmbiguousPuzzle { date, ambiguity });
+                }
+            }
+            puzzles.sort_by_key(|puzzle| Reverse(puzzle.ambiguity.solutions));
+
+            print!("{}", format.render(&AmbiguityReport(puzzles)));
+        }
+
+        ArchiveCommand::Difficulty { weights } => {
+            let weights = match weights {
+                Some(path) => serde_json::from_str(&std::fs::read_to_string(path)?)
+                    .map_err(|e| CliError::new(ErrorKind::Usage, format!("bad weights: {e}")))?,
+                None => DifficultyWeights::default(),
+            };
+
+            let mut puzzles = Vec::new();
+            for puzzle in archive()?.iter() {
+                let typeshift = Typeshift::parse(&puzzle.input)?;
+                let mut difficulty = typeshift.difficulty()?;
+                difficulty.score = weights.score(&difficulty);
+                let date = puzzle.date;
+                puzzles.push(RatedPuzzle { date, difficulty });
+            }
+            puzzles.sort_by(|a, b| b.difficulty.score.total_cmp(&a.difficulty.score));
+
+            print!("{}", format.render(&DifficultyReport(puzzles)));
+        }
+
+        ArchiveCommand::Calibrate { results, out } => {
+            let results = calibrate::load_results(results)?;
+            let puzzles = (archive()?.iter())
+                .map(|puzzle| Typeshift::parse(&puzzle.input))
+                .collect::<Result<Vec<_>, _>>()?;
+            let weights = calibrate::calibrate(&results, &puzzles, &Default::default())?;
+
+            if let Some(path) = out {
+                std::fs::write(path, serde_json::to_string_pretty(&weights).unwrap() + "\n")?;
+            }
+            print!("{}", format.render(&Weights(weights)));
+        }
+
+        ArchiveCommand::Stats => {
+            let puzzles = (archive()?.iter())
+                .map(|puzzle| Typeshift::parse(&puzzle.input))
+                .collect::<Result<Vec<_>, _>>()?;
+            let stats = CorpusStats::new(&puzzles, &Default::default())?;
+
+            print!("{}", format.render(&StatsReport(stats)));
+        }
+
+        ArchiveCommand::Solve { all } => {
+            let mut solves = Vec::new();
+            for puzzle in archive()?.iter() {
+                let typeshift = Typeshift::parse(&puzzle.input)?;
+                let report = SolutionReport::with_config(&typeshift, all, &Default::default())?;
+                let date = puzzle.date;
+
+                solves.push(SolvedPuzzle { date, report });
+            }
+
+            print!("{}", format.render(&Solves(solves)));
+        }
+
+        ArchiveCommand::Export { out } => {
+            let mut rows = Vec::new();
+            for puzzle in archive()?.iter() {
+                let typeshift = Typeshift::parse(&puzzle.input)?;
+                rows.push(DatasetRow::new(
+                    puzzle.date,
+                    &typeshift,
+                    &Default::default(),
+                )?);
+            }
+
+            write_dataset(&rows, &out)?;
+        }
+
+        ArchiveCommand::Flashcards {
+            out,
+            frequency_list,
+            max_rank,
+        } => {
+            let mut solutions = Vec::new();
+            for puzzle in archive()?.iter() {
+                let typeshift = Typeshift::parse(&puzzle.input)?;
+                let (solution, _steps) = typeshift.solve_with(&Default::default())?;
+                solutions.push((puzzle.date, solution));
+            }
+
+            let frequency_list = std::fs::read_to_string(frequency_list)?;
+            let frequency_list = Vec::from_iter(frequency_list.lines().map(str::trim));
+            let solutions = solutions.iter().map(|(date, solution)| (*date, solution));
+            let cards = flashcards::obscure_words(solutions, &frequency_list, max_rank);
+
+            let tsv = flashcards::render_tsv(&cards);
+            if out == Path::new("-") {
+                print!("{tsv}");
+            } else {
+                std::fs::write(&out, tsv)?;
+                eprintln!("wrote {} cards to {}", cards.len(), out.display());
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn write_dataset(rows: &[DatasetRow], out: &Path) -> Result<(), CliError> {
+    if out == Path::new("-") {
+        return Ok(dataset::write_csv(rows, io::stdout().lock())?);
+    }
+
+    let file = BufWriter::new(File::create(out)?);
+    if out.extension().is_some_and(|ext| ext == "parquet") {
+        #[cfg(feature = "parquet")]
+        return Ok(dataset::write_parquet(rows, file)?);
+
+        #[cfg(not(feature = "parquet"))]
+        return Err(CliError::new(
+            ErrorKind::Usage,
+            "writing Parquet needs the parquet feature",
+        ));
+    }
+
+    Ok(dataset::write_csv(rows, file)?)
+}
+
+fn parse_key_value(s: &str) -> Result<(String, String), String> {
+    let (key, value) = s
+        .split_once('=')
+        .ok_or_else(|| format!("expected key=value, got '{s}'"))?;
+
+    Ok((key.to_string(), value.to_string()))
+}
+
+#[derive(Serialize)]
+struct ListedPuzzle {
+    date: Date,
+    columns: usize,
+    metadata: BTreeMap<String, String>,
+}
+
+impl ListedPuzzle {
+    fn new(puzzle: &ArchivedPuzzle) -> Self {
+        Self {
+            date: puzzle.date,
+            columns: puzzle.input.lines().count(),
+            metadata: puzzle.metadata.clone(),
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(transparent)]
+struct Listing(Vec<ListedPuzzle>);
+
+impl fmt::Display for Listing {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for puzzle in &self.0 {
+            write!(f, "{}  {} columns", puzzle.date, puzzle.columns)?;
+            for (key, value) in &puzzle.metadata {
+                write!(f, "  {key}: {value}")?;
+            }
+            writeln!(f)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+struct ShownPuzzle {
+    date: Date,
+    columns: Vec<String>,
+    metadata: BTreeMap<String, String>,
+}
+
+impl ShownPuzzle {
+    fn new(puzzle: &ArchivedPuzzle) -> Self {
+        Self {
+            date: puzzle.date,
+            columns: puzzle.input.lines().map(String::from).collect(),
+            metadata: puzzle.metadata.clone(),
+        }
+    }
+}
+
+impl fmt::Display for ShownPuzzle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{}", self.date)?;
+        for (key, value) in &self.metadata {
+            writeln!(f, "{key}: {value}")?;
+        }
+        writeln!(f)?;
+        write!(f, "{}", render_grid(&self.columns, &[], false))
+    }
+}
+
+#[derive(Serialize)]
+struct SolvedPuzzle {
+    date: Date,
+    #[serde(flatten)]
+    report: SolutionReport,
+}
+
+#[derive(Serialize)]
+#[serde(transparent)]
+struct Solves(Vec<SolvedPuzzle>);
+
+impl fmt::Display for Solves {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for solve in &self.0 {
+            writeln!(f, "== {} ==", solve.date)?;
+            writeln!(f, "{}", solve.report)?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+struct AmbiguousPuzzle {
+    date: Date,
+    #[serde(flatten)]
+    ambiguity: Ambiguity,
+}
+
+#[derive(Serialize)]
+#[serde(transparent)]
+struct AmbiguityReport(Vec<AmbiguousPuzzle>);
+
+impl fmt::Display for AmbiguityReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for puzzle in &self.0 {
+            let Ambiguity {
+                solutions,
+                common_words,
+                distinguishing_words,
+                families,
+            } = &puzzle.ambiguity;
+            let join = |words: &BTreeSet<&str>| Vec::from_iter(words.iter().copied()).join(" ");
+
+            writeln!(f, "{}: {solutions} minimal solutions", puzzle.date)?;
+            writeln!(f, "  always: {}", join(common_words))?;
+            writeln!(f, "  distinguishing: {}", join(distinguishing_words))?;
+            for family in families {
+                let pivot = family.pivot.unwrap_or("-");
+                write!(
+                    f,
+                    "  {pivot} ({} solutions): {}",
+                    family.solutions,
+                    join(&family.shared_words)
+                )?;
+                if !family.varying_words.is_empty() {
+                    write!(f, ", with some of {}", join(&family.varying_words))?;
+                }
+                writeln!(f)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+struct RatedPuzzle {
+    date: Date,
+    #[serde(flatten)]
+    difficulty: Difficulty,
+}
+
+#[derive(Serialize)]
+#[serde(transparent)]
+struct DifficultyReport(Vec<RatedPuzzle>);
+
+impl fmt::Display for DifficultyReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for puzzle in &self.0 {
+            let Difficulty {
+                score,
+                candidate_words,
+                root_branching,
+                minimal_words,
+                solutions,
+            } = &puzzle.difficulty;
+
+            writeln!(
+                f,
+                "{}: {score:.1} ({candidate_words} words, {root_branching} first choices, \
+                 {minimal_words}-word solution, {solutions} minimal solutions)",
+                puzzle.date
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+#[derive(Serialize)]
+#[serde(transparent)]
+struct Weights(DifficultyWeights);
+
+impl fmt::Display for Weights {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let DifficultyWeights {
+            intercept,
+            ln_candidate_words,
+            ln_root_branching,
+            minimal_words,
+            ln_solutions,
+        } = self.0;
+
+        writeln!(f, "intercept: {intercept:.3}")?;
+        writeln!(f, "ln candidate words: {ln_candidate_words:.3}")?;
+        writeln!(f, "ln root branching: {ln_root_branching:.3}")?;
+        writeln!(f, "minimal words: {minimal_words:.3}")?;
+        writeln!(f, "ln solutions: {ln_solutions:.3}")
+    }
+}
+
+#[derive(Serialize)]
+#[serde(transparent)]
+struct StatsReport(CorpusStats);
+
+impl fmt::Display for StatsReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stats = &self.0;
+        let measures = [
+            ("columns", &stats.columns),
+            ("letters", &stats.letters),
+            ("candidate words", &stats.candidate_words),
+            ("steps", &stats.steps),
+            ("minimal words", &stats.minimal_words),
+            ("minimal solutions", &stats.solutions),
+        ];
+
+        writeln!(f, "puzzles: {}", stats.puzzles)?;
+        for (name, distribution) in measures {
+            let Distribution {
+                min,
+                median,
+                mean,
+                max,
+            } = distribution;
+            writeln!(
+                f,
+                "{name}: min {min}, median {median:.1}, mean {mean:.1}, max {max}"
+            )?;
+        }
+
+        let mut letters = Vec::from_iter(&stats.letter_columns);
+        letters.sort_by_key(|&(_, columns)| Reverse(columns));
+        let letters: Vec<_> = (letters.into_iter())
+            .map(|(letter, columns)| format!("{letter} {columns}"))
+            .collect();
+        writeln!(f, "columns with each letter: {}", letters.join(", "))
+    }
+}
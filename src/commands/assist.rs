@@ -0,0 +1,70 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use clap::Args;
+use serde::Serialize;
+
+use typeshift_solver::hint::human_order;
+use typeshift_solver::report::OutputFormat;
+use typeshift_solver::{SolverConfig, Typeshift};
+
+use super::{read_puzzle, CliError};
+
+#[derive(Args)]
+pub struct AssistArgs {
+    /// A puzzle file with one column per line, leftmost first; '-' reads stdin
+    puzzle: PathBuf,
+
+    /// The letters already covered, each as its 1-based column and letter, like `1a`
+    #[arg(value_parser = parse_cell)]
+    covered: Vec<(usize, char)>,
+
+    /// Give up after touching this many partial solutions
+    #[arg(long)]
+    max_steps: Option<usize>,
+}
+
+/// Parses a cell like `3e` into a 0-based column and a letter
+fn parse_cell(s: &str) -> Result<(usize, char), String> {
+    let invalid = || format!("expected a column number and a letter, like 1a: {s}");
+
+    let letter = s
+        .chars()
+        .last()
+        .filter(char::is_ascii_lowercase)
+        .ok_or_else(invalid)?;
+    let column: usize = s[..s.len() - 1].parse().map_err(|_| invalid())?;
+    if column == 0 {
+        return Err(invalid());
+    }
+
+    Ok((column - 1, letter))
+}
+
+#[derive(Serialize)]
+#[serde(transparent)]
+struct Remaining(Vec<&'static str>);
+
+impl fmt::Display for Remaining {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0.as_slice() {
+            [] => writeln!(f, "every letter is covered"),
+            words => writeln!(f, "{}", words.join(" ")),
+        }
+    }
+}
+
+/// Prints the fewest words that cover the letters not covered yet, easiest first
+pub fn run(args: AssistArgs, format: OutputFormat) -> Result<(), CliError> {
+    let config = SolverConfig {
+        max_steps: args.max_steps,
+        ..Default::default()
+    };
+    let typeshift = Typeshift::parse(&read_puzzle(&args.puzzle)?)?;
+
+    let (remaining, _steps) = typeshift.complete_cells_with(&args.covered, &config)?;
+    let remaining = human_order(&typeshift, &remaining, &[]);
+    print!("{}", format.render(&Remaining(remaining)));
+
+    Ok(())
+}
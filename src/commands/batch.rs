@@ -0,0 +1,94 @@
+use std::io::{self, BufRead, Write};
+use std::path::Path;
+
+use serde::Serialize;
+
+use typeshift_solver::report::SolutionReport;
+use typeshift_solver::{SolverConfig, Typeshift};
+
+use super::decisions::DecisionLog;
+use super::{read_puzzle, CliError};
+
+/// One line of batch output
+#[derive(Serialize)]
+struct BatchLine<'a> {
+    file: &'a str,
+    #[serde(flatten)]
+    result: BatchResult,
+}
+
+#[derive(Serialize)]
+#[serde(untagged)]
+enum BatchResult {
+    Solved(SolutionReport),
+    Failed { error: CliError },
+}
+
+/// Solves each puzzle path read from stdin, writing one JSON object per line (ndjson).
+/// Failures for individual puzzles are written in place of their results;
+/// the command fails at the end if any puzzle did.
+/// Every puzzle's search decisions are logged to one file, if given.
+pub fn run(
+    include_all: bool,
+    config: &SolverConfig,
+    decision_log: Option<&Path>,
+) -> Result<(), CliError> {
+    let mut decisions = decision_log.map(DecisionLog::create).transpose()?;
+    let mut stdout = io::stdout().lock();
+    let mut failures = Vec::new();
+    let mut total = 0;
+
+    for path in io::stdin().lock().lines() {
+        let path = path?;
+        let path = path.trim();
+        if path.is_empty() {
+            continue;
+        }
+        total += 1;
+
+        if let Some(log) = &mut decisions {
+            log.start(path);
+        }
+        let result = solve(path, include_all, config, &mut decisions);
+        let result = match result {
+            Ok(report) => BatchResult::Solved(report),
+            Err(error) => {
+                failures.push(error.kind);
+                BatchResult::Failed { error }
+            }
+        };
+
+        let line = BatchLine { file: path, result };
+        serde_json::to_writer(&mut stdout, &line).map_err(io::Error::from)?;
+        writeln!(stdout)?;
+    }
+
+    if let Some(log) = decisions {
+        log.finish()?;
+    }
+
+    match failures.first() {
+        None => Ok(()),
+        Some(&kind) => Err(CliError::new(
+            kind,
+            format!("{} of {total} puzzles failed", failures.len()),
+        )),
+    }
+}
+
+fn solve(
+    path: &str,
+    include_all: bool,
+    config: &SolverConfig,
+    decisions: &mut Option<DecisionLog>,
+) -> Result<SolutionReport, CliError> {
+    let input = read_puzzle(path.as_ref())?;
+    let typeshift = Typeshift::parse(&input)?;
+
+    let report = match decisions {
+        Some(log) => SolutionReport::with_observer(&typeshift, include_all, config, log)?,
+        None => SolutionReport::with_config(&typeshift, include_all, config)?,
+    };
+
+    Ok(report)
+}
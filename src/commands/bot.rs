@@ -0,0 +1,61 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use clap::Args;
+use serde::Serialize;
+
+use typeshift_solver::bot::{Bot, BotSkill};
+use typeshift_solver::report::OutputFormat;
+use typeshift_solver::Typeshift;
+
+use super::{read_puzzle, CliError, ErrorKind};
+
+#[derive(Args)]
+pub struct BotArgs {
+    /// A puzzle file with one column per line, leftmost first; '-' reads stdin
+    puzzle: PathBuf,
+
+    /// The chance, from 0 to 1, that the bot passes over the word it likes best each turn
+    #[arg(long, default_value_t = 0.0)]
+    mistakes: f64,
+
+    /// How many more words the bot follows the solver's ranking for
+    /// when weighing each word
+    #[arg(long, default_value_t = 0)]
+    lookahead: usize,
+
+    /// Play repeatably for the same seed
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+}
+
+#[derive(Serialize)]
+#[serde(transparent)]
+struct BotGame(Vec<&'static str>);
+
+impl fmt::Display for BotGame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (turn, word) in self.0.iter().enumerate() {
+            writeln!(f, "{}. {word}", turn + 1)?;
+        }
+        writeln!(f, "finished in {} words", self.0.len())
+    }
+}
+
+/// Prints the words a bot of the given skill plays, in order, as an opponent's game
+pub fn run(args: BotArgs, format: OutputFormat) -> Result<(), CliError> {
+    if !(0.0..=1.0).contains(&args.mistakes) {
+        let message = format!("--mistakes must be from 0 to 1: {}", args.mistakes);
+        return Err(CliError::new(ErrorKind::Usage, message));
+    }
+    let skill = BotSkill {
+        mistake_chance: args.mistakes,
+        lookahead: args.lookahead,
+    };
+    let typeshift = Typeshift::parse(&read_puzzle(&args.puzzle)?)?;
+
+    let played = Bot::new(skill, args.seed).play(&typeshift)?;
+    print!("{}", format.render(&BotGame(played)));
+
+    Ok(())
+}
@@ -0,0 +1,226 @@
+use std::fmt;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use clap::Args;
+use serde::Serialize;
+
+use typeshift_solver::archive::PuzzleArchive;
+use typeshift_solver::report::OutputFormat;
+use typeshift_solver::{RankWeights, SolverConfig, Strategy, Typeshift};
+
+use super::{read_puzzle, CliError};
+
+#[derive(Args)]
+pub struct CompareArgs {
+    /// Puzzle files to compare on; defaults to every puzzle in files/puzzles
+    puzzles: Vec<PathBuf>,
+
+    /// The first solver strategy
+    #[arg(long, value_enum, default_value_t = Strategy::BestFirst)]
+    left: Strategy,
+
+    /// The second solver strategy
+    #[arg(long, value_enum, default_value_t = Strategy::Greedy)]
+    right: Strategy,
+
+    /// Ranking weights for the first solver, instead of the usual ranking
+    #[arg(long, value_name = "NEW_LETTERS,MIN_CHAR_FREQ,OVERLAPS,DEPTH", value_parser = parse_weights)]
+    left_weights: Option<RankWeights>,
+
+    /// Ranking weights for the second solver, instead of the usual ranking
+    #[arg(long, value_name = "NEW_LETTERS,MIN_CHAR_FREQ,OVERLAPS,DEPTH", value_parser = parse_weights)]
+    right_weights: Option<RankWeights>,
+
+    /// Break the first solver's ties in a random order from this seed
+    #[arg(long)]
+    left_tie_seed: Option<u64>,
+
+    /// Break the second solver's ties in a random order from this seed
+    #[arg(long)]
+    right_tie_seed: Option<u64>,
+}
+
+/// Parses weights like `100,1,10,1`, in the order of `RankWeights`' fields
+fn parse_weights(s: &str) -> Result<RankWeights, String> {
+    let invalid = || format!("expected four comma-separated integers, like 100,1,10,1: {s}");
+
+    let weights: Vec<i64> = (s.split(','))
+        .map(|weight| weight.trim().parse().map_err(|_| invalid()))
+        .collect::<Result<_, _>>()?;
+    let [new_letters, min_char_freq, overlaps, depth] = weights[..] else {
+        return Err(invalid());
+    };
+
+    Ok(RankWeights {
+        new_letters,
+        min_char_freq,
+        overlaps,
+        depth,
+    })
+}
+
+/// One side of the comparison: a strategy, and how its search ranks
+#[derive(Serialize)]
+struct Solver {
+    strategy: Strategy,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    weights: Option<RankWeights>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    tie_seed: Option<u64>,
+}
+
+impl Solver {
+    fn config(&self) -> SolverConfig {
+        SolverConfig {
+            strategy: self.strategy,
+            weights: self.weights,
+            tie_seed: self.tie_seed,
+            ..Default::default()
+        }
+    }
+}
+
+impl fmt::Display for Solver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:?}", self.strategy)?;
+        if let Some(weights) = self.weights {
+            let RankWeights {
+                new_letters,
+                min_char_freq,
+                overlaps,
+                depth,
+            } = weights;
+            write!(f, " {new_letters},{min_char_freq},{overlaps},{depth}")?;
+        }
+        if let Some(tie_seed) = self.tie_seed {
+            write!(f, " seed {tie_seed}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The results of one solver configuration on one puzzle
+#[derive(Serialize)]
+struct Run {
+    steps: usize,
+    solve_ms: f64,
+    words: usize,
+}
+
+impl Run {
+    fn new(
+        typeshift: &Typeshift,
+        config: &SolverConfig,
+    ) -> Result<(Self, Vec<&'static str>), CliError> {
+        let start = Instant::now();
+        let (solution, steps) = typeshift.solve_with(config)?;
+        let solve_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+        let run = Self {
+            steps,
+            solve_ms,
+            words: solution.len(),
+        };
+        Ok((run, solution.into_iter().collect()))
+    }
+}
+
+#[derive(Serialize)]
+struct Row {
+    puzzle: String,
+    left: Run,
+    right: Run,
+    same_solution: bool,
+}
+
+#[derive(Serialize)]
+struct Comparison {
+    left: Solver,
+    right: Solver,
+    rows: Vec<Row>,
+}
+
+impl fmt::Display for Comparison {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let left = self.left.to_string();
+        let right = self.right.to_string();
+        let run = |run: &Run| format!("{}w {}s {:.2}ms", run.words, run.steps, run.solve_ms);
+
+        let puzzle_width = (self.rows.iter())
+            .map(|row| row.puzzle.len())
+            .fold("puzzle".len(), usize::max);
+        let left_width = (self.rows.iter())
+            .map(|row| run(&row.left).len())
+            .fold(left.len(), usize::max);
+        let right_width = (self.rows.iter())
+            .map(|row| run(&row.right).len())
+            .fold(right.len(), usize::max);
+
+        writeln!(
+            f,
+            "{:<puzzle_width$} {left:>left_width$} {right:>right_width$}  same",
+            "puzzle"
+        )?;
+        for row in &self.rows {
+            writeln!(
+                f,
+                "{:<puzzle_width$} {:>left_width$} {:>right_width$}  {}",
+                row.puzzle,
+                run(&row.left),
+                run(&row.right),
+                if row.same_solution { "yes" } else { "no" }
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn run(args: CompareArgs, format: OutputFormat) -> Result<(), CliError> {
+    let puzzles: Vec<(String, String)> = if args.puzzles.is_empty() {
+        PuzzleArchive::load_dir("files/puzzles")?
+            .iter()
+            .map(|puzzle| (puzzle.date.to_string(), puzzle.input.clone()))
+            .collect()
+    } else {
+        let mut puzzles = Vec::new();
+        for path in &args.puzzles {
+            puzzles.push((path.display().to_string(), read_puzzle(path)?));
+        }
+        puzzles
+    };
+
+    let left = Solver {
+        strategy: args.left,
+        weights: args.left_weights,
+        tie_seed: args.left_tie_seed,
+    };
+    let right = Solver {
+        strategy: args.right,
+        weights: args.right_weights,
+        tie_seed: args.right_tie_seed,
+    };
+    let left_config = left.config();
+    let right_config = right.config();
+
+    let mut rows = Vec::new();
+    for (puzzle, input) in puzzles {
+        let typeshift = Typeshift::parse(&input)?;
+        let (left, left_solution) = Run::new(&typeshift, &left_config)?;
+        let (right, right_solution) = Run::new(&typeshift, &right_config)?;
+
+        rows.push(Row {
+            puzzle,
+            left,
+            right,
+            same_solution: left_solution == right_solution,
+        });
+    }
+
+    let comparison = Comparison { left, right, rows };
+    print!("{}", format.render(&comparison));
+
+    Ok(())
+}
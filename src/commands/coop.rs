@@ -0,0 +1,72 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use clap::Args;
+use serde::Serialize;
+
+use typeshift_solver::game::GameState;
+use typeshift_solver::hint::human_order;
+use typeshift_solver::report::OutputFormat;
+use typeshift_solver::{SolverConfig, Typeshift};
+
+use super::{read_puzzle, CliError};
+
+#[derive(Args)]
+pub struct CoopArgs {
+    /// A puzzle file with one column per line, leftmost first; '-' reads stdin
+    puzzle: PathBuf,
+
+    /// One player's words, separated by spaces or commas; repeat for each player
+    #[arg(long = "player", value_name = "WORDS", required = true)]
+    players: Vec<String>,
+
+    /// Give up after touching this many partial solutions
+    #[arg(long)]
+    max_steps: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct CoopOutput {
+    /// Every player's words, each once
+    played: Vec<&'static str>,
+    /// The fewest more words that finish the shared game, easiest first
+    remaining: Vec<&'static str>,
+}
+
+impl fmt::Display for CoopOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "played together: {}", self.played.join(" "))?;
+        match self.remaining.as_slice() {
+            [] => writeln!(f, "every letter is covered"),
+            words => writeln!(f, "to finish: {}", words.join(" ")),
+        }
+    }
+}
+
+/// Merges several players' games of the same puzzle,
+/// and prints the fewest words that finish the merged game
+pub fn run(args: CoopArgs, format: OutputFormat) -> Result<(), CliError> {
+    let config = SolverConfig {
+        max_steps: args.max_steps,
+        ..Default::default()
+    };
+    let typeshift = Typeshift::parse(&read_puzzle(&args.puzzle)?)?;
+
+    let mut merged = GameState::new(&typeshift);
+    for words in &args.players {
+        let mut game = GameState::new(&typeshift);
+        for word in words.split([' ', ',']).filter(|word| !word.is_empty()) {
+            game.play(&word.to_lowercase())?;
+        }
+        merged.merge(&game)?;
+    }
+
+    let (remaining, _steps) = typeshift.complete_with(merged.played(), &config)?;
+    let output = CoopOutput {
+        played: merged.played().to_vec(),
+        remaining: human_order(&typeshift, &remaining, &[]),
+    };
+    print!("{}", format.render(&output));
+
+    Ok(())
+}
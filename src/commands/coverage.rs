@@ -0,0 +1,84 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use clap::Args;
+use serde::Serialize;
+
+use typeshift_solver::analysis::LetterCoverage;
+use typeshift_solver::coverage::CoverageMatrix;
+use typeshift_solver::report::OutputFormat;
+use typeshift_solver::Typeshift;
+
+use super::{read_puzzle, CliError};
+
+#[derive(Args)]
+pub struct CoverageArgs {
+    /// A puzzle file with one column per line, leftmost first; '-' reads stdin
+    puzzle: PathBuf,
+
+    /// Instead of the matrix, list how many words use each letter,
+    /// flagging letters used by at most this many words (2 if no number is given)
+    #[arg(long, value_name = "MAX_WORDS", num_args = 0..=1, default_missing_value = "2")]
+    bottlenecks: Option<usize>,
+}
+
+#[derive(Serialize)]
+#[serde(transparent)]
+struct CoverageOutput(CoverageMatrix);
+
+impl fmt::Display for CoverageOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0.to_csv())
+    }
+}
+
+#[derive(Serialize)]
+#[serde(transparent)]
+struct BottleneckOutput(LetterCoverage);
+
+impl fmt::Display for BottleneckOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for letter in &self.0.letters {
+            let flag = if letter.bottleneck {
+                "  bottleneck"
+            } else {
+                ""
+            };
+            writeln!(
+                f,
+                "{}{}: {} words{flag}",
+                letter.column + 1,
+                letter.letter,
+                letter.words.len()
+            )?;
+        }
+
+        for letter in self.0.bottlenecks() {
+            let words = letter.words.join(" ");
+            writeln!(
+                f,
+                "{}{} needs one of: {words}",
+                letter.column + 1,
+                letter.letter
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Writes which words use which letters, as CSV in the plain format,
+/// or how many words use each letter
+pub fn run(args: CoverageArgs, format: OutputFormat) -> Result<(), CliError> {
+    let typeshift = Typeshift::parse(&read_puzzle(&args.puzzle)?)?;
+
+    if let Some(max_words) = args.bottlenecks {
+        let coverage = LetterCoverage::with_threshold(&typeshift, max_words);
+        print!("{}", format.render(&BottleneckOutput(coverage)));
+    } else {
+        let matrix = CoverageMatrix::new(&typeshift);
+        print!("{}", format.render(&CoverageOutput(matrix)));
+    }
+
+    Ok(())
+}
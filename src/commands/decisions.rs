@@ -0,0 +1,124 @@
+//! A JSON lines log of the solver's decisions, for analyzing the ranking heuristics
+//!
+//! Every line names its puzzle and the step it belongs to, so logs from many puzzles
+//! can be concatenated into one table. The events are:
+//! - `popped`: a partial solution was taken off the frontier, with its `rank`
+//! - `expanded`: it ranked `candidates` next words, keeping the `kept` tied for best
+//! - `pushed`: a child partial solution was added to the frontier, with its `rank`
+//! - `pruned`: a child was dropped, with the `reason`
+
+use std::collections::BTreeSet;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use serde::Serialize;
+
+use typeshift_solver::trace::{NodeRank, SearchObserver};
+
+#[derive(Serialize)]
+struct Line<'a> {
+    puzzle: &'a str,
+    step: usize,
+    #[serde(flatten)]
+    decision: Decision<'a>,
+}
+
+#[derive(Serialize)]
+#[serde(tag = "event", rename_all = "snake_case")]
+enum Decision<'a> {
+    Popped {
+        words: &'a BTreeSet<&'static str>,
+        rank: NodeRank,
+    },
+    Expanded {
+        candidates: usize,
+        kept: usize,
+    },
+    Pushed {
+        words: &'a BTreeSet<&'static str>,
+        rank: NodeRank,
+    },
+    Pruned {
+        words: &'a BTreeSet<&'static str>,
+        reason: PruneReason,
+    },
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "snake_case")]
+enum PruneReason {
+    /// The same set of words was already queued
+    AlreadyQueued,
+}
+
+/// Writes the decisions of one or more searches to a file
+pub struct DecisionLog {
+    out: BufWriter<File>,
+    puzzle: String,
+    step: usize,
+    /// The first write error; observers can't fail, so it's reported by `finish`
+    error: Option<io::Error>,
+}
+
+impl DecisionLog {
+    pub fn create(path: &Path) -> io::Result<Self> {
+        Ok(Self {
+            out: BufWriter::new(File::create(path)?),
+            puzzle: String::new(),
+            step: 0,
+            error: None,
+        })
+    }
+
+    /// Names the puzzle for the following decisions, such as by its file path
+    pub fn start(&mut self, puzzle: &str) {
+        self.puzzle = puzzle.to_string();
+        self.step = 0;
+    }
+
+    /// Flushes the log, returning the first error writing it
+    pub fn finish(mut self) -> io::Result<()> {
+        match self.error.take() {
+            Some(e) => Err(e),
+            None => self.out.flush(),
+        }
+    }
+
+    fn write(&mut self, decision: Decision) {
+        if self.error.is_some() {
+            return;
+        }
+
+        let line = Line {
+            puzzle: &self.puzzle,
+            step: self.step,
+            decision,
+        };
+        let result = serde_json::to_writer(&mut self.out, &line)
+            .map_err(io::Error::from)
+            .and_then(|_| writeln!(self.out));
+
+        self.error = result.err();
+    }
+}
+
+impl SearchObserver for DecisionLog {
+    fn popped(&mut self, step: usize, words: &BTreeSet<&'static str>, rank: NodeRank) {
+        self.step = step;
+        self.write(Decision::Popped { words, rank });
+    }
+
+    fn expanded(&mut self, candidates: usize, kept: usize) {
+        self.write(Decision::Expanded { candidates, kept });
+    }
+
+    fn pushed(&mut self, words: &BTreeSet<&'static str>, rank: NodeRank) {
+        self.write(Decision::Pushed { words, rank });
+    }
+
+    fn skipped(&mut self, words: &BTreeSet<&'static str>) {
+        let reason = PruneReason::AlreadyQueued;
+        self.write(Decision::Pruned { words, reason });
+    }
+}
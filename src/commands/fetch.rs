@@ -0,0 +1,57 @@
+use std::path::PathBuf;
+
+use clap::Args;
+
+use typeshift_solver::date::Date;
+use typeshift_solver::puzzle::PuzzleDefinition;
+use typeshift_solver::report::{OutputFormat, SolutionReport};
+use typeshift_solver::Typeshift;
+
+use super::{CliError, ErrorKind};
+
+#[derive(Args)]
+pub struct FetchArgs {
+    /// The puzzle source; '{date}' is replaced with the puzzle date.
+    /// The response may be the JSON puzzle format or the plain one-column-per-line format.
+    #[arg(long, env = "TYPESHIFT_PUZZLE_URL")]
+    url: String,
+
+    /// The puzzle date to fetch; defaults to today (UTC)
+    #[arg(long)]
+    date: Option<Date>,
+
+    /// The directory to save the puzzle in, as <date>.txt
+    #[arg(long, default_value = "files/puzzles")]
+    dir: PathBuf,
+
+    /// Solve the puzzle after saving it
+    #[arg(long)]
+    solve: bool,
+}
+
+pub fn run(args: FetchArgs, format: OutputFormat) -> Result<(), CliError> {
+    let date = args.date.unwrap_or_else(Date::today);
+    let url = args.url.replace("{date}", &date.to_string());
+
+    let body = reqwest::blocking::get(&url)
+        .and_then(|response| response.error_for_status())
+        .and_then(|response| response.text())
+        .map_err(|e| CliError::new(ErrorKind::Io, format!("failed to fetch {url}: {e}")))?;
+
+    // accepts either the JSON puzzle format or the plain input format
+    let puzzle =
+        serde_json::from_str(&body).unwrap_or_else(|_| PuzzleDefinition::from_input(&body));
+    let input = puzzle.to_input();
+    let typeshift = Typeshift::parse(&input)?;
+
+    let path = args.dir.join(format!("{date}.txt"));
+    std::fs::write(&path, &input)?;
+    eprintln!("saved {}", path.display());
+
+    if args.solve {
+        let report = SolutionReport::with_config(&typeshift, false, &Default::default())?;
+        print!("{}", format.render(&report));
+    }
+
+    Ok(())
+}
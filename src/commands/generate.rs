@@ -0,0 +1,261 @@
+use std::collections::BTreeSet;
+use std::fmt;
+use std::path::PathBuf;
+
+use clap::Args;
+use serde::Serialize;
+
+use typeshift_solver::date::Date;
+use typeshift_solver::generate::{self, DifficultyBand, GeneratedPuzzle, PuzzleShape, Vocabulary};
+use typeshift_solver::report::OutputFormat;
+use typeshift_solver::sheet::{render_sheet, SheetAnswers};
+use typeshift_solver::SolverConfig;
+
+use super::CliError;
+
+#[derive(Args)]
+pub struct GenerateArgs {
+    /// The words to build the puzzle from, all the same length
+    #[arg(
+        required_unless_present_any = ["columns", "daily"],
+        conflicts_with_all = ["columns", "daily"]
+    )]
+    words: Vec<String>,
+
+    /// Build the unofficial daily puzzle for this date, the same for everyone
+    /// with this dictionary; `--date` defaults to it
+    #[arg(long, value_name = "DATE", conflicts_with = "columns")]
+    daily: Option<Date>,
+
+    /// Build a puzzle with this many columns (4 to 7) from dictionary words,
+    /// instead of from given words
+    #[arg(long, conflicts_with_all = [
+        "unique",
+        "min_estimated_steps",
+        "max_estimated_steps",
+        "min_branching",
+        "max_branching",
+        "required",
+    ])]
+    columns: Option<usize>,
+
+    /// The fewest letters in each column of a puzzle built with `--columns`
+    #[arg(long, default_value_t = 3, requires = "columns")]
+    min_height: usize,
+
+    /// The most letters in each column of a puzzle built with `--columns`
+    #[arg(long, default_value_t = 5, requires = "columns")]
+    max_height: usize,
+
+    /// Pick the words for `--columns` at random, repeatably for the same seed
+    #[arg(long, requires = "columns")]
+    seed: Option<u64>,
+
+    /// Instead of picking words for `--columns`, pick each column's letters at random
+    /// by how often they're in that position of dictionary words, until the puzzle is
+    /// solvable or `--attempts` puzzles have been tried; the seed defaults to 0
+    #[arg(long, requires = "columns", conflicts_with_all = ["frequency_list", "theme"])]
+    sample_letters: bool,
+
+    /// Change letters of the words until they're the puzzle's only minimal solution
+    #[arg(long)]
+    unique: bool,
+
+    /// Change letters of the other words until every minimal solution uses this word,
+    /// like a word of the day; it's added to the words if they don't have it
+    #[arg(long, conflicts_with_all = [
+        "unique",
+        "min_estimated_steps",
+        "max_estimated_steps",
+        "min_branching",
+        "max_branching",
+    ])]
+    required: Option<String>,
+
+    /// Change letters of the words until the puzzle's estimated search steps
+    /// are at least this many
+    #[arg(long, conflicts_with = "unique")]
+    min_estimated_steps: Option<usize>,
+
+    /// Change letters of the words until the puzzle's estimated search steps
+    /// are at most this many
+    #[arg(long, conflicts_with = "unique")]
+    max_estimated_steps: Option<usize>,
+
+    /// Change letters of the words until the puzzle's estimated branching factor
+    /// is at least this
+    #[arg(long, conflicts_with = "unique")]
+    min_branching: Option<f64>,
+
+    /// Change letters of the words until the puzzle's estimated branching factor
+    /// is at most this
+    #[arg(long, conflicts_with = "unique")]
+    max_branching: Option<f64>,
+
+    /// How many puzzles to check for `--unique`, `--required`, or a difficulty
+    /// before giving up
+    #[arg(long, default_value_t = 1000)]
+    attempts: usize,
+
+    /// Give up checking each puzzle after touching this many partial solutions
+    #[arg(long)]
+    max_steps: Option<usize>,
+
+    /// Also write the puzzle in the JSON puzzle format that `fetch` and `serve` take;
+    /// '-' writes it to stdout instead of the usual output
+    #[arg(long, value_name = "PATH")]
+    export: Option<PathBuf>,
+
+    /// The date to give the exported puzzle
+    #[arg(long, requires = "export")]
+    date: Option<Date>,
+
+    /// Also write a sheet for playing the puzzle on paper, with a blank line for each word
+    /// of a minimal solution and the answers; '-' writes it to stdout instead
+    #[arg(long, value_name = "PATH", conflicts_with = "export")]
+    sheet: Option<PathBuf>,
+
+    /// Where the sheet's answers go
+    #[arg(long, value_enum, default_value_t, requires = "sheet")]
+    sheet_answers: SheetAnswers,
+
+    /// A word list ordered most common first; the intended words, and any words
+    /// changed or picked to reach them, come from its first `--max-rank` words.
+    /// Other solutions are still looked for with the whole dictionary
+    #[arg(long)]
+    frequency_list: Option<PathBuf>,
+
+    /// How far down the frequency list intended words may come from
+    #[arg(long, default_value_t = 10_000, requires = "frequency_list")]
+    max_rank: usize,
+
+    /// A themed word list, like animals; the intended words, and any words changed or
+    /// picked to reach them, come from it. Other solutions are still looked for with
+    /// the whole dictionary
+    #[arg(long, conflicts_with = "frequency_list")]
+    theme: Option<PathBuf>,
+}
+
+#[derive(Serialize)]
+#[serde(transparent)]
+struct GenerateOutput(GeneratedPuzzle);
+
+impl fmt::Display for GenerateOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let generated = &self.0;
+        let join = |words: &BTreeSet<&str>| Vec::from_iter(words.iter().copied()).join(" ");
+
+        for column in &generated.columns {
+            writeln!(f, "{column}")?;
+        }
+        writeln!(f)?;
+
+        let minimal = if generated.minimal {
+            "minimal"
+        } else {
+            "not minimal"
+        };
+        writeln!(f, "intended ({minimal}): {}", join(&generated.intended))?;
+        writeln!(
+            f,
+            "estimated steps: {}, branching: {:.2}",
+            generated.estimate.estimated_steps, generated.estimate.branching
+        )?;
+        for alternative in &generated.alternatives {
+            writeln!(f, "alternative: {}", join(alternative))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Builds a puzzle from words and reports whether they're its only minimal solution,
+/// or from similar words that are unique or in a difficulty band,
+/// or from dictionary words filling a shape, or for a date
+pub fn run(args: GenerateArgs, format: OutputFormat) -> Result<(), CliError> {
+    let config = SolverConfig {
+        max_steps: args.max_steps,
+        ..Default::default()
+    };
+    let vocabulary = match (&args.frequency_list, &args.theme) {
+        (Some(path), _) => {
+            let list = std::fs::read_to_string(path)?;
+            Vocabulary::common(list.lines().map(str::trim), args.max_rank)
+        }
+        (None, Some(path)) => {
+            let list = std::fs::read_to_string(path)?;
+            Vocabulary::themed(list.lines().map(str::trim))
+        }
+        (None, None) => Vocabulary::all(),
+    };
+    let words: Vec<&str> = args.words.iter().map(String::as_str).collect();
+    let generated = if let Some(date) = args.daily {
+        generate::daily(date)?
+    } else if let Some(columns) = args.columns {
+        let shape = PuzzleShape::uniform(columns, args.min_height..=args.max_height);
+        match args.seed {
+            _ if args.sample_letters => {
+                let seed = args.seed.unwrap_or_default();
+                generate::from_letter_frequencies(seed, &shape, &config, args.attempts)?
+            }
+            Some(seed) => generate::generate_random_with(seed, &shape, &vocabulary, &config)?,
+            None => generate::from_shape(&shape, &vocabulary, &config)?,
+        }
+    } else if let Some(required) = &args.required {
+        generate::with_required_word(&words, required, &vocabulary, &config, args.attempts)?
+    } else if args.unique {
+        generate::unique_from_words(&words, &vocabulary, &config, args.attempts)?
+    } else if let Some(band) = difficulty_band(&args) {
+        generate::with_difficulty(&words, &band, &vocabulary, &config, args.attempts)?
+    } else {
+        generate::from_words(&words, &config)?
+    };
+
+    if let Some(path) = &args.export {
+        let date = args.date.or(args.daily);
+        let definition = serde_json::to_string_pretty(&generated.definition(date)).unwrap();
+        if path.as_os_str() == "-" {
+            println!("{definition}");
+            return Ok(());
+        }
+        std::fs::write(path, definition + "\n")?;
+    }
+
+    if let Some(path) = &args.sheet {
+        let (solution, _steps) = generated.typeshift().solve_with(&config)?;
+        let solution = Vec::from_iter(solution.into_iter().map(String::from));
+        let sheet = render_sheet(&generated.columns, &solution, args.sheet_answers);
+        if path.as_os_str() == "-" {
+            print!("{sheet}");
+            return Ok(());
+        }
+        std::fs::write(path, sheet)?;
+    }
+
+    print!("{}", format.render(&GenerateOutput(generated)));
+
+    Ok(())
+}
+
+/// The band from the difficulty flags, if any were given
+fn difficulty_band(args: &GenerateArgs) -> Option<DifficultyBand> {
+    let any_bound = args.min_estimated_steps.is_some()
+        || args.max_estimated_steps.is_some()
+        || args.min_branching.is_some()
+        || args.max_branching.is_some();
+    if !any_bound {
+        return None;
+    }
+
+    let every = DifficultyBand::default();
+    Some(DifficultyBand {
+        estimated_steps: args
+            .min_estimated_steps
+            .unwrap_or(*every.estimated_steps.start())
+            ..=args
+                .max_estimated_steps
+                .unwrap_or(*every.estimated_steps.end()),
+        branching: args.min_branching.unwrap_or(*every.branching.start())
+            ..=args.max_branching.unwrap_or(*every.branching.end()),
+    })
+}
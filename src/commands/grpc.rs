@@ -0,0 +1,37 @@
+use std::net::SocketAddr;
+use std::time::Duration;
+
+use clap::Args;
+
+use typeshift_solver::grpc::{SolverServer, SolverService};
+
+use super::{CliError, ErrorKind};
+
+#[derive(Args)]
+pub struct GrpcArgs {
+    /// The address to listen on
+    #[arg(long, default_value = "127.0.0.1:50051")]
+    addr: SocketAddr,
+
+    /// The longest any request's search may take, in milliseconds
+    #[arg(long, default_value_t = 1000)]
+    timeout_ms: u64,
+}
+
+/// Serves the Solver service from proto/typeshift.proto until interrupted
+pub fn run(args: GrpcArgs) -> Result<(), CliError> {
+    let service = SolverService {
+        max_timeout: Duration::from_millis(args.timeout_ms),
+    };
+
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime
+        .block_on(async {
+            eprintln!("listening on {}", args.addr);
+            tonic::transport::Server::builder()
+                .add_service(SolverServer::new(service))
+                .serve(args.addr)
+                .await
+        })
+        .map_err(|e| CliError::new(ErrorKind::Io, e.to_string()))
+}
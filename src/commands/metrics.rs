@@ -0,0 +1,109 @@
+//! Prometheus metrics for the serve command
+
+use std::time::Duration;
+
+use prometheus::{Histogram, HistogramOpts, IntCounter, Registry, TextEncoder};
+
+use typeshift_solver::cache::{CachedSolution, MemoryCache, SolutionCache};
+use typeshift_solver::error::SolveError;
+
+/// The server's metrics, and the solution cache they count hits for
+#[derive(Debug)]
+pub struct Metrics {
+    registry: Registry,
+    solves: IntCounter,
+    timeouts: IntCounter,
+    steps: Histogram,
+    duration: Histogram,
+    cache_hits: IntCounter,
+    cache_misses: IntCounter,
+    cache: MemoryCache,
+}
+
+impl Metrics {
+    pub fn new() -> Self {
+        let solves = IntCounter::new("typeshift_solves_total", "Searches run, including failures");
+        let timeouts = IntCounter::new(
+            "typeshift_timeouts_total",
+            "Searches that hit the step or time limit",
+        );
+        let steps = Histogram::with_opts(
+            HistogramOpts::new(
+                "typeshift_solve_steps",
+                "Partial solutions touched by successful searches",
+            )
+            .buckets(prometheus::exponential_buckets(1.0, 4.0, 10).unwrap()),
+        );
+        let duration = Histogram::with_opts(HistogramOpts::new(
+            "typeshift_solve_duration_seconds",
+            "Time spent in searches, including failures",
+        ));
+        let cache_hits = IntCounter::new(
+            "typeshift_cache_hits_total",
+            "Searches answered from the solution cache",
+        );
+        let cache_misses = IntCounter::new(
+            "typeshift_cache_misses_total",
+            "Searches not found in the solution cache",
+        );
+
+        // the names and help are constants, so these can't fail
+        let metrics = Self {
+            registry: Registry::new(),
+            solves: solves.unwrap(),
+            timeouts: timeouts.unwrap(),
+            steps: steps.unwrap(),
+            duration: duration.unwrap(),
+            cache_hits: cache_hits.unwrap(),
+            cache_misses: cache_misses.unwrap(),
+            cache: MemoryCache::new(),
+        };
+        metrics.register(metrics.solves.clone());
+        metrics.register(metrics.timeouts.clone());
+        metrics.register(metrics.steps.clone());
+        metrics.register(metrics.duration.clone());
+        metrics.register(metrics.cache_hits.clone());
+        metrics.register(metrics.cache_misses.clone());
+
+        metrics
+    }
+
+    fn register(&self, collector: impl prometheus::core::Collector + 'static) {
+        self.registry.register(Box::new(collector)).unwrap();
+    }
+
+    /// Records the outcome of one search
+    pub fn record<T>(&self, result: &Result<(T, usize), SolveError>, elapsed: Duration) {
+        self.solves.inc();
+        self.duration.observe(elapsed.as_secs_f64());
+
+        match result {
+            Ok((_solution, steps)) => self.steps.observe(*steps as f64),
+            Err(SolveError::Timeout { .. }) => self.timeouts.inc(),
+            Err(_) => {}
+        }
+    }
+
+    /// Every metric, in Prometheus' text format
+    pub fn render(&self) -> String {
+        TextEncoder::new()
+            .encode_to_string(&self.registry.gather())
+            .unwrap()
+    }
+}
+
+impl SolutionCache for Metrics {
+    fn get(&self, key: &str) -> Option<CachedSolution> {
+        let solution = self.cache.get(key);
+        match solution {
+            Some(_) => self.cache_hits.inc(),
+            None => self.cache_misses.inc(),
+        }
+
+        solution
+    }
+
+    fn put(&self, key: &str, solution: CachedSolution) {
+        self.cache.put(key, solution);
+    }
+}
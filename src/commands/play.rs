@@ -0,0 +1,269 @@
+use std::path::PathBuf;
+
+use clap::Args;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Paragraph};
+use ratatui::{DefaultTerminal, Frame};
+
+use typeshift_solver::analysis::PlayFeedback;
+use typeshift_solver::game::GameState;
+use typeshift_solver::hint::{self, Hint, HintLevel};
+use typeshift_solver::Typeshift;
+
+use super::{read_puzzle, CliError, ErrorKind};
+
+#[derive(Args)]
+pub struct PlayArgs {
+    /// A puzzle file with one column per line, leftmost first; '-' reads stdin
+    puzzle: PathBuf,
+
+    /// Resume the game saved in this file, if there is one, and save it there on quitting
+    #[arg(long, value_name = "PATH")]
+    save: Option<PathBuf>,
+}
+
+pub fn run(args: PlayArgs) -> Result<(), CliError> {
+    let input = read_puzzle(&args.puzzle)?;
+    let typeshift = Typeshift::parse(&input)?;
+    let fewest_words = typeshift
+        .count_solutions(&Default::default())?
+        .minimal_words;
+    let mut game = Game::new(typeshift, fewest_words);
+    if let Some(path) = args.save.as_ref().filter(|path| path.exists()) {
+        let saved = std::fs::read_to_string(path)?;
+        let state: GameState = serde_json::from_str(&saved)
+            .map_err(|e| CliError::new(ErrorKind::Usage, format!("bad saved game: {e}")))?;
+        if state.columns() != game.state.columns() {
+            let message = "the saved game is of a different puzzle";
+            return Err(CliError::new(ErrorKind::Usage, message));
+        }
+        game.state = state;
+    }
+
+    let mut terminal = ratatui::init();
+    let result = game.run(&mut terminal);
+    ratatui::restore();
+    result?;
+
+    if let Some(path) = &args.save {
+        std::fs::write(path, serde_json::to_string(&game.state).unwrap())?;
+    }
+
+    Ok(())
+}
+
+/// The state of an interactive game
+struct Game {
+    typeshift: Typeshift,
+
+    state: GameState,
+
+    /// The words in a minimal solution, for the final comparison
+    fewest_words: usize,
+
+    /// The word last hinted at, and how much of it the hint gave away
+    last_hint: Option<(&'static str, HintLevel)>,
+
+    /// The word being typed
+    input: String,
+
+    /// Feedback from the last action
+    message: String,
+}
+
+impl Game {
+    fn new(typeshift: Typeshift, fewest_words: usize) -> Self {
+        Self {
+            state: GameState::new(&typeshift),
+            typeshift,
+            fewest_words,
+            last_hint: None,
+            input: String::new(),
+            message: "Type a word and press enter. Press * for a forced letter, ? for a hint (again for more), ! for the solver's favorites, ctrl-z/y to undo/redo, esc to quit.".to_string(),
+        }
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> std::io::Result<()> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+
+            let Event::Key(key) = event::read()? else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            match key.code {
+                KeyCode::Esc => return Ok(()),
+                KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                    return Ok(())
+                }
+                KeyCode::Char('z') if key.modifiers.contains(KeyModifiers::CONTROL) => self.undo(),
+                KeyCode::Char('y') if key.modifiers.contains(KeyModifiers::CONTROL) => self.redo(),
+                KeyCode::Char('?') => self.hint(),
+                KeyCode::Char('!') => self.favorites(),
+                KeyCode::Char('*') => self.forced(),
+                KeyCode::Char(ch) if ch.is_ascii_alphabetic() => {
+                    self.input.push(ch.to_ascii_lowercase())
+                }
+                KeyCode::Backspace => {
+                    self.input.pop();
+                }
+                KeyCode::Enter => self.submit(),
+                _ => {}
+            }
+        }
+    }
+
+    fn submit(&mut self) {
+        let word = std::mem::take(&mut self.input);
+
+        if let Err(e) = self.state.play(&word) {
+            self.message = e.to_string();
+            return;
+        }
+
+        if self.state.is_solved() {
+            self.message = format!(
+                "Solved with {} words and {} hints! The best possible is {} words.",
+                self.state.played().len(),
+                self.state.hints_used(),
+                self.fewest_words
+            );
+        } else {
+            self.message = match self.feedback() {
+                Some(feedback) => format!("Careful: {feedback}"),
+                None => format!("Played '{word}'"),
+            };
+        }
+    }
+
+    /// Judges the word just played, if it keeps the game from finishing in as few words
+    /// as it could have
+    fn feedback(&self) -> Option<PlayFeedback> {
+        let (word, earlier) = self.state.played().split_last()?;
+        let feedback =
+            PlayFeedback::new(&self.typeshift, earlier, word, &Default::default()).ok()?;
+
+        (!feedback.in_minimal_solution || feedback.extra_words() > 0).then_some(feedback)
+    }
+
+    fn undo(&mut self) {
+        self.message = match self.state.undo() {
+            Some(word) => format!("Took back '{word}'"),
+            None => "Nothing to undo".to_string(),
+        };
+    }
+
+    fn redo(&mut self) {
+        self.message = match self.state.redo() {
+            Some(word) => format!("Played '{word}' again"),
+            None => "Nothing to redo".to_string(),
+        };
+    }
+
+    /// Hints at the easiest of the fewest words that finish the game from here,
+    /// giving more of it away each time it's hinted at in a row
+    fn hint(&mut self) {
+        let remaining = match self.typeshift.complete(self.state.played()) {
+            Ok(remaining) => hint::human_order(&self.typeshift, &remaining, &[]),
+            Err(e) => {
+                self.message = e.to_string();
+                return;
+            }
+        };
+        let Some(word) = hint::suggest(&remaining, self.state.played()) else {
+            self.message = "No hint available; every letter is covered".to_string();
+            return;
+        };
+
+        let level = match self.last_hint {
+            Some((last, level)) if last == word => level.next().unwrap_or(level),
+            _ => HintLevel::Clue,
+        };
+        self.last_hint = Some((word, level));
+        self.state.record_hint();
+        self.message = format!("Try {}", Hint::new(&self.typeshift, word, level));
+    }
+
+    /// Points out a letter only one word left can cover, the gentlest hint
+    fn forced(&mut self) {
+        let forced = hint::forced_letters(&self.typeshift, self.state.played());
+        self.message = match forced.first() {
+            Some(forced) => {
+                self.state.record_hint();
+                format!("Forced letter: {forced}")
+            }
+            None => "No letter is forced; each uncovered letter has a choice of words".to_string(),
+        };
+    }
+
+    /// Shows the words the solver would try next, and the parts of its ranking for each
+    fn favorites(&mut self) {
+        self.message = match self.typeshift.score_words(self.state.played()) {
+            Ok(scores) => {
+                let best = Vec::from_iter(scores.iter().filter(|score| score.best));
+                let best = Vec::from_iter(best.iter().map(|score| score.to_string()));
+                match best.is_empty() {
+                    true => "Every word is played".to_string(),
+                    false => format!("The solver likes {}", best.join("; ")),
+                }
+            }
+            Err(e) => e.to_string(),
+        };
+    }
+
+    fn draw(&self, frame: &mut Frame) {
+        let [grid_area, played_area, input_area, message_area] = Layout::vertical([
+            Constraint::Min(4),
+            Constraint::Length(3),
+            Constraint::Length(3),
+            Constraint::Length(3),
+        ])
+        .areas(frame.area());
+
+        frame.render_widget(
+            Paragraph::new(self.grid_lines()).block(Block::bordered().title("Typeshift")),
+            grid_area,
+        );
+        frame.render_widget(
+            Paragraph::new(self.state.played().join(" ")).block(Block::bordered().title("Played")),
+            played_area,
+        );
+        frame.render_widget(
+            Paragraph::new(self.input.as_str()).block(Block::bordered().title("Word")),
+            input_area,
+        );
+        frame.render_widget(Paragraph::new(self.message.as_str()), message_area);
+    }
+
+    fn grid_lines(&self) -> Vec<Line<'static>> {
+        let columns = self.state.columns();
+        let height = columns.iter().map(|col| col.len()).max().unwrap_or(0);
+        let covered_style = Style::default()
+            .fg(Color::Green)
+            .add_modifier(Modifier::BOLD);
+
+        (0..height)
+            .map(|row| {
+                let spans: Vec<Span> = columns
+                    .iter()
+                    .enumerate()
+                    .map(|(col, letters)| match letters.chars().nth(row) {
+                        Some(ch) if self.state.is_covered(col, ch) => {
+                            Span::styled(format!("{} ", ch.to_ascii_uppercase()), covered_style)
+                        }
+                        Some(ch) => Span::raw(format!("{ch} ")),
+                        None => Span::raw("  "),
+                    })
+                    .collect();
+
+                Line::from(spans)
+            })
+            .collect()
+    }
+}
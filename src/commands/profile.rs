@@ -0,0 +1,143 @@
+//! Per-phase timing for `solve --profile`, and allocation counts with the
+//! `profile-allocations` feature
+
+#[cfg(feature = "profile-allocations")]
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::fmt;
+#[cfg(feature = "profile-allocations")]
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+
+use serde::Serialize;
+
+#[cfg(feature = "profile-allocations")]
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+#[cfg(feature = "profile-allocations")]
+static ALLOCATED_BYTES: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, counting allocations as they happen.
+/// Installed as the global allocator of the main binary with the `profile-allocations`
+/// feature. The counts are process-wide, so they include other threads' allocations.
+#[cfg(feature = "profile-allocations")]
+pub struct CountingAllocator;
+
+#[cfg(feature = "profile-allocations")]
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(layout.size(), Ordering::Relaxed);
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        ALLOCATED_BYTES.fetch_add(new_size, Ordering::Relaxed);
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// The allocations and bytes allocated so far, if they're being counted
+#[cfg(feature = "profile-allocations")]
+fn allocation_counts() -> Option<(usize, usize)> {
+    Some((
+        ALLOCATIONS.load(Ordering::Relaxed),
+        ALLOCATED_BYTES.load(Ordering::Relaxed),
+    ))
+}
+
+/// Without `profile-allocations` there's no counting allocator
+#[cfg(not(feature = "profile-allocations"))]
+fn allocation_counts() -> Option<(usize, usize)> {
+    None
+}
+
+/// The cost of one phase of a solve
+#[derive(Serialize)]
+pub struct PhaseProfile {
+    pub phase: &'static str,
+    pub ms: f64,
+    /// Allocations and reallocations, if counted
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allocations: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub allocated_bytes: Option<usize>,
+}
+
+/// Measures phases one after another
+#[derive(Default, Serialize)]
+#[serde(transparent)]
+pub struct Profiler {
+    phases: Vec<PhaseProfile>,
+}
+
+impl Profiler {
+    /// Runs one phase, recording its time, and its allocations if they're being counted
+    pub fn phase<T>(&mut self, phase: &'static str, f: impl FnOnce() -> T) -> T {
+        let before = allocation_counts();
+        let start = Instant::now();
+
+        let result = f();
+
+        let ms = start.elapsed().as_secs_f64() * 1000.0;
+        let counts = before.zip(allocation_counts());
+        self.phases.push(PhaseProfile {
+            phase,
+            ms,
+            allocations: counts.map(|(before, after)| after.0 - before.0),
+            allocated_bytes: counts.map(|(before, after)| after.1 - before.1),
+        });
+
+        result
+    }
+}
+
+impl fmt::Display for Profiler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let width = self.phases.iter().map(|p| p.phase.len()).max().unwrap_or(0);
+        let counted = allocation_counts().is_some();
+        // the allocation columns, or nothing when they aren't counted
+        let counts = |allocations: &dyn fmt::Display, bytes: &dyn fmt::Display| match counted {
+            true => format!("  {allocations:>8}  {bytes:>10}"),
+            false => String::new(),
+        };
+
+        writeln!(
+            f,
+            "{:width$}  {:>10}{}",
+            "phase",
+            "ms",
+            counts(&"allocs", &"bytes")
+        )?;
+        for phase in &self.phases {
+            let allocations = phase.allocations.unwrap_or_default();
+            let bytes = phase.allocated_bytes.unwrap_or_default();
+            writeln!(
+                f,
+                "{:width$}  {:>10.3}{}",
+                phase.phase,
+                phase.ms,
+                counts(&allocations, &bytes)
+            )?;
+        }
+
+        let ms: f64 = self.phases.iter().map(|p| p.ms).sum();
+        let allocations: usize = self.phases.iter().filter_map(|p| p.allocations).sum();
+        let bytes: usize = self.phases.iter().filter_map(|p| p.allocated_bytes).sum();
+        writeln!(
+            f,
+            "{:width$}  {ms:>10.3}{}",
+            "total",
+            counts(&allocations, &bytes)
+        )
+    }
+}
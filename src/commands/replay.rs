@@ -0,0 +1,80 @@
+use std::fmt;
+use std::io::{self, BufRead, IsTerminal, Write};
+use std::path::PathBuf;
+
+use clap::Args;
+use serde::Serialize;
+
+use typeshift_solver::report::OutputFormat;
+use typeshift_solver::trace::{Trace, TraceEvent};
+
+use super::CliError;
+
+#[derive(Args)]
+pub struct ReplayArgs {
+    /// A trace file written by `solve --record`
+    trace: PathBuf,
+}
+
+/// The whole trace, for non-interactive output
+#[derive(Serialize)]
+#[serde(transparent)]
+struct ReplayOutput(Trace);
+
+impl fmt::Display for ReplayOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "puzzle: {}", self.0.columns.join(" "))?;
+        for event in &self.0.events {
+            writeln!(f, "{event}")?;
+        }
+
+        Ok(())
+    }
+}
+
+const HELP: &str = "enter: next event, e: next expansion, b: back, <n>: go to event n, q: quit";
+
+/// Steps through the trace when run interactively; otherwise prints all of it
+pub fn run(args: ReplayArgs, format: OutputFormat) -> Result<(), CliError> {
+    let file = std::fs::File::open(&args.trace)?;
+    let trace = Trace::read_from(io::BufReader::new(file))?;
+
+    if format != OutputFormat::Plain || !io::stdin().is_terminal() {
+        print!("{}", format.render(&ReplayOutput(trace)));
+        return Ok(());
+    }
+
+    println!("puzzle: {}", trace.columns.join(" "));
+    println!("{} events; {HELP}", trace.events.len());
+
+    let events = &trace.events;
+    let mut current = 0;
+    let mut lines = io::stdin().lock().lines();
+    while let Some(event) = events.get(current) {
+        print!("[{}/{}] {event} > ", current + 1, events.len());
+        io::stdout().flush()?;
+
+        let Some(line) = lines.next().transpose()? else {
+            break;
+        };
+
+        match line.trim() {
+            "" | "n" => current += 1,
+            "e" => {
+                current = (current + 1..events.len())
+                    .find(|&i| matches!(events[i], TraceEvent::Popped { .. }))
+                    .unwrap_or(events.len());
+            }
+            "b" => current = current.saturating_sub(1),
+            "q" => return Ok(()),
+            other => match other.parse::<usize>() {
+                Ok(n) if (1..=events.len()).contains(&n) => current = n - 1,
+                _ => println!("{HELP}"),
+            },
+        }
+    }
+
+    println!("end of trace");
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use clap::Args;
+use serde::Serialize;
+
+use typeshift_solver::analysis::MistakeReport;
+use typeshift_solver::report::OutputFormat;
+use typeshift_solver::{SolverConfig, Typeshift};
+
+use super::{read_puzzle, CliError};
+
+#[derive(Args)]
+pub struct ReviewArgs {
+    /// A puzzle file with one column per line, leftmost first; '-' reads stdin
+    puzzle: PathBuf,
+
+    /// Every word of the finished game
+    #[arg(required = true)]
+    words: Vec<String>,
+
+    /// Give up after touching this many partial solutions
+    #[arg(long)]
+    max_steps: Option<usize>,
+}
+
+#[derive(Serialize)]
+#[serde(transparent)]
+struct Review(MistakeReport);
+
+impl fmt::Display for Review {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Compares a finished game to the fewest words possible, with the changes that close the gap
+pub fn run(args: ReviewArgs, format: OutputFormat) -> Result<(), CliError> {
+    let config = SolverConfig {
+        max_steps: args.max_steps,
+        ..Default::default()
+    };
+    let typeshift = Typeshift::parse(&read_puzzle(&args.puzzle)?)?;
+
+    let words = Vec::from_iter(args.words.iter().map(String::as_str));
+    let report = MistakeReport::new(&typeshift, &words, &config)?;
+    print!("{}", format.render(&Review(report)));
+
+    Ok(())
+}
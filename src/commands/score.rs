@@ -0,0 +1,68 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use clap::Args;
+use serde::Serialize;
+
+use typeshift_solver::report::OutputFormat;
+use typeshift_solver::score::{Score, ScoringRules};
+use typeshift_solver::{SolverConfig, Typeshift};
+
+use super::{read_puzzle, CliError};
+
+#[derive(Args)]
+pub struct ScoreArgs {
+    /// A puzzle file with one column per line, leftmost first; '-' reads stdin
+    puzzle: PathBuf,
+
+    /// The words played, in order; the solver's solution if none are given
+    words: Vec<String>,
+
+    /// Give up finding the fewest words after touching this many partial solutions
+    #[arg(long)]
+    max_steps: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct ScoreOutput {
+    words: Vec<String>,
+    #[serde(flatten)]
+    score: Score,
+}
+
+impl fmt::Display for ScoreOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (word, points) in self.words.iter().zip(&self.score.word_points) {
+            writeln!(f, "{word}: {points}")?;
+        }
+        writeln!(f, "completion bonus: {}", self.score.completion_bonus)?;
+        writeln!(f, "efficiency bonus: {}", self.score.efficiency_bonus)?;
+        writeln!(f, "total: {}", self.score.total)
+    }
+}
+
+/// Predicts the in-game score of playing the words in order
+pub fn run(args: ScoreArgs, format: OutputFormat) -> Result<(), CliError> {
+    let config = SolverConfig {
+        max_steps: args.max_steps,
+        ..Default::default()
+    };
+    let typeshift = Typeshift::parse(&read_puzzle(&args.puzzle)?)?;
+    let minimal_words = typeshift.count_solutions(&config)?.minimal_words;
+
+    let words = if args.words.is_empty() {
+        let (solution, _steps) = typeshift.solve_with(&config)?;
+        solution.into_iter().map(String::from).collect()
+    } else {
+        args.words
+    };
+    let score = ScoringRules::default().score(
+        &typeshift,
+        words.iter().map(String::as_str),
+        minimal_words,
+    )?;
+
+    print!("{}", format.render(&ScoreOutput { words, score }));
+
+    Ok(())
+}
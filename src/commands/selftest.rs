@@ -0,0 +1,137 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use typeshift_solver::archive::PuzzleArchive;
+use typeshift_solver::report::{OutputFormat, SolutionReport};
+use typeshift_solver::Typeshift;
+
+use super::{CliError, ErrorKind};
+
+/// Embedded puzzles with their known answers: the date, the number of possible words,
+/// and the first solution
+const KNOWN_ANSWERS: [(&str, usize, &[&str]); 4] = [
+    (
+        "2023-11-16",
+        31,
+        &["above", "basic", "study", "wheel", "whups"],
+    ),
+    (
+        "2023-11-17",
+        60,
+        &["again", "gater", "mouth", "quick", "woods"],
+    ),
+    ("2023-11-18", 26, &["backup", "fridge", "heists", "lander"]),
+    (
+        "2023-11-19",
+        70,
+        &["chumps", "corves", "fifers", "granny", "poiser"],
+    ),
+];
+
+/// The outcome of one known puzzle
+#[derive(Serialize)]
+struct Check {
+    date: &'static str,
+    passed: bool,
+    /// What went wrong, if it failed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    problem: Option<String>,
+}
+
+#[derive(Serialize)]
+struct SelftestOutput {
+    checks: Vec<Check>,
+}
+
+impl fmt::Display for SelftestOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for check in &self.checks {
+            match &check.problem {
+                None => writeln!(f, "ok    {}", check.date)?,
+                Some(problem) => writeln!(f, "FAIL  {}: {problem}", check.date)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Solves embedded puzzles with known answers,
+/// to check that the dictionary and solver in this binary work
+pub fn run(format: OutputFormat) -> Result<(), CliError> {
+    let archive = PuzzleArchive::embedded();
+
+    let checks: Vec<Check> = KNOWN_ANSWERS
+        .iter()
+        .map(|&(date, possible_words, solution)| {
+            let problem = check(&archive, date, possible_words, solution).err();
+            Check {
+                date,
+                passed: problem.is_none(),
+                problem,
+            }
+        })
+        .collect();
+
+    let failures = checks.iter().filter(|check| !check.passed).count();
+    print!("{}", format.render(&SelftestOutput { checks }));
+
+    if failures > 0 {
+        return Err(CliError::new(
+            ErrorKind::SelfTest,
+            format!(
+                "{failures} of {} self-test puzzles failed",
+                KNOWN_ANSWERS.len()
+            ),
+        ));
+    }
+
+    Ok(())
+}
+
+fn check(
+    archive: &PuzzleArchive,
+    date: &str,
+    possible_words: usize,
+    solution: &[&str],
+) -> Result<(), String> {
+    let puzzle = archive
+        .get(date.parse().unwrap())
+        .ok_or("the puzzle is not embedded")?;
+    let typeshift = Typeshift::parse(&puzzle.input).map_err(|e| e.to_string())?;
+    let report = SolutionReport::with_config(&typeshift, false, &Default::default())
+        .map_err(|e| e.to_string())?;
+
+    if report.possible_words != possible_words {
+        return Err(format!(
+            "expected {possible_words} possible words, found {}",
+            report.possible_words
+        ));
+    }
+    if report.solution != solution {
+        return Err(format!(
+            "expected the solution '{}', found '{}'",
+            solution.join(" "),
+            report.solution.join(" ")
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn known_answers_match() {
+        let archive = PuzzleArchive::embedded();
+        for (date, possible_words, solution) in KNOWN_ANSWERS {
+            let result = check(&archive, date, possible_words, solution);
+            assert_eq!((date, result), (date, Ok(())));
+        }
+    }
+}
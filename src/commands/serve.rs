@@ -0,0 +1,176 @@
+use std::future::Future;
+use std::net::SocketAddr;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use axum::extract::{Json, State};
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use axum::routing::{get, post};
+use axum::Router;
+use clap::Args;
+use serde::{Deserialize, Serialize};
+
+use typeshift_solver::async_solve::{solve_all_async, solve_async};
+use typeshift_solver::error::SolveError;
+use typeshift_solver::hint;
+use typeshift_solver::puzzle::PuzzleDefinition;
+use typeshift_solver::report::SolutionReport;
+use typeshift_solver::{SolverConfig, Typeshift};
+
+use super::metrics::Metrics;
+use super::{CliError, ErrorKind};
+
+#[derive(Args)]
+pub struct ServeArgs {
+    /// The address to listen on
+    #[arg(long, default_value = "127.0.0.1:3000")]
+    addr: SocketAddr,
+
+    /// Give up on each request's search after this many milliseconds
+    #[arg(long, default_value_t = 1000)]
+    timeout_ms: u64,
+}
+
+#[derive(Deserialize)]
+struct SolveRequest {
+    #[serde(flatten)]
+    puzzle: PuzzleDefinition,
+
+    /// Also find the smallest solutions the search finds
+    #[serde(default)]
+    all: bool,
+}
+
+#[derive(Deserialize)]
+struct HintRequest {
+    #[serde(flatten)]
+    puzzle: PuzzleDefinition,
+
+    /// The words played so far
+    #[serde(default)]
+    played: Vec<String>,
+}
+
+#[derive(Serialize)]
+struct HintResponse {
+    /// A word that covers a new letter, or null if every letter is covered
+    hint: Option<String>,
+}
+
+/// A failed request, with the same body as the CLI's structured errors
+struct ApiError(CliError);
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        #[derive(Serialize)]
+        struct ErrorBody {
+            error: CliError,
+        }
+
+        let status = match self.0.kind {
+            ErrorKind::Parse | ErrorKind::Usage => StatusCode::BAD_REQUEST,
+            ErrorKind::Unsolvable | ErrorKind::GaveUp => StatusCode::UNPROCESSABLE_ENTITY,
+            ErrorKind::Timeout => StatusCode::SERVICE_UNAVAILABLE,
+            ErrorKind::Io | ErrorKind::SelfTest => StatusCode::INTERNAL_SERVER_ERROR,
+        };
+
+        (status, Json(ErrorBody { error: self.0 })).into_response()
+    }
+}
+
+impl<E: Into<CliError>> From<E> for ApiError {
+    fn from(e: E) -> Self {
+        Self(e.into())
+    }
+}
+
+/// The state shared by every request
+#[derive(Clone, Copy)]
+struct Server {
+    config: SolverConfig,
+    metrics: &'static Metrics,
+}
+
+impl Server {
+    /// Runs a search, recording it in the metrics
+    async fn search<T>(
+        &self,
+        search: impl Future<Output = Result<(T, usize), SolveError>>,
+    ) -> Result<(T, usize), SolveError> {
+        let start = Instant::now();
+        let result = search.await;
+        self.metrics.record(&result, start.elapsed());
+
+        result
+    }
+}
+
+/// Serves POST /solve, POST /hint, and GET /metrics until interrupted.
+/// A search stops early if its client disconnects.
+pub fn run(args: ServeArgs) -> Result<(), CliError> {
+    // the metrics count hits for the cache they hold, for the life of the server
+    let metrics: &'static Metrics = Box::leak(Box::new(Metrics::new()));
+    let config = SolverConfig {
+        timeout: Some(Duration::from_millis(args.timeout_ms)),
+        cache: Some(metrics),
+        ..Default::default()
+    };
+    let app = Router::new()
+        .route("/solve", post(solve))
+        .route("/hint", post(hint))
+        .route("/metrics", get(metrics_text))
+        .with_state(Server { config, metrics });
+
+    let runtime = tokio::runtime::Runtime::new()?;
+    runtime.block_on(async {
+        let listener = tokio::net::TcpListener::bind(args.addr).await?;
+        eprintln!("listening on http://{}", listener.local_addr()?);
+
+        axum::serve(listener, app).await
+    })?;
+
+    Ok(())
+}
+
+async fn solve(
+    State(server): State<Server>,
+    Json(request): Json<SolveRequest>,
+) -> Result<Json<SolutionReport>, ApiError> {
+    let typeshift = Arc::new(Typeshift::parse(&request.puzzle.to_input())?);
+    let config = server.config;
+
+    let (solution, steps) = server
+        .search(solve_async(typeshift.clone(), config))
+        .await?;
+    let all_solutions = if request.all {
+        let all = solve_all_async(typeshift.clone(), config);
+        Some(server.search(all).await?.0)
+    } else {
+        None
+    };
+
+    Ok(Json(SolutionReport::from_solutions(
+        &typeshift,
+        &solution,
+        steps,
+        all_solutions.as_ref(),
+    )))
+}
+
+async fn hint(
+    State(server): State<Server>,
+    Json(request): Json<HintRequest>,
+) -> Result<Json<HintResponse>, ApiError> {
+    let typeshift = Arc::new(Typeshift::parse(&request.puzzle.to_input())?);
+
+    let (solution, _steps) = server.search(solve_async(typeshift, server.config)).await?;
+    let played: Vec<&str> = request.played.iter().map(String::as_str).collect();
+    let hint = hint::suggest(&solution, &played).map(String::from);
+
+    Ok(Json(HintResponse { hint }))
+}
+
+async fn metrics_text(State(server): State<Server>) -> String {
+    server.metrics.render()
+}
@@ -0,0 +1,99 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use clap::Args;
+use serde::Serialize;
+
+use typeshift_solver::minimize::minimize;
+use typeshift_solver::report::OutputFormat;
+use typeshift_solver::{SolverConfig, Typeshift};
+
+use super::{read_puzzle, CliError, ErrorKind};
+
+#[derive(Args)]
+pub struct ShrinkArgs {
+    /// A puzzle file with one column per line, leftmost first; '-' reads stdin
+    puzzle: PathBuf,
+
+    /// Keep the puzzle slow: its first solution takes at least this many steps
+    #[arg(long, required_unless_present = "mismatch")]
+    min_steps: Option<usize>,
+
+    /// Keep the solver's answers inconsistent: the best-first solution isn't minimal, or
+    /// listing every minimal solution finds fewer than the exact count
+    #[arg(long, conflicts_with = "min_steps")]
+    mismatch: bool,
+
+    /// Give up checking each candidate after touching this many partial solutions,
+    /// counting it as uninteresting
+    #[arg(long)]
+    max_steps: Option<usize>,
+}
+
+#[derive(Serialize)]
+struct ShrinkOutput {
+    columns: Vec<String>,
+    letters_removed: usize,
+}
+
+impl fmt::Display for ShrinkOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for column in &self.columns {
+            writeln!(f, "{column}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Removes letters from a puzzle while it stays slow or inconsistent,
+/// printing the smallest puzzle found in the plain input format
+pub fn run(args: ShrinkArgs, format: OutputFormat) -> Result<(), CliError> {
+    let config = SolverConfig {
+        max_steps: args.max_steps,
+        ..Default::default()
+    };
+    let columns = Typeshift::parse_columns(&read_puzzle(&args.puzzle)?)?;
+
+    let interesting = |columns: &[String]| {
+        let typeshift = Typeshift::from_columns(columns.to_vec());
+        match args.min_steps {
+            Some(min_steps) => {
+                let solved = typeshift.solve_with(&config);
+                solved.is_ok_and(|(_solution, steps)| steps >= min_steps)
+            }
+            None => mismatched(&typeshift, &config),
+        }
+    };
+    if !interesting(&columns) {
+        let message = "the puzzle doesn't have the property to keep";
+        return Err(CliError::new(ErrorKind::Usage, message));
+    }
+
+    let letters = |columns: &[String]| columns.iter().map(String::len).sum::<usize>();
+    let before = letters(&columns);
+    let columns = minimize(columns, interesting);
+    let letters_removed = before - letters(&columns);
+
+    eprintln!("removed {letters_removed} letters");
+    let output = ShrinkOutput {
+        columns,
+        letters_removed,
+    };
+    print!("{}", format.render(&output));
+
+    Ok(())
+}
+
+/// True if the searches disagree with the exact count of minimal solutions
+fn mismatched(typeshift: &Typeshift, config: &SolverConfig) -> bool {
+    let (Ok((solution, _)), Ok((all_solutions, _)), Ok(count)) = (
+        typeshift.solve_with(config),
+        typeshift.solve_all_with(config),
+        typeshift.count_solutions(config),
+    ) else {
+        return false;
+    };
+
+    solution.len() > count.minimal_words || (all_solutions.len() as u64) < count.solutions
+}
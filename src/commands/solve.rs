@@ -0,0 +1,321 @@
+use std::io::IsTerminal;
+use std::path::PathBuf;
+use std::time::Duration;
+
+use clap::{Args, ValueEnum};
+
+use typeshift_solver::accessible::render_accessible;
+use typeshift_solver::analysis::{NearestAlternative, UnsolvableCore, WordExplanation};
+use typeshift_solver::cache::{FileCache, SolutionCache};
+use typeshift_solver::chat::render_chat;
+use typeshift_solver::date::Date;
+use typeshift_solver::grid::render_grid;
+use typeshift_solver::hint::human_order;
+use typeshift_solver::history::{self, HistoryEntry};
+use typeshift_solver::report::{OutputFormat, SolutionReport};
+use typeshift_solver::trace::Trace;
+use typeshift_solver::{SolverConfig, Typeshift};
+
+use super::decisions::DecisionLog;
+use super::profile::Profiler;
+use super::{batch, read_puzzle, watch, CliError, ErrorKind};
+
+#[derive(Args)]
+pub struct SolveArgs {
+    /// A puzzle file with one column per line, leftmost first; '-' reads stdin
+    #[arg(required_unless_present_any = ["watch", "batch"])]
+    puzzle: Option<PathBuf>,
+
+    /// Solve the puzzle files named on stdin, one path per line,
+    /// writing one JSON result per line (ndjson) whatever the format
+    #[arg(long, conflicts_with_all = ["puzzle", "watch", "record"])]
+    batch: bool,
+
+    /// Watch a directory, solving puzzle files (*.txt or *.json) as they are added or changed
+    #[arg(long, value_name = "DIR", conflicts_with = "puzzle")]
+    watch: Option<PathBuf>,
+
+    /// How often to check the watched directory, in milliseconds
+    #[arg(long, default_value_t = 1000, requires = "watch")]
+    poll_ms: u64,
+
+    /// Give up after touching this many partial solutions
+    #[arg(long)]
+    max_steps: Option<usize>,
+
+    /// Give up after searching for this many milliseconds
+    #[arg(long)]
+    timeout_ms: Option<u64>,
+
+    /// Break ties in the search's rankings in a random order from this seed,
+    /// for sampling other minimal solutions; the same seed finds the same solution
+    #[arg(long)]
+    tie_seed: Option<u64>,
+
+    /// Append the solve to this history file, for the stats command
+    #[arg(long, env = "TYPESHIFT_HISTORY")]
+    history: Option<PathBuf>,
+
+    /// How many words you used, recorded in the history
+    #[arg(long, requires = "history")]
+    my_words: Option<usize>,
+
+    /// Reuse first solutions from this cache file, and add new ones to it
+    #[arg(long, value_name = "PATH", env = "TYPESHIFT_CACHE")]
+    cache: Option<PathBuf>,
+
+    /// Also find the smallest solutions the search finds
+    #[arg(long)]
+    all: bool,
+
+    /// Report time for each phase of the solve, and allocations in builds with
+    /// the profile-allocations feature
+    #[arg(long)]
+    profile: bool,
+
+    /// Explain why the solution uses each word: the letters only it covers,
+    /// the rarest of them, and the words that could replace it
+    #[arg(long)]
+    explain: bool,
+
+    /// Also find the minimal solution that differs least from this one,
+    /// or report that this one is the only one
+    #[arg(long)]
+    alternative: bool,
+
+    /// Record every step of the search to this file, for the replay command
+    #[arg(long, value_name = "PATH", conflicts_with = "watch")]
+    record: Option<PathBuf>,
+
+    /// Log every decision of the search to this file as JSON lines,
+    /// for analyzing the ranking heuristics; works with --batch
+    #[arg(long, value_name = "PATH", conflicts_with = "watch")]
+    decision_log: Option<PathBuf>,
+
+    /// When to colorize the solution grid in plain output
+    #[arg(long, value_enum, default_value_t)]
+    color: ColorChoice,
+
+    /// How to order the solution's words
+    #[arg(long, value_enum, default_value_t, conflicts_with_all = ["watch", "batch"])]
+    order: WordOrder,
+
+    /// A word list ordered most common first, for putting common words first
+    /// in the human order
+    #[arg(long, value_name = "PATH")]
+    frequency_list: Option<PathBuf>,
+}
+
+#[derive(Default, Clone, Copy, PartialEq, Eq, ValueEnum)]
+enum WordOrder {
+    #[default]
+    Alphabetical,
+    /// Easiest to find first: common words, then words without rare letters
+    Human,
+}
+
+#[derive(Default, Clone, Copy, ValueEnum)]
+enum ColorChoice {
+    /// Colorize when writing to a terminal, unless NO_COLOR is set
+    #[default]
+    Auto,
+    Always,
+    Never,
+}
+
+impl ColorChoice {
+    fn enabled(self) -> bool {
+        match self {
+            ColorChoice::Auto => {
+                std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none()
+            }
+            ColorChoice::Always => true,
+            ColorChoice::Never => false,
+        }
+    }
+}
+
+/// A solution report, with a profile if requested
+#[derive(serde::Serialize)]
+struct SolveOutput {
+    #[serde(flatten)]
+    report: SolutionReport,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    explanations: Option<Vec<WordExplanation>>,
+    /// null when the solution is unique
+    #[serde(skip_serializing_if = "Option::is_none")]
+    alternative: Option<Option<NearestAlternative>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    profile: Option<Profiler>,
+}
+
+impl std::fmt::Display for SolveOutput {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.report)?;
+        if let Some(explanations) = &self.explanations {
+            writeln!(f)?;
+            for explanation in explanations {
+                writeln!(f, "{explanation}")?;
+            }
+        }
+        match &self.alternative {
+            Some(Some(alternative)) => writeln!(f, "\nanother way: {alternative}")?,
+            Some(None) => writeln!(f, "\nno other solution uses as few words")?,
+            None => {}
+        }
+        if let Some(profile) = &self.profile {
+            writeln!(f)?;
+            write!(f, "{profile}")?;
+        }
+
+        Ok(())
+    }
+}
+
+pub fn run(args: SolveArgs, format: OutputFormat) -> Result<(), CliError> {
+    // the cache lives for the rest of the process, like the config that borrows it
+    let cache: Option<&'static dyn SolutionCache> = match &args.cache {
+        Some(path) => Some(Box::leak(Box::new(FileCache::open(path)?))),
+        None => None,
+    };
+    let config = SolverConfig {
+        max_steps: args.max_steps,
+        timeout: args.timeout_ms.map(Duration::from_millis),
+        cache,
+        tie_seed: args.tie_seed,
+        ..Default::default()
+    };
+
+    if let Some(dir) = &args.watch {
+        let interval = Duration::from_millis(args.poll_ms);
+        return watch::run(dir, interval, args.all, &config, format);
+    }
+    if args.batch {
+        return batch::run(args.all, &config, args.decision_log.as_deref());
+    }
+
+    let puzzle = args.puzzle.as_deref().unwrap();
+    let input = read_puzzle(puzzle)?;
+
+    let mut profiler = Profiler::default();
+    let columns = profiler.phase("parse", || Typeshift::parse_columns(&input))?;
+    let typeshift = profiler.phase("filter dictionary", || Typeshift::from_columns(columns));
+
+    let mut decisions = args
+        .decision_log
+        .as_deref()
+        .map(DecisionLog::create)
+        .transpose()?;
+    if let Some(log) = &mut decisions {
+        log.start(&puzzle.display().to_string());
+    }
+    let trace = args.record.as_ref().map(|_| Trace::new(&typeshift));
+
+    // observing bypasses the solution cache, which would skip the search
+    let mut observer = (trace, decisions);
+    let observed = observer.0.is_some() || observer.1.is_some();
+    let (solution, steps) = profiler
+        .phase("search", || {
+            if observed {
+                typeshift.solve_observed(&config, &mut observer)
+            } else {
+                typeshift.solve_with(&config)
+            }
+        })
+        .map_err(|e| match UnsolvableCore::new(&typeshift) {
+            Some(core) => unsolvable_error(&core),
+            None => e.into(),
+        })?;
+    let (trace, decisions) = observer;
+    let all_solutions = if args.all {
+        Some(
+            profiler
+                .phase("search all", || typeshift.solve_all_with(&config))?
+                .0,
+        )
+    } else {
+        None
+    };
+    let alternative = match args.alternative {
+        true => Some(NearestAlternative::new(&typeshift, &solution, &config)?),
+        false => None,
+    };
+    let mut report = profiler.phase("report", || {
+        SolutionReport::from_solutions(&typeshift, &solution, steps, all_solutions.as_ref())
+    });
+    if args.order == WordOrder::Human {
+        let frequency_list = match &args.frequency_list {
+            Some(path) => std::fs::read_to_string(path)?,
+            None => String::new(),
+        };
+        let frequency_list = Vec::from_iter(frequency_list.lines().map(str::trim));
+        let ordered = human_order(&typeshift, &solution, &frequency_list);
+        report.solution = ordered.into_iter().map(String::from).collect();
+    }
+
+    if let (Some(path), Some(trace)) = (&args.record, &trace) {
+        let file = std::fs::File::create(path)?;
+        trace.write_to(std::io::BufWriter::new(file))?;
+    }
+    if let Some(log) = decisions {
+        log.finish()?;
+    }
+
+    let output = SolveOutput {
+        report,
+        explanations: (args.explain).then(|| WordExplanation::for_solution(&typeshift, &solution)),
+        alternative,
+        profile: args.profile.then_some(profiler),
+    };
+
+    if let Some(path) = &args.history {
+        let entry = HistoryEntry {
+            date: Date::today(),
+            puzzle_hash: history::puzzle_hash(typeshift.input_columns()),
+            optimal_words: output.report.solution.len(),
+            player_words: args.my_words,
+        };
+        history::append(path, &entry)?;
+    }
+
+    match format {
+        OutputFormat::Chat => {
+            let report = &output.report;
+            print!("{}", render_chat(&report.columns, &report.solution));
+        }
+        OutputFormat::Accessible => {
+            let report = &output.report;
+            print!("{}", render_accessible(&report.columns, &report.solution));
+        }
+        _ => print!("{}", format.render(&output)),
+    }
+
+    if format == OutputFormat::Plain {
+        let report = &output.report;
+        println!();
+        print!(
+            "{}",
+            render_grid(&report.columns, &report.solution, args.color.enabled())
+        );
+    }
+
+    Ok(())
+}
+
+/// Names the letters no word uses, and the dictionary words that nearly fit them
+fn unsolvable_error(core: &UnsolvableCore) -> CliError {
+    let cells: Vec<_> = (core.cells.iter())
+        .map(|cell| {
+            let mut message = format!("no word has {} in column {}", cell.letter, cell.column + 1);
+            if !cell.near_words.is_empty() {
+                let near_words = cell.near_words.iter().take(5).copied();
+                let near_words = Vec::from_iter(near_words).join(", ");
+                message.push_str(&format!(" (nearly: {near_words})"));
+            }
+            message
+        })
+        .collect();
+
+    let message = format!("the puzzle has no solution: {}", cells.join("; "));
+    CliError::new(ErrorKind::Unsolvable, message)
+}
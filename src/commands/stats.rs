@@ -0,0 +1,56 @@
+use std::fmt;
+use std::path::PathBuf;
+
+use clap::Args;
+use serde::Serialize;
+
+use typeshift_solver::date::Date;
+use typeshift_solver::history::{self, HistoryStats};
+use typeshift_solver::report::OutputFormat;
+
+use super::CliError;
+
+#[derive(Args)]
+pub struct StatsArgs {
+    /// The history file written by `solve --history`
+    #[arg(long, env = "TYPESHIFT_HISTORY")]
+    history: PathBuf,
+}
+
+#[derive(Serialize)]
+#[serde(transparent)]
+struct StatsOutput(HistoryStats);
+
+impl fmt::Display for StatsOutput {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let stats = &self.0;
+        let average = |avg: Option<f64>| match avg {
+            Some(avg) => format!("{avg:.2}"),
+            None => "-".to_string(),
+        };
+
+        writeln!(f, "solves: {}", stats.solves)?;
+        writeln!(f, "current streak: {} days", stats.current_streak)?;
+        writeln!(f, "longest streak: {} days", stats.longest_streak)?;
+        writeln!(
+            f,
+            "average optimal words: {}",
+            average(stats.average_optimal_words)
+        )?;
+        writeln!(
+            f,
+            "average words used: {}",
+            average(stats.average_player_words)
+        )?;
+        writeln!(f, "optimal solves: {}", stats.optimal_solves)
+    }
+}
+
+pub fn run(args: StatsArgs, format: OutputFormat) -> Result<(), CliError> {
+    let entries = history::load(&args.history)?;
+    let stats = HistoryStats::new(&entries, Date::today());
+
+    print!("{}", format.render(&StatsOutput(stats)));
+
+    Ok(())
+}
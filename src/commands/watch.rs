@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+use typeshift_solver::report::{OutputFormat, SolutionReport};
+use typeshift_solver::{SolverConfig, Typeshift};
+
+use super::{read_puzzle, CliError};
+
+/// A solved puzzle file
+#[derive(serde::Serialize)]
+struct WatchedSolve {
+    file: String,
+    #[serde(flatten)]
+    report: SolutionReport,
+}
+
+impl fmt::Display for WatchedSolve {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "== {} ==", self.file)?;
+        writeln!(f, "{}", self.report)
+    }
+}
+
+/// Polls a directory for new or modified puzzle files, solving each as it appears.
+/// Files already present when watching starts are solved first.
+/// Failures for individual puzzles are reported without stopping the watch.
+pub fn run(
+    dir: &Path,
+    interval: Duration,
+    include_all: bool,
+    config: &SolverConfig,
+    format: OutputFormat,
+) -> Result<(), CliError> {
+    let mut seen: HashMap<PathBuf, SystemTime> = HashMap::new();
+
+    loop {
+        for (path, modified) in changed_files(dir, &seen)? {
+            seen.insert(path.clone(), modified);
+
+            // the file may still be mid-write; it will be retried once it changes again
+            let Ok(input) = read_puzzle(&path) else {
+                continue;
+            };
+            if input.trim().is_empty() {
+                continue;
+            }
+
+            let file = path.display().to_string();
+            let report = Typeshift::parse(&input)
+                .map_err(CliError::from)
+                .and_then(|typeshift| {
+                    SolutionReport::with_config(&typeshift, include_all, config)
+                        .map_err(CliError::from)
+                });
+
+            match report {
+                Ok(report) => print!("{}", format.render(&WatchedSolve { file, report })),
+                Err(mut error) => {
+                    error.message = format!("{file}: {}", error.message);
+                    error.report(format);
+                }
+            }
+        }
+
+        std::thread::sleep(interval);
+    }
+}
+
+/// Returns the puzzle files in the directory that are new or modified since last seen,
+/// sorted by path
+fn changed_files(
+    dir: &Path,
+    seen: &HashMap<PathBuf, SystemTime>,
+) -> Result<Vec<(PathBuf, SystemTime)>, CliError> {
+    let mut changed = Vec::new();
+
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path
+            .extension()
+            .is_none_or(|ext| ext != "txt" && ext != "json")
+        {
+            continue;
+        }
+
+        let Ok(modified) = std::fs::metadata(&path).and_then(|m| m.modified()) else {
+            continue;
+        };
+
+        if seen.get(&path) != Some(&modified) {
+            changed.push((path, modified));
+        }
+    }
+
+    changed.sort();
+    Ok(changed)
+}
@@ -0,0 +1,116 @@
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
+
+use clap::Args;
+use serde::Serialize;
+
+use typeshift_solver::archive::{ArchivedPuzzle, PuzzleArchive};
+use typeshift_solver::date::Date;
+use typeshift_solver::generate::{self, PlannedPuzzle, PuzzleShape, Vocabulary};
+use typeshift_solver::report::OutputFormat;
+use typeshift_solver::{SolverConfig, Typeshift};
+
+use super::CliError;
+
+#[derive(Args)]
+pub struct WeekArgs {
+    /// The first day of the week
+    start: Date,
+
+    /// Pick from the puzzles in this directory instead of generating a pool
+    #[arg(long, conflicts_with_all = ["columns", "seed", "pool_size"])]
+    pool: Option<PathBuf>,
+
+    /// The number of columns in generated puzzles (4 to 7)
+    #[arg(long, default_value_t = 5)]
+    columns: usize,
+
+    /// The fewest letters in each column of generated puzzles
+    #[arg(long, default_value_t = 3)]
+    min_height: usize,
+
+    /// The most letters in each column of generated puzzles
+    #[arg(long, default_value_t = 5)]
+    max_height: usize,
+
+    /// The seed of the first generated puzzle; the rest count up from it
+    #[arg(long, default_value_t = 0)]
+    seed: u64,
+
+    /// How many puzzles to generate to pick the week from
+    #[arg(long, default_value_t = 50)]
+    pool_size: u64,
+
+    /// Write each day's puzzle to this directory as `<date>.txt`,
+    /// with its difficulty score in `<date>.meta`
+    #[arg(long)]
+    out: Option<PathBuf>,
+
+    /// Give up rating each puzzle after touching this many partial solutions
+    #[arg(long)]
+    max_steps: Option<usize>,
+}
+
+#[derive(Serialize)]
+#[serde(transparent)]
+struct Week(Vec<PlannedPuzzle>);
+
+impl fmt::Display for Week {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for puzzle in &self.0 {
+            let columns = puzzle.columns.join(" ");
+            writeln!(f, "{}: {:.1} {columns}", puzzle.date, puzzle.score)?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Plans a week of puzzles getting harder each day, from generated or archived puzzles
+pub fn run(args: WeekArgs, format: OutputFormat) -> Result<(), CliError> {
+    let config = SolverConfig {
+        max_steps: args.max_steps,
+        ..Default::default()
+    };
+
+    let pool: Vec<Vec<String>> = match &args.pool {
+        Some(dir) => (PuzzleArchive::load_dir(dir)?.iter())
+            .map(|puzzle| Typeshift::parse_columns(&puzzle.input))
+            .collect::<Result<_, _>>()?,
+        None => {
+            let shape = PuzzleShape::uniform(args.columns, args.min_height..=args.max_height);
+            shape.validate()?;
+            (args.seed..args.seed + args.pool_size)
+                .filter_map(|seed| {
+                    generate::generate_random_with(seed, &shape, &Vocabulary::all(), &config).ok()
+                })
+                .map(|generated| generated.columns)
+                .collect()
+        }
+    };
+
+    let week = generate::plan_week(&pool, args.start, &config)?;
+
+    if let Some(dir) = &args.out {
+        std::fs::create_dir_all(dir)?;
+        for planned in &week {
+            let mut input = planned.columns.join("\n");
+            input.push('\n');
+            let puzzle = ArchivedPuzzle {
+                date: planned.date,
+                input,
+                metadata: BTreeMap::from([(
+                    "difficulty".to_string(),
+                    format!("{:.1}", planned.score),
+                )]),
+            };
+            PuzzleArchive::save_to_dir(&puzzle, dir)?;
+        }
+        eprintln!("wrote {} puzzles to {}", week.len(), dir.display());
+    }
+
+    print!("{}", format.render(&Week(week)));
+
+    Ok(())
+}
@@ -0,0 +1,267 @@
+//! A long-running worker speaking line-delimited JSON-RPC 2.0 over stdin and stdout
+//!
+//! Methods:
+//! - `solve`, with `{"columns": [...], "all": bool, "max_steps": n, "timeout_ms": n}`,
+//!   returns the same schema as `solve --format json`
+//! - `hint`, with `{"columns": [...], "played": [...]}`, returns `{"hint": word or null}`
+//! - `cancel`, with `{"id": id}`, stops a running request, which then fails with
+//!   error code -32800; returns whether the request was still running
+//!
+//! Requests run concurrently, so responses may arrive out of order.
+
+use std::collections::HashMap;
+use std::io::{self, BufRead, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use clap::Args;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use typeshift_solver::error::SolveError;
+use typeshift_solver::report::SolutionReport;
+use typeshift_solver::{hint, SolverConfig, Typeshift};
+
+use super::CliError;
+
+#[derive(Args)]
+pub struct WorkerArgs {
+    /// Speak JSON-RPC over stdin and stdout, one message per line (the only transport)
+    #[arg(long, required = true)]
+    stdio: bool,
+}
+
+#[derive(Deserialize)]
+struct RpcRequest {
+    /// Absent for notifications, which get no response
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+#[derive(Serialize)]
+struct RpcResponse {
+    jsonrpc: &'static str,
+    id: Value,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    result: Option<Value>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<RpcError>,
+}
+
+#[derive(Serialize)]
+struct RpcError {
+    code: i32,
+    message: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    data: Option<CliError>,
+}
+
+impl RpcError {
+    const PARSE_ERROR: i32 = -32700;
+    const INVALID_REQUEST: i32 = -32600;
+    const METHOD_NOT_FOUND: i32 = -32601;
+    const INVALID_PARAMS: i32 = -32602;
+    /// An application error; `data` holds the same error as the CLI's structured output
+    const SOLVER_ERROR: i32 = -32000;
+    /// The code LSP uses for cancelled requests
+    const CANCELLED: i32 = -32800;
+
+    fn new(code: i32, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+            data: None,
+        }
+    }
+}
+
+impl From<CliError> for RpcError {
+    fn from(e: CliError) -> Self {
+        Self {
+            code: Self::SOLVER_ERROR,
+            message: e.message.clone(),
+            data: Some(e),
+        }
+    }
+}
+
+impl From<SolveError> for RpcError {
+    fn from(e: SolveError) -> Self {
+        match e {
+            SolveError::Cancelled { .. } => Self::new(Self::CANCELLED, e.to_string()),
+            _ => CliError::from(e).into(),
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct SolveParams {
+    columns: Vec<String>,
+    #[serde(default)]
+    all: bool,
+    max_steps: Option<usize>,
+    timeout_ms: Option<u64>,
+}
+
+#[derive(Deserialize)]
+struct HintParams {
+    columns: Vec<String>,
+    #[serde(default)]
+    played: Vec<String>,
+}
+
+#[derive(Deserialize)]
+struct CancelParams {
+    id: Value,
+}
+
+/// The state shared between the reader and the running requests
+#[derive(Default)]
+struct Worker {
+    /// Cancellation flags for running requests, by their ids' JSON text
+    running: Mutex<HashMap<String, Arc<AtomicBool>>>,
+}
+
+impl Worker {
+    fn respond(&self, id: Value, result: Result<Value, RpcError>) {
+        let (result, error) = match result {
+            Ok(result) => (Some(result), None),
+            Err(error) => (None, Some(error)),
+        };
+        let response = RpcResponse {
+            jsonrpc: "2.0",
+            id,
+            result,
+            error,
+        };
+
+        let mut stdout = io::stdout().lock();
+        let line = serde_json::to_string(&response).unwrap();
+        // a reader that went away will also close stdin, which ends the worker
+        let _ = writeln!(stdout, "{line}").and_then(|_| stdout.flush());
+    }
+
+    fn cancel(&self, params: Value) -> Result<Value, RpcError> {
+        let params: CancelParams = params_from(params)?;
+        let flag = self.running.lock().unwrap().remove(&params.id.to_string());
+
+        if let Some(flag) = &flag {
+            flag.store(true, Ordering::Relaxed);
+        }
+
+        Ok(Value::Bool(flag.is_some()))
+    }
+}
+
+/// Handles requests until stdin closes, then waits for running requests to finish
+pub fn run(_args: WorkerArgs) -> Result<(), CliError> {
+    let worker = Worker::default();
+
+    std::thread::scope(|scope| {
+        for line in io::stdin().lock().lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+
+            let request: RpcRequest = match serde_json::from_str::<Value>(&line) {
+                Err(e) => {
+                    let error = RpcError::new(RpcError::PARSE_ERROR, e.to_string());
+                    worker.respond(Value::Null, Err(error));
+                    continue;
+                }
+                Ok(value) => match serde_json::from_value(value) {
+                    Ok(request) => request,
+                    Err(e) => {
+                        let error = RpcError::new(RpcError::INVALID_REQUEST, e.to_string());
+                        worker.respond(Value::Null, Err(error));
+                        continue;
+                    }
+                },
+            };
+
+            if request.method == "cancel" {
+                let result = worker.cancel(request.params);
+                if let Some(id) = request.id {
+                    worker.respond(id, result);
+                }
+                continue;
+            }
+
+            let cancel = Arc::new(AtomicBool::new(false));
+            if let Some(id) = &request.id {
+                let mut running = worker.running.lock().unwrap();
+                running.insert(id.to_string(), cancel.clone());
+            }
+
+            let worker = &worker;
+            scope.spawn(move || {
+                let result = handle(&request.method, request.params, &cancel);
+
+                if let Some(id) = request.id {
+                    worker.running.lock().unwrap().remove(&id.to_string());
+                    worker.respond(id, result);
+                }
+            });
+        }
+
+        Ok(())
+    })
+}
+
+fn handle(method: &str, params: Value, cancel: &AtomicBool) -> Result<Value, RpcError> {
+    match method {
+        "solve" => {
+            let params: SolveParams = params_from(params)?;
+            let typeshift = parse(&params.columns)?;
+            let config = SolverConfig {
+                max_steps: params.max_steps,
+                timeout: params.timeout_ms.map(Duration::from_millis),
+                ..Default::default()
+            };
+
+            let (solution, steps) = typeshift.solve_cancellable(&config, cancel)?;
+            let all_solutions = if params.all {
+                Some(typeshift.solve_all_cancellable(&config, cancel)?.0)
+            } else {
+                None
+            };
+            let report = SolutionReport::from_solutions(
+                &typeshift,
+                &solution,
+                steps,
+                all_solutions.as_ref(),
+            );
+
+            Ok(serde_json::to_value(report).unwrap())
+        }
+
+        "hint" => {
+            let params: HintParams = params_from(params)?;
+            let typeshift = parse(&params.columns)?;
+
+            let (solution, _steps) = typeshift.solve_cancellable(&Default::default(), cancel)?;
+            let played: Vec<&str> = params.played.iter().map(String::as_str).collect();
+            let hint = hint::suggest(&solution, &played);
+
+            Ok(serde_json::json!({ "hint": hint }))
+        }
+
+        _ => Err(RpcError::new(
+            RpcError::METHOD_NOT_FOUND,
+            format!("unknown method '{method}'"),
+        )),
+    }
+}
+
+fn params_from<T: serde::de::DeserializeOwned>(params: Value) -> Result<T, RpcError> {
+    serde_json::from_value(params)
+        .map_err(|e| RpcError::new(RpcError::INVALID_PARAMS, e.to_string()))
+}
+
+fn parse(columns: &[String]) -> Result<Typeshift, RpcError> {
+    Typeshift::parse(&columns.join("\n")).map_err(|e| CliError::from(e).into())
+}
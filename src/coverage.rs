@@ -0,0 +1,103 @@
+//! The solver's view of a puzzle as a set cover problem: which words use which letters
+
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use crate::Typeshift;
+
+/// A boolean matrix of the puzzle's words by its letters,
+/// true where a word uses a letter; a solution is a set of rows covering every column
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct CoverageMatrix {
+    /// The puzzle's letters as (column, letter), leftmost column first,
+    /// and alphabetically within a column; columns count from zero
+    pub letters: Vec<(usize, char)>,
+
+    /// The words spellable from the puzzle, alphabetically
+    pub words: Vec<&'static str>,
+
+    /// A row for each word, with whether it uses each letter
+    pub rows: Vec<Vec<bool>>,
+}
+
+impl CoverageMatrix {
+    pub fn new(typeshift: &Typeshift) -> Self {
+        let letters: Vec<(usize, char)> = (typeshift.input_columns().iter().enumerate())
+            .flat_map(|(column, letters)| {
+                let letters: BTreeSet<char> = letters.chars().collect();
+                letters.into_iter().map(move |letter| (column, letter))
+            })
+            .collect();
+
+        let words = typeshift.words().to_vec();
+        let rows = words
+            .iter()
+            .map(|word| {
+                let word = word.as_bytes();
+                letters
+                    .iter()
+                    .map(|&(column, letter)| word[column] == letter as u8)
+                    .collect()
+            })
+            .collect();
+
+        Self {
+            letters,
+            words,
+            rows,
+        }
+    }
+
+    /// The number of words using each letter, in the order of `letters`
+    pub fn letter_counts(&self) -> Vec<usize> {
+        (0..self.letters.len())
+            .map(|letter| self.rows.iter().filter(|row| row[letter]).count())
+            .collect()
+    }
+
+    /// Writes the matrix as CSV: a header of `word` and each letter as its
+    /// one-based column and letter (like `1a`), then a row of 0s and 1s for each word
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("word");
+        for (column, letter) in &self.letters {
+            write!(csv, ",{}{letter}", column + 1).unwrap();
+        }
+        csv.push('\n');
+
+        for (word, row) in self.words.iter().zip(&self.rows) {
+            csv.push_str(word);
+            for &uses in row {
+                csv.push_str(if uses { ",1" } else { ",0" });
+            }
+            csv.push('\n');
+        }
+
+        csv
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn csv_matrix() {
+        let typeshift = Typeshift::new("b\nao\nr\nex");
+        let matrix = CoverageMatrix::new(&typeshift);
+
+        assert_eq!(matrix.words, vec!["bare", "bore"]);
+        assert_eq!(matrix.letter_counts(), vec![2, 1, 1, 2, 2, 0]);
+
+        let expected = "\
+word,1b,2a,2o,3r,4e,4x
+bare,1,1,0,1,1,0
+bore,1,0,1,1,1,0
+";
+        assert_eq!(matrix.to_csv(), expected);
+    }
+}
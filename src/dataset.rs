@@ -0,0 +1,229 @@
+//! Tidy datasets of solved puzzles, for difficulty modeling in other tools
+//!
+//! Each row describes one puzzle. Rows can be written as CSV, or with the `parquet`
+//! feature, as Parquet.
+
+use std::io::{self, Write};
+use std::time::Instant;
+
+use crate::date::Date;
+use crate::error::SolveError;
+use crate::{SolverConfig, Typeshift};
+
+/// The column names, in order
+pub const HEADER: [&str; 8] = [
+    "date",
+    "columns",
+    "possible_words",
+    "steps",
+    "optimal_words",
+    "solutions",
+    "solve_ms",
+    "solve_all_ms",
+];
+
+/// One solved puzzle
+#[derive(Debug, Clone, PartialEq)]
+pub struct DatasetRow {
+    pub date: Date,
+
+    /// The puzzle columns, leftmost first, separated by spaces
+    pub columns: String,
+
+    /// The number of words spellable from the puzzle
+    pub possible_words: usize,
+
+    /// The steps the search for the first solution took
+    pub steps: usize,
+
+    /// The number of words in a minimal solution
+    pub optimal_words: usize,
+
+    /// The number of minimal solutions
+    pub solutions: usize,
+
+    /// The time to find the first solution, in milliseconds
+    pub solve_ms: f64,
+
+    /// The time to count every minimal solution exactly, in milliseconds
+    pub solve_all_ms: f64,
+}
+
+impl DatasetRow {
+    /// Solves the puzzle for its first solution, and counts its minimal solutions,
+    /// timing each
+    pub fn new(
+        date: Date,
+        typeshift: &Typeshift,
+        config: &SolverConfig,
+    ) -> Result<Self, SolveError> {
+        let start = Instant::now();
+        let (_solution, steps) = typeshift.solve_with(config)?;
+        let solve_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+        let start = Instant::now();
+        let count = typeshift.count_solutions(config)?;
+        let solve_all_ms = start.elapsed().as_secs_f64() * 1000.0;
+
+        Ok(Self {
+            date,
+            columns: typeshift.input_columns().join(" "),
+            possible_words: typeshift.size(),
+            steps,
+            optimal_words: count.minimal_words,
+            solutions: count.solutions as usize,
+            solve_ms,
+            solve_all_ms,
+        })
+    }
+}
+
+/// Writes rows as CSV with a header line.
+/// No field can contain a comma or quote, so nothing is quoted.
+pub fn write_csv(rows: &[DatasetRow], mut w: impl Write) -> io::Result<()> {
+    writeln!(w, "{}", HEADER.join(","))?;
+
+    for row in rows {
+        writeln!(
+            w,
+            "{},{},{},{},{},{},{:.3},{:.3}",
+            row.date,
+            row.columns,
+            row.possible_words,
+            row.steps,
+            row.optimal_words,
+            row.solutions,
+            row.solve_ms,
+            row.solve_all_ms
+        )?;
+    }
+
+    Ok(())
+}
+
+/// Writes rows as an uncompressed Parquet file, in one row group
+#[cfg(feature = "parquet")]
+pub fn write_parquet(rows: &[DatasetRow], w: impl Write + Send) -> io::Result<()> {
+    parquet_rows(rows, w).map_err(io::Error::other)
+}
+
+#[cfg(feature = "parquet")]
+fn parquet_rows(rows: &[DatasetRow], w: impl Write + Send) -> parquet::errors::Result<()> {
+    use std::sync::Arc;
+
+    use parquet::data_type::{ByteArray, ByteArrayType, DoubleType, Int64Type};
+    use parquet::file::properties::WriterProperties;
+    use parquet::file::writer::SerializedFileWriter;
+    use parquet::schema::parser::parse_message_type;
+
+    const SCHEMA: &str = "
+        message typeshift_puzzle {
+            required binary date (UTF8);
+            required binary columns (UTF8);
+            required int64 possible_words;
+            required int64 steps;
+            required int64 optimal_words;
+            required int64 solutions;
+            required double solve_ms;
+            required double solve_all_ms;
+        }
+    ";
+
+    let strings = |field: fn(&DatasetRow) -> String| -> Vec<ByteArray> {
+        rows.iter().map(|row| field(row).as_str().into()).collect()
+    };
+    let ints = |field: fn(&DatasetRow) -> usize| -> Vec<i64> {
+        rows.iter().map(|row| field(row) as i64).collect()
+    };
+    let doubles = |field: fn(&DatasetRow) -> f64| -> Vec<f64> { rows.iter().map(field).collect() };
+
+    let schema = Arc::new(parse_message_type(SCHEMA)?);
+    let properties = Arc::new(WriterProperties::builder().build());
+    let mut writer = SerializedFileWriter::new(w, schema, properties)?;
+    let mut row_group = writer.next_row_group()?;
+
+    // the columns must be written in schema order
+    for values in [
+        strings(|row| row.date.to_string()),
+        strings(|row| row.columns.clone()),
+    ] {
+        let mut column = row_group.next_column()?.unwrap();
+        column
+            .typed::<ByteArrayType>()
+            .write_batch(&values, None, None)?;
+        column.close()?;
+    }
+    for values in [
+        ints(|row| row.possible_words),
+        ints(|row| row.steps),
+        ints(|row| row.optimal_words),
+        ints(|row| row.solutions),
+    ] {
+        let mut column = row_group.next_column()?.unwrap();
+        column
+            .typed::<Int64Type>()
+            .write_batch(&values, None, None)?;
+        column.close()?;
+    }
+    for values in [doubles(|row| row.solve_ms), doubles(|row| row.solve_all_ms)] {
+        let mut column = row_group.next_column()?.unwrap();
+        column
+            .typed::<DoubleType>()
+            .write_batch(&values, None, None)?;
+        column.close()?;
+    }
+
+    row_group.close()?;
+    writer.close()?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn csv_rows() {
+        let input = include_str!("../files/puzzles/2023-11-16.txt");
+        let typeshift = Typeshift::new(input);
+        let date = "2023-11-16".parse().unwrap();
+        let mut row = DatasetRow::new(date, &typeshift, &SolverConfig::default()).unwrap();
+        row.solve_ms = 1.5;
+        row.solve_all_ms = 2.0;
+
+        let mut csv = Vec::new();
+        write_csv(&[row], &mut csv).unwrap();
+
+        let expected = "\
+date,columns,possible_words,steps,optimal_words,solutions,solve_ms,solve_all_ms
+2023-11-16,wsab hbta oesu dpive lceys,31,6,5,16,1.500,2.000
+";
+        assert_eq!(String::from_utf8(csv).unwrap(), expected);
+    }
+
+    #[cfg(feature = "parquet")]
+    #[test]
+    fn parquet_rows() {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+
+        let input = include_str!("../files/puzzles/2023-11-16.txt");
+        let typeshift = Typeshift::new(input);
+        let date = "2023-11-16".parse().unwrap();
+        let row = DatasetRow::new(date, &typeshift, &SolverConfig::default()).unwrap();
+
+        let path = std::env::temp_dir().join(format!("typeshift-{}.parquet", std::process::id()));
+        write_parquet(&[row.clone(), row], std::fs::File::create(&path).unwrap()).unwrap();
+
+        let reader = SerializedFileReader::new(std::fs::File::open(&path).unwrap()).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        let metadata = reader.metadata();
+        assert_eq!(metadata.file_metadata().num_rows(), 2);
+        assert_eq!(
+            metadata.file_metadata().schema_descr().num_columns(),
+            HEADER.len()
+        );
+    }
+}
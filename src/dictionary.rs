@@ -0,0 +1,162 @@
+//! Lookups in the embedded dictionary
+//!
+//! By default the dictionary is compiled in as a static array of strings.
+//! The `compact-dict` feature instead embeds `dict.bin`, a front-coded encoding
+//! several times smaller, and decodes it on first use; this is meant for size-sensitive
+//! targets like wasm. `create_dict compact` writes `dict.bin`, optionally reduced
+//! to the words in a list of common words.
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+#[cfg(not(feature = "compact-dict"))]
+use crate::dict::DICT;
+
+/// Every word in the embedded dictionary, sorted
+#[cfg(not(feature = "compact-dict"))]
+pub fn words() -> &'static [&'static str] {
+    &DICT
+}
+
+/// Every word in the embedded dictionary, sorted
+#[cfg(feature = "compact-dict")]
+pub fn words() -> &'static [&'static str] {
+    static WORDS: std::sync::OnceLock<Vec<&'static str>> = std::sync::OnceLock::new();
+
+    WORDS.get_or_init(|| {
+        let words = decode_compact(include_bytes!("dict.bin"));
+
+        // one allocation for every word's letters, kept for the life of the program
+        let letters: &'static str = words.concat().leak();
+        let mut start = 0;
+        words
+            .iter()
+            .map(|word| {
+                let end = start + word.len();
+                let word = &letters[start..end];
+                start = end;
+                word
+            })
+            .collect()
+    })
+}
+
+/// Returns true if the word is in the embedded dictionary
+pub fn is_word(word: &str) -> bool {
+    // create_dict writes the dictionary sorted and deduplicated
+    words().binary_search(&word).is_ok()
+}
+
+/// A hash of the whole embedded dictionary, for recognizing data that refers to its words
+/// by index, like serialized puzzles
+#[cfg(feature = "std")]
+pub fn fingerprint() -> u64 {
+    static FINGERPRINT: std::sync::OnceLock<u64> = std::sync::OnceLock::new();
+
+    *FINGERPRINT.get_or_init(|| fingerprint_of(words()))
+}
+
+/// A hash of the whole embedded dictionary, for recognizing data that refers to its words
+/// by index, like serialized puzzles.
+/// Without `std` there's nowhere to keep it, so it's recomputed on every call.
+#[cfg(not(feature = "std"))]
+pub fn fingerprint() -> u64 {
+    fingerprint_of(words())
+}
+
+/// A hash of a whole word list, the same as `fingerprint` for the embedded dictionary
+pub fn fingerprint_of(words: &[&str]) -> u64 {
+    // FNV-1a, as in puzzle::canonical_hash
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in words.iter().flat_map(|word| word.bytes().chain([b'\n'])) {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+
+    hash
+}
+
+/// Front-codes a sorted word list: each word is one byte holding the length of the
+/// prefix it shares with the previous word (high four bits) and the length of the rest
+/// (low four bits), followed by the rest.
+///
+/// Panics on words longer than 15 bytes.
+pub fn encode_compact<S: AsRef<str>>(words: &[S]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    let mut previous = "";
+
+    for word in words {
+        let word = word.as_ref();
+        assert!(word.len() <= 15, "'{word}' is too long to encode");
+
+        let prefix = word
+            .bytes()
+            .zip(previous.bytes())
+            .take_while(|(a, b)| a == b)
+            .count();
+        let suffix = &word[prefix..];
+
+        bytes.push(((prefix as u8) << 4) | suffix.len() as u8);
+        bytes.extend_from_slice(suffix.as_bytes());
+        previous = word;
+    }
+
+    bytes
+}
+
+/// Decodes a word list written by `encode_compact`.
+///
+/// Panics on malformed input.
+pub fn decode_compact(bytes: &[u8]) -> Vec<String> {
+    let mut words: Vec<String> = Vec::new();
+    let mut rest = bytes;
+
+    while let Some((&lengths, tail)) = rest.split_first() {
+        let prefix = usize::from(lengths >> 4);
+        let (suffix, tail) = tail.split_at(usize::from(lengths & 0x0f));
+
+        let mut word = match words.last() {
+            Some(previous) => previous[..prefix].to_string(),
+            None => String::new(),
+        };
+        word.push_str(core::str::from_utf8(suffix).expect("malformed compact dictionary"));
+
+        words.push(word);
+        rest = tail;
+    }
+
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn dict_is_sorted_and_deduplicated() {
+        assert!(words().windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn is_word_smoke() {
+        assert!(is_word("chumps"));
+        assert!(is_word("aahed"));
+        assert!(!is_word("chumpz"));
+        // shorter than the generator's length bounds
+        assert!(!is_word("cat"));
+    }
+
+    #[test]
+    fn compact_encoding() {
+        let embedded = decode_compact(include_bytes!("dict.bin"));
+        assert!(embedded.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let some_words = ["aahed", "aahing", "abacus", "abandon", "zebra"];
+        assert_eq!(
+            decode_compact(&encode_compact(&some_words)),
+            some_words.to_vec()
+        );
+    }
+}
@@ -0,0 +1,288 @@
+//! Errors from reading and solving puzzles
+
+use alloc::string::String;
+use core::fmt;
+
+/// An invalid puzzle input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ParseError {
+    /// The input has no columns
+    Empty,
+    /// A line of the input has no letters; columns are 1-based
+    EmptyColumn { column: usize },
+    /// A column contains something other than a lowercase ascii letter; columns are 1-based
+    InvalidLetter { column: usize, letter: char },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "the puzzle has no columns"),
+            ParseError::EmptyColumn { column } => write!(f, "column {column} is empty"),
+            ParseError::InvalidLetter { column, letter } => {
+                write!(
+                    f,
+                    "column {column} contains {letter:?}; expected lowercase letters"
+                )
+            }
+        }
+    }
+}
+
+impl core::error::Error for ParseError {}
+
+/// A search that ended without a solution
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SolveError {
+    /// Some letter can't be covered by any word in the dictionary
+    Unsolvable,
+    /// The search hit its step or time limit
+    Timeout { steps: usize },
+    /// The search was cancelled by its caller
+    Cancelled { steps: usize },
+}
+
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SolveError::Unsolvable => write!(f, "the puzzle has no solution"),
+            SolveError::Timeout { steps } => {
+                write!(f, "the search hit its limit after {steps} steps")
+            }
+            SolveError::Cancelled { steps } => {
+                write!(f, "the search was cancelled after {steps} steps")
+            }
+        }
+    }
+}
+
+impl core::error::Error for SolveError {}
+
+/// A serialized puzzle that can't be restored
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RestoreError {
+    /// The data doesn't decode
+    Decode(String),
+    /// The puzzle was prepared with a different dictionary
+    DictionaryMismatch,
+    /// The puzzle's columns are invalid
+    Parse(ParseError),
+    /// A word id is out of range, out of order, or not spellable from the columns
+    InvalidWord { id: u32 },
+    /// The letter frequencies don't match the words
+    InvalidFrequencies,
+}
+
+impl fmt::Display for RestoreError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RestoreError::Decode(message) => write!(f, "can't decode the puzzle: {message}"),
+            RestoreError::DictionaryMismatch => {
+                write!(f, "the puzzle was prepared with a different dictionary")
+            }
+            RestoreError::Parse(e) => write!(f, "{e}"),
+            RestoreError::InvalidWord { id } => write!(f, "invalid word id {id}"),
+            RestoreError::InvalidFrequencies => {
+                write!(f, "the letter frequencies don't match the words")
+            }
+        }
+    }
+}
+
+impl core::error::Error for RestoreError {}
+
+/// Words that can't be made into a puzzle, or a puzzle that couldn't be checked
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GenerateError {
+    /// No words were given
+    NoWords,
+    /// A word's length differs from the first word's
+    MixedLengths { expected: usize, word: String },
+    /// A word isn't in the dictionary
+    NotAWord(String),
+    /// A word isn't in the vocabulary the generator was limited to
+    OutsideVocabulary(String),
+    /// Searching the generated puzzle failed
+    Solve(SolveError),
+    /// No puzzle with a unique minimal solution was found in the attempts allowed
+    NotUnique { attempts: usize },
+    /// No puzzle in the difficulty band was found in the attempts allowed
+    OutsideBand { attempts: usize },
+    /// No puzzle with the required word in every minimal solution was found
+    /// in the attempts allowed
+    NotRequired { word: String, attempts: usize },
+    /// A puzzle shape has a number of columns outside `PuzzleShape::COLUMNS`
+    ColumnCount(usize),
+    /// A puzzle shape's column allows no letters; columns are 1-based
+    EmptyColumn { column: usize },
+    /// The dictionary's words have fewer different letters in a column's position
+    /// than the column needs; columns are 1-based
+    UnsatisfiableColumn { column: usize, letters: usize },
+    /// No words were found to fill a puzzle shape
+    ShapeUnfilled,
+    /// Too few puzzles in a pool have different difficulty scores to plan a week
+    PoolTooSmall { rated: usize },
+    /// The embedded dictionary isn't the one daily puzzles are generated with,
+    /// so they'd differ from everyone else's
+    DictionaryMismatch { expected: u64, found: u64 },
+}
+
+impl fmt::Display for GenerateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            GenerateError::NoWords => write!(f, "no words to build a puzzle from"),
+            GenerateError::MixedLengths { expected, word } => {
+                write!(
+                    f,
+                    "'{word}' isn't {expected} letters long like the first word"
+                )
+            }
+            GenerateError::NotAWord(word) => write!(f, "'{word}' isn't in the dictionary"),
+            GenerateError::OutsideVocabulary(word) => {
+                write!(f, "'{word}' isn't in the allowed vocabulary")
+            }
+            GenerateError::Solve(e) => write!(f, "{e}"),
+            GenerateError::NotUnique { attempts } => {
+                write!(f, "no unique puzzle found in {attempts} attempts")
+            }
+            GenerateError::OutsideBand { attempts } => {
+                write!(
+                    f,
+                    "no puzzle in the difficulty band found in {attempts} attempts"
+                )
+            }
+            GenerateError::NotRequired { word, attempts } => write!(
+                f,
+                "no puzzle needing '{word}' in every minimal solution found in {attempts} attempts"
+            ),
+            GenerateError::ColumnCount(columns) => {
+                write!(f, "puzzles have 4 to 7 columns, not {columns}")
+            }
+            GenerateError::EmptyColumn { column } => write!(f, "column {column} allows no letters"),
+            GenerateError::UnsatisfiableColumn { column, letters } => write!(
+                f,
+                "column {column} needs more letters than the {letters} in that position of any word"
+            ),
+            GenerateError::ShapeUnfilled => write!(f, "no words fill the puzzle shape"),
+            GenerateError::PoolTooSmall { rated } => write!(
+                f,
+                "a week needs 7 puzzles of different difficulties, but the pool has {rated}"
+            ),
+            GenerateError::DictionaryMismatch { expected, found } => write!(
+                f,
+                "daily puzzles need the dictionary with fingerprint {expected:016x}, not {found:016x}"
+            ),
+        }
+    }
+}
+
+impl core::error::Error for GenerateError {}
+
+impl From<SolveError> for GenerateError {
+    fn from(e: SolveError) -> Self {
+        GenerateError::Solve(e)
+    }
+}
+
+/// A word that can't be played, or a failed search for the words that finish a game
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlayError {
+    /// The word isn't one of the puzzle's words
+    NotInPuzzle(String),
+    /// The word has been played already
+    AlreadyPlayed(String),
+    /// Searching for the rest of the words failed
+    Solve(SolveError),
+    /// Games being merged are of different puzzles
+    DifferentPuzzle,
+    /// The words leave some letters uncovered
+    Unfinished { uncovered: usize },
+}
+
+impl fmt::Display for PlayError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlayError::NotInPuzzle(word) => write!(f, "'{word}' is not a word in this puzzle"),
+            PlayError::AlreadyPlayed(word) => write!(f, "'{word}' was already played"),
+            PlayError::Solve(e) => write!(f, "{e}"),
+            PlayError::DifferentPuzzle => write!(f, "the games are of different puzzles"),
+            PlayError::Unfinished { uncovered } => {
+                write!(f, "the words leave {uncovered} letters uncovered")
+            }
+        }
+    }
+}
+
+impl core::error::Error for PlayError {}
+
+impl From<SolveError> for PlayError {
+    fn from(e: SolveError) -> Self {
+        PlayError::Solve(e)
+    }
+}
+
+/// Why a word isn't one of a puzzle's words
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum InvalidWord {
+    /// The word has a different number of letters than the puzzle has columns
+    WrongLength { expected: usize, found: usize },
+    /// A letter of the word isn't in its column; columns are 1-based
+    NotInColumn { column: usize, letter: char },
+    /// Every letter is in the puzzle, but the word isn't in the dictionary
+    NotInDictionary,
+}
+
+impl fmt::Display for InvalidWord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InvalidWord::WrongLength { expected, found } => {
+                write!(f, "expected {expected} letters, found {found}")
+            }
+            InvalidWord::NotInColumn { column, letter } => {
+                write!(f, "column {column} has no '{letter}'")
+            }
+            InvalidWord::NotInDictionary => write!(f, "not in the dictionary"),
+        }
+    }
+}
+
+impl core::error::Error for InvalidWord {}
+
+/// A failed fit of difficulty weights to human results
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CalibrateError {
+    /// Rating a puzzle failed
+    Solve(SolveError),
+    /// Fewer results matched a puzzle than there are weights to fit
+    TooFewResults { matched: usize },
+    /// The matched puzzles don't vary enough in their measures to fit every weight
+    Underdetermined,
+}
+
+impl fmt::Display for CalibrateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CalibrateError::Solve(e) => write!(f, "{e}"),
+            CalibrateError::TooFewResults { matched } => {
+                write!(
+                    f,
+                    "only {matched} results matched a puzzle; at least 5 are needed"
+                )
+            }
+            CalibrateError::Underdetermined => {
+                write!(
+                    f,
+                    "the puzzles with results don't vary enough to fit every weight"
+                )
+            }
+        }
+    }
+}
+
+impl core::error::Error for CalibrateError {}
+
+impl From<SolveError> for CalibrateError {
+    fn from(e: SolveError) -> Self {
+        CalibrateError::Solve(e)
+    }
+}
@@ -0,0 +1,31 @@
+//! Rich display in evcxr, the Rust Jupyter kernel
+//!
+//! evcxr calls an `evcxr_display` method on values shown in a notebook cell,
+//! which prints its content between markers with a MIME type; no dependency is needed.
+
+use crate::grid::render_html;
+use crate::report::SolutionReport;
+use crate::Typeshift;
+
+fn display_html(html: &str) {
+    println!("EVCXR_BEGIN_CONTENT text/html\n{html}EVCXR_END_CONTENT");
+}
+
+impl Typeshift {
+    /// Shows the puzzle grid as an HTML table
+    pub fn evcxr_display(&self) {
+        let html = render_html(self.input_columns(), &[]);
+        display_html(&format!("{html}<p>{} possible words</p>\n", self.size()));
+    }
+}
+
+impl SolutionReport {
+    /// Shows the puzzle grid as an HTML table, colored by the solution words
+    pub fn evcxr_display(&self) {
+        let html = render_html(&self.columns, &self.solution);
+        display_html(&format!(
+            "{html}<p>{} possible words, {} steps</p>\n",
+            self.possible_words, self.steps
+        ));
+    }
+}
@@ -0,0 +1,96 @@
+//! Flashcards of the uncommon words in puzzle solutions, for building vocabulary
+//!
+//! Cards are written as tab-separated lines that Anki imports directly: the word,
+//! the puzzle it was first seen in, and a link to its dictionary entry.
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::date::Date;
+
+/// Where a card's definition link points; the word goes on the end
+pub const DEFINITION_URL: &str = "https://en.wiktionary.org/wiki/";
+
+/// One card: an uncommon word, and the earliest puzzle whose solution used it
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Flashcard {
+    pub word: &'static str,
+    pub puzzle: Date,
+    pub definition_url: String,
+}
+
+/// Makes a card for each solution word that isn't in the first `max_rank` words of the
+/// frequency list (most common first), alphabetically; a word in several puzzles' solutions
+/// gets one card, naming the earliest
+pub fn obscure_words<'a>(
+    solutions: impl IntoIterator<Item = (Date, &'a BTreeSet<&'static str>)>,
+    frequency_list: &[&str],
+    max_rank: usize,
+) -> Vec<Flashcard> {
+    let common = BTreeSet::from_iter(frequency_list.iter().copied().take(max_rank));
+
+    let mut first_seen: BTreeMap<&'static str, Date> = BTreeMap::new();
+    for (date, solution) in solutions {
+        for &word in solution.iter().filter(|word| !common.contains(**word)) {
+            let seen = first_seen.entry(word).or_insert(date);
+            *seen = (*seen).min(date);
+        }
+    }
+
+    (first_seen.into_iter())
+        .map(|(word, puzzle)| Flashcard {
+            word,
+            puzzle,
+            definition_url: format!("{DEFINITION_URL}{word}"),
+        })
+        .collect()
+}
+
+/// Renders cards as TSV for Anki, with header lines telling it the separator and that
+/// the fields are plain text
+pub fn render_tsv(cards: &[Flashcard]) -> String {
+    let mut out = String::from("#separator:tab\n#html:false\n");
+    for card in cards {
+        out.push_str(&format!(
+            "{}\t{}\t{}\n",
+            card.word, card.puzzle, card.definition_url
+        ));
+    }
+
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn flashcards() {
+        let first = BTreeSet::from(["above", "basic", "study", "wheel", "whups"]);
+        let second = BTreeSet::from(["stoic", "whups"]);
+        let frequency_list = ["the", "above", "study", "basic", "wheel", "stoic"];
+
+        let cards = obscure_words(
+            [
+                ("2023-11-17".parse().unwrap(), &second),
+                ("2023-11-16".parse().unwrap(), &first),
+            ],
+            &frequency_list,
+            4,
+        );
+
+        let expected = "\
+#separator:tab
+#html:false
+stoic\t2023-11-17\thttps://en.wiktionary.org/wiki/stoic
+wheel\t2023-11-16\thttps://en.wiktionary.org/wiki/wheel
+whups\t2023-11-16\thttps://en.wiktionary.org/wiki/whups
+";
+        assert_eq!(render_tsv(&cards), expected);
+    }
+}
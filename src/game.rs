@@ -0,0 +1,333 @@
+//! The state of a game in progress, for interactive frontends
+
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::error::PlayError;
+use crate::Typeshift;
+
+/// A puzzle being played: the words played so far, and how many of them use each letter.
+///
+/// With the `serde` feature it serializes as the puzzle's columns, the played and undone
+/// words, and the hints used, and deserializes by replaying them on the puzzle as the
+/// embedded dictionary builds it, so a frontend can save a game and resume it later.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameState {
+    /// The puzzle columns, leftmost first
+    columns: Vec<String>,
+
+    /// The words spellable from the puzzle, alphabetically
+    words: Vec<&'static str>,
+
+    /// The accepted words, in the order they were played
+    played: Vec<&'static str>,
+
+    /// Undone words, most recently undone last; cleared by playing a new word
+    undone: Vec<&'static str>,
+
+    /// For each column, the number of played words using each of its letters
+    usage: Vec<BTreeMap<char, usize>>,
+
+    /// The number of hints taken
+    hints: usize,
+}
+
+impl GameState {
+    pub fn new(typeshift: &Typeshift) -> Self {
+        let columns = typeshift.input_columns().to_vec();
+        let usage = (columns.iter())
+            .map(|letters| letters.chars().map(|letter| (letter, 0)).collect())
+            .collect();
+
+        Self {
+            columns,
+            words: typeshift.words().to_vec(),
+            played: Vec::new(),
+            undone: Vec::new(),
+            usage,
+            hints: 0,
+        }
+    }
+
+    /// Plays a word, returning the number of letters it covers that no played word did
+    pub fn play(&mut self, word: &str) -> Result<usize, PlayError> {
+        let Ok(index) = self.words.binary_search(&word) else {
+            return Err(PlayError::NotInPuzzle(word.to_string()));
+        };
+        let word = self.words[index];
+        if self.played.contains(&word) {
+            return Err(PlayError::AlreadyPlayed(word.to_string()));
+        }
+
+        self.undone.clear();
+        Ok(self.push(word))
+    }
+
+    /// Takes back the last played word, returning it
+    pub fn undo(&mut self) -> Option<&'static str> {
+        let word = self.played.pop()?;
+        for (usage, letter) in self.usage.iter_mut().zip(word.chars()) {
+            *usage.get_mut(&letter).unwrap() -= 1;
+        }
+        self.undone.push(word);
+
+        Some(word)
+    }
+
+    /// Plays the last undone word again, returning it
+    pub fn redo(&mut self) -> Option<&'static str> {
+        let word = self.undone.pop()?;
+        self.push(word);
+
+        Some(word)
+    }
+
+    /// Plays every word another game of the same puzzle played that this one hasn't,
+    /// in its order, for players sharing progress; returns the number of letters
+    /// that newly covers. Clears the redo stack, like `play`.
+    pub fn merge(&mut self, other: &GameState) -> Result<usize, PlayError> {
+        if self.columns != other.columns {
+            return Err(PlayError::DifferentPuzzle);
+        }
+
+        self.undone.clear();
+        let mut newly_covered = 0;
+        for &word in &other.played {
+            if !self.played.contains(&word) {
+                newly_covered += self.push(word);
+            }
+        }
+
+        Ok(newly_covered)
+    }
+
+    /// Records a played word, returning the number of letters it newly covers
+    fn push(&mut self, word: &'static str) -> usize {
+        let mut newly_covered = 0;
+        for (usage, letter) in self.usage.iter_mut().zip(word.chars()) {
+            let count = usage.get_mut(&letter).unwrap();
+            newly_covered += usize::from(*count == 0);
+            *count += 1;
+        }
+        self.played.push(word);
+
+        newly_covered
+    }
+
+    /// Counts a hint the player took, for frontends that show or score hint usage
+    pub fn record_hint(&mut self) {
+        self.hints += 1;
+    }
+
+    /// The number of hints taken; undoing words doesn't give them back
+    pub fn hints_used(&self) -> usize {
+        self.hints
+    }
+
+    /// The puzzle columns, leftmost first
+    pub fn columns(&self) -> &[String] {
+        &self.columns
+    }
+
+    /// The accepted words, in the order they were played
+    pub fn played(&self) -> &[&'static str] {
+        &self.played
+    }
+
+    /// The number of played words using a letter in a column, counting from zero
+    pub fn usage(&self, column: usize, letter: char) -> usize {
+        (self.usage.get(column))
+            .and_then(|usage| usage.get(&letter))
+            .copied()
+            .unwrap_or(0)
+    }
+
+    pub fn is_covered(&self, column: usize, letter: char) -> bool {
+        self.usage(column, letter) > 0
+    }
+
+    /// The letters no played word uses, as (column, letter), leftmost column first
+    /// and alphabetically within a column
+    pub fn uncovered(&self) -> Vec<(usize, char)> {
+        (self.usage.iter().enumerate())
+            .flat_map(|(column, usage)| {
+                (usage.iter())
+                    .filter(|(_, &count)| count == 0)
+                    .map(move |(&letter, _)| (column, letter))
+            })
+            .collect()
+    }
+
+    /// Returns true once the played words use every letter
+    pub fn is_solved(&self) -> bool {
+        self.usage
+            .iter()
+            .flat_map(BTreeMap::values)
+            .all(|&count| count > 0)
+    }
+}
+
+/// What a `GameState` saves; the coverage follows from the played words
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize, serde::Deserialize)]
+struct SavedGame {
+    columns: Vec<String>,
+    played: Vec<String>,
+    undone: Vec<String>,
+    hints: usize,
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for GameState {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let words = |words: &[&str]| words.iter().map(|word| word.to_string()).collect();
+        let saved = SavedGame {
+            columns: self.columns.clone(),
+            played: words(&self.played),
+            undone: words(&self.undone),
+            hints: self.hints,
+        };
+
+        saved.serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for GameState {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use serde::de::Error;
+
+        let saved = SavedGame::deserialize(deserializer)?;
+        let typeshift = Typeshift::parse(&saved.columns.join("\n")).map_err(D::Error::custom)?;
+
+        let mut game = GameState::new(&typeshift);
+        for word in &saved.played {
+            game.play(word).map_err(D::Error::custom)?;
+        }
+        for word in &saved.undone {
+            let Ok(index) = game.words.binary_search(&word.as_str()) else {
+                return Err(D::Error::custom(PlayError::NotInPuzzle(word.clone())));
+            };
+            game.undone.push(game.words[index]);
+        }
+        game.hints = saved.hints;
+
+        Ok(game)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::collections::BTreeSet;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn playing_words() {
+        let typeshift = Typeshift::new(include_str!("../files/puzzles/2023-11-16.txt"));
+        let mut game = GameState::new(&typeshift);
+
+        assert_eq!(game.uncovered().len(), 22);
+        assert_eq!(game.play("above"), Ok(5));
+        assert_eq!(game.play("basic"), Ok(5));
+        assert_eq!(game.play("stoic"), Ok(2));
+        assert_eq!(game.usage(0, 'a'), 1);
+        assert_eq!(game.usage(2, 'o'), 2);
+        assert!(game.is_covered(4, 'c') && !game.is_covered(4, 'y'));
+
+        assert_eq!(
+            game.play("above"),
+            Err(PlayError::AlreadyPlayed("above".to_string()))
+        );
+        assert_eq!(
+            game.play("xyzzy"),
+            Err(PlayError::NotInPuzzle("xyzzy".to_string()))
+        );
+        assert_eq!(game.played(), ["above", "basic", "stoic"]);
+
+        for word in ["study", "wheel", "whups"] {
+            game.play(word).unwrap();
+        }
+        assert!(game.is_solved());
+        assert_eq!(game.uncovered(), []);
+    }
+
+    #[test]
+    fn undo_and_redo() {
+        let typeshift = Typeshift::new(include_str!("../files/puzzles/2023-11-16.txt"));
+        let mut game = GameState::new(&typeshift);
+        assert_eq!(game.undo(), None);
+
+        game.play("above").unwrap();
+        game.play("stoic").unwrap();
+        let before = game.clone();
+        game.play("basic").unwrap();
+
+        assert_eq!(game.undo(), Some("basic"));
+        assert_eq!(game.played(), before.played());
+        assert_eq!(game.usage(2, 'o'), 2);
+        assert_eq!(game.usage(0, 'b'), 0);
+        assert_eq!(game.uncovered(), before.uncovered());
+
+        assert_eq!(game.undo(), Some("stoic"));
+        assert_eq!(game.usage(2, 'o'), 1);
+        assert_eq!(game.redo(), Some("stoic"));
+        assert_eq!(game.redo(), Some("basic"));
+        assert_eq!(game.redo(), None);
+        assert_eq!(game.played(), ["above", "stoic", "basic"]);
+
+        game.undo();
+        game.play("study").unwrap();
+        assert_eq!(game.redo(), None);
+        assert_eq!(game.usage(0, 'b'), 0);
+    }
+    #[test]
+    fn merging_games() {
+        let typeshift = Typeshift::new(include_str!("../files/puzzles/2023-11-16.txt"));
+        let mut mine = GameState::new(&typeshift);
+        mine.play("above").unwrap();
+        mine.play("stoic").unwrap();
+        let mut theirs = GameState::new(&typeshift);
+        theirs.play("wheel").unwrap();
+        theirs.play("above").unwrap();
+
+        assert_eq!(mine.merge(&theirs), Ok(5));
+        assert_eq!(mine.played(), ["above", "stoic", "wheel"]);
+        assert_eq!(mine.usage(0, 'a'), 1);
+        assert_eq!(
+            typeshift.complete(mine.played()),
+            Ok(BTreeSet::from(["bauds", "waspy"]))
+        );
+
+        let other_puzzle = GameState::new(&Typeshift::new("ab\ncd"));
+        assert_eq!(mine.merge(&other_puzzle), Err(PlayError::DifferentPuzzle));
+    }
+
+    #[cfg(feature = "cli")]
+    #[test]
+    fn saving_and_restoring() {
+        let typeshift = Typeshift::new(include_str!("../files/puzzles/2023-11-16.txt"));
+        let mut game = GameState::new(&typeshift);
+        game.play("above").unwrap();
+        game.play("stoic").unwrap();
+        game.play("basic").unwrap();
+        game.undo();
+        game.record_hint();
+
+        let saved = serde_json::to_string(&game).unwrap();
+        assert_eq!(
+            saved,
+            r#"{"columns":["wsab","hbta","oesu","dpive","lceys"],"played":["above","stoic"],"undone":["basic"],"hints":1}"#
+        );
+        let mut restored: GameState = serde_json::from_str(&saved).unwrap();
+        assert_eq!(restored, game);
+        assert_eq!(restored.redo(), Some("basic"));
+
+        let cheating = saved.replace("stoic", "xyzzy");
+        let error = serde_json::from_str::<GameState>(&cheating).unwrap_err();
+        assert_eq!(error.to_string(), "'xyzzy' is not a word in this puzzle");
+    }
+}
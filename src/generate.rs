@@ -0,0 +1,1118 @@
+//! Building puzzles from the words meant to solve them
+
+use alloc::collections::BTreeSet;
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use core::iter::zip;
+use core::ops::RangeInclusive;
+
+use crate::date::Date;
+use crate::dictionary;
+use crate::error::{GenerateError, SolveError};
+use crate::puzzle::PuzzleDefinition;
+use crate::random::SplitMix64;
+use crate::typeshift::SearchEstimate;
+use crate::{SolverConfig, Typeshift};
+
+/// A puzzle built from a set of intended words, with how well they solve it
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct GeneratedPuzzle {
+    /// The puzzle columns, leftmost first, each in alphabetical order
+    pub columns: Vec<String>,
+
+    /// The words the puzzle was built from
+    pub intended: BTreeSet<&'static str>,
+
+    /// True if no solution uses fewer words than the intended ones
+    pub minimal: bool,
+
+    /// The minimal solutions other than the intended words, alphabetically;
+    /// all of them use fewer words if the intended words aren't minimal
+    pub alternatives: Vec<BTreeSet<&'static str>>,
+
+    /// The steps the best-first search for a first solution took
+    pub steps: usize,
+
+    /// A quick estimate of how hard the puzzle is to search
+    pub estimate: SearchEstimate,
+}
+
+impl GeneratedPuzzle {
+    pub fn typeshift(&self) -> Typeshift {
+        Typeshift::from_columns(self.columns.clone())
+    }
+
+    /// Returns true if the intended words are the only minimal solution
+    pub fn is_unique(&self) -> bool {
+        self.minimal && self.alternatives.is_empty()
+    }
+
+    /// The puzzle in the JSON puzzle format that `fetch` and `serve` take,
+    /// for sharing with other tools; the intended words are left out
+    pub fn definition(&self, date: Option<Date>) -> PuzzleDefinition {
+        PuzzleDefinition {
+            date: date.map(|date| date.to_string()),
+            columns: self.columns.clone(),
+        }
+    }
+}
+
+/// The words a generator may put in a puzzle's intended solution. Other solutions are
+/// always looked for with the whole dictionary.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Vocabulary {
+    /// None for every dictionary word
+    words: Option<BTreeSet<&'static str>>,
+}
+
+impl Vocabulary {
+    /// Every dictionary word
+    pub fn all() -> Self {
+        Self::default()
+    }
+
+    /// The dictionary words in a themed list, like animals, so that every intended
+    /// word fits the theme
+    pub fn themed<'a>(words: impl IntoIterator<Item = &'a str>) -> Self {
+        let words = (words.into_iter())
+            .filter_map(|word| {
+                let index = dictionary::words().binary_search(&word).ok()?;
+                Some(dictionary::words()[index])
+            })
+            .collect();
+
+        Self { words: Some(words) }
+    }
+
+    /// The dictionary words among the first `max_rank` words of a list ordered most
+    /// common first, so that puzzles are solvable with everyday words
+    pub fn common<'a>(frequency_list: impl IntoIterator<Item = &'a str>, max_rank: usize) -> Self {
+        Self::themed(frequency_list.into_iter().take(max_rank))
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        match &self.words {
+            Some(words) => words.contains(word),
+            None => dictionary::words().binary_search(&word).is_ok(),
+        }
+    }
+
+    /// The vocabulary's words of a length, alphabetically
+    fn words_of_length(&self, length: usize) -> Vec<&'static str> {
+        let words: Vec<&'static str> = match &self.words {
+            Some(words) => words.iter().copied().collect(),
+            None => dictionary::words().to_vec(),
+        };
+
+        words
+            .into_iter()
+            .filter(|word| word.len() == length)
+            .collect()
+    }
+}
+
+/// Builds the puzzle whose columns are exactly the letters of some dictionary words
+/// of the same length, then checks that the words are a minimal solution and lists any
+/// others, exactly, with `Typeshift::count_solutions` and `Typeshift::for_each_cover`.
+pub fn from_words(words: &[&str], config: &SolverConfig) -> Result<GeneratedPuzzle, GenerateError> {
+    let intended = dictionary_words(words)?;
+    let columns = columns_for(&intended);
+
+    let typeshift = Typeshift::from_columns(columns.clone());
+    let estimate = typeshift.estimate_search()?;
+    let (_solution, steps) = typeshift.solve_with(config)?;
+
+    let minimal_words = typeshift.count_solutions(config)?.minimal_words;
+    let minimal = intended.len() <= minimal_words;
+    let mut alternatives = BTreeSet::new();
+    typeshift.for_each_cover(minimal_words, config, |solution| {
+        if *solution != intended {
+            alternatives.insert(solution.clone());
+        }
+    })?;
+    let alternatives = alternatives.into_iter().collect();
+
+    Ok(GeneratedPuzzle {
+        columns,
+        intended,
+        minimal,
+        alternatives,
+        steps,
+        estimate,
+    })
+}
+
+/// The number of columns and letters per column for generated puzzles
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PuzzleShape {
+    /// The range of letters allowed in each column, leftmost first
+    pub heights: Vec<RangeInclusive<usize>>,
+}
+
+impl Default for PuzzleShape {
+    /// Five columns of three to five letters, like the daily puzzles
+    fn default() -> Self {
+        Self::uniform(5, 3..=5)
+    }
+}
+
+impl PuzzleShape {
+    /// The fewest and most columns allowed
+    pub const COLUMNS: RangeInclusive<usize> = 4..=7;
+
+    /// A shape with the same range of letters in every column
+    pub fn uniform(columns: usize, heights: RangeInclusive<usize>) -> Self {
+        Self {
+            heights: vec![heights; columns],
+        }
+    }
+
+    /// Checks that the shape has an allowed number of columns, that every column allows
+    /// some letters, and that the dictionary's words of its length have enough
+    /// different letters in each position to fill its columns
+    pub fn validate(&self) -> Result<(), GenerateError> {
+        let columns = self.heights.len();
+        if !Self::COLUMNS.contains(&columns) {
+            return Err(GenerateError::ColumnCount(columns));
+        }
+
+        let mut available = vec![BTreeSet::new(); columns];
+        for word in dictionary::words()
+            .iter()
+            .filter(|word| word.len() == columns)
+        {
+            for (letters, ch) in available.iter_mut().zip(word.chars()) {
+                letters.insert(ch);
+            }
+        }
+
+        for (i, (heights, letters)) in self.heights.iter().zip(&available).enumerate() {
+            let column = i + 1;
+            if heights.is_empty() || *heights.end() == 0 {
+                return Err(GenerateError::EmptyColumn { column });
+            }
+            if letters.len() < *heights.start() {
+                return Err(GenerateError::UnsatisfiableColumn {
+                    column,
+                    letters: letters.len(),
+                });
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Returns true if puzzle columns have this shape
+    pub fn fits(&self, columns: &[String]) -> bool {
+        columns.len() == self.heights.len()
+            && zip(columns, &self.heights).all(|(column, heights)| heights.contains(&column.len()))
+    }
+}
+
+/// Builds a puzzle of a shape, from the first words of the vocabulary (alphabetically)
+/// that each add a letter to a column that needs one without overfilling any column.
+/// As with `from_words`, the words are searched to check whether they're minimal.
+pub fn from_shape(
+    shape: &PuzzleShape,
+    vocabulary: &Vocabulary,
+    config: &SolverConfig,
+) -> Result<GeneratedPuzzle, GenerateError> {
+    shape.validate()?;
+
+    let candidates = vocabulary.words_of_length(shape.heights.len());
+    let words = fill_shape(shape, candidates).ok_or(GenerateError::ShapeUnfilled)?;
+
+    from_words(&words, config)
+}
+
+/// Builds a random puzzle of a shape, the same for the same seed and shape.
+/// Like `from_shape`, but picking from the dictionary's words in a shuffled order.
+pub fn generate_random(seed: u64, shape: &PuzzleShape) -> Result<GeneratedPuzzle, GenerateError> {
+    generate_random_with(seed, shape, &Vocabulary::all(), &SolverConfig::default())
+}
+
+/// Like `generate_random`, but picking from a vocabulary's words,
+/// and checking the puzzle with the configuration's limits
+pub fn generate_random_with(
+    seed: u64,
+    shape: &PuzzleShape,
+    vocabulary: &Vocabulary,
+    config: &SolverConfig,
+) -> Result<GeneratedPuzzle, GenerateError> {
+    shape.validate()?;
+
+    let mut candidates = vocabulary.words_of_length(shape.heights.len());
+    SplitMix64(seed).shuffle(&mut candidates);
+    let words = fill_shape(shape, candidates).ok_or(GenerateError::ShapeUnfilled)?;
+
+    from_words(&words, config)
+}
+
+/// The fingerprint of the dictionary `daily` puzzles are generated with
+pub const DAILY_DICTIONARY: u64 = 0x3a2c_a7e1_942b_73e8;
+
+/// How many seeds `daily` tries for a puzzle with a unique minimal solution
+pub const DAILY_ATTEMPTS: usize = 20;
+
+/// The unofficial daily puzzle for a date, the same for everyone with the same dictionary,
+/// so a group can play it without a server. Random puzzles of the default shape are
+/// generated from seeds derived from the date, and the first whose intended words are
+/// its only minimal solution, by `Typeshift::count_solutions`, is kept; if none of
+/// `DAILY_ATTEMPTS` is, the first is.
+///
+/// Fails with `GenerateError::DictionaryMismatch` unless the embedded dictionary
+/// is `DAILY_DICTIONARY`, since another dictionary makes other puzzles.
+pub fn daily(date: Date) -> Result<GeneratedPuzzle, GenerateError> {
+    let found = dictionary::fingerprint();
+    if found != DAILY_DICTIONARY {
+        let expected = DAILY_DICTIONARY;
+        return Err(GenerateError::DictionaryMismatch { expected, found });
+    }
+
+    let shape = PuzzleShape::default();
+    let mut seeds = SplitMix64(date.to_days() as u64);
+    let mut first = None;
+    for _ in 0..DAILY_ATTEMPTS {
+        let generated = generate_random(seeds.next(), &shape)?;
+        if ambiguity(&generated.intended, &SolverConfig::default())? == UNIQUE {
+            return Ok(generated);
+        }
+        first.get_or_insert(generated);
+    }
+
+    Ok(first.unwrap())
+}
+
+/// How often each letter is in each position of a vocabulary's words of one length,
+/// for sampling columns that look like a real puzzle's
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LetterFrequencies {
+    /// For each position, the number of words with each letter there, from 'a' to 'z'
+    pub counts: Vec<[usize; 26]>,
+}
+
+impl LetterFrequencies {
+    /// Counts the letters of the vocabulary's words of a length
+    pub fn new(length: usize, vocabulary: &Vocabulary) -> Self {
+        let mut counts = vec![[0; 26]; length];
+        for word in vocabulary.words_of_length(length) {
+            for (position, letter) in counts.iter_mut().zip(word.bytes()) {
+                position[usize::from(letter - b'a')] += 1;
+            }
+        }
+
+        Self { counts }
+    }
+
+    /// Picks up to `height` different letters for a position, each with odds in proportion
+    /// to its count among the letters not yet picked, in alphabetical order
+    fn sample_column(&self, position: usize, height: usize, random: &mut SplitMix64) -> String {
+        let mut counts = self.counts[position];
+        let mut letters = BTreeSet::new();
+        while letters.len() < height {
+            let total: usize = counts.iter().sum();
+            if total == 0 {
+                break;
+            }
+
+            let mut pick = random.below(total);
+            let index = (counts.iter())
+                .position(|&count| {
+                    let found = pick < count;
+                    pick = pick.saturating_sub(count);
+                    found
+                })
+                .unwrap();
+            counts[index] = 0;
+            letters.insert(char::from(b'a' + index as u8));
+        }
+
+        letters.into_iter().collect()
+    }
+}
+
+/// Builds a random puzzle of a shape, the same for the same seed and shape, by sampling
+/// each column's letters from how often they're in that position of dictionary words.
+/// Unlike `generate_random`, the puzzle isn't built from words, so it may have no
+/// solution; it's sampled again until it has one, up to `max_attempts` times. The first
+/// solution found is taken as the intended words.
+///
+/// Gives up with `GenerateError::ShapeUnfilled` if no solvable puzzle was sampled.
+pub fn from_letter_frequencies(
+    seed: u64,
+    shape: &PuzzleShape,
+    config: &SolverConfig,
+    max_attempts: usize,
+) -> Result<GeneratedPuzzle, GenerateError> {
+    shape.validate()?;
+
+    let frequencies = LetterFrequencies::new(shape.heights.len(), &Vocabulary::all());
+    let mut random = SplitMix64(seed);
+    for _ in 0..max_attempts {
+        let columns = (shape.heights.iter().enumerate())
+            .map(|(position, heights)| {
+                let height = heights.start() + random.below(heights.end() - heights.start() + 1);
+                frequencies.sample_column(position, height, &mut random)
+            })
+            .collect();
+
+        match Typeshift::from_columns(columns).solve_with(config) {
+            // every letter of a solvable puzzle is in its solution, which rebuilds its columns
+            Ok((solution, _steps)) => return from_words(&Vec::from_iter(solution), config),
+            Err(SolveError::Unsolvable) => continue,
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    Err(GenerateError::ShapeUnfilled)
+}
+
+/// Picks candidate words in order, keeping each that adds a letter to a column below its
+/// fewest letters without taking any column past its most, until every column has enough
+fn fill_shape(
+    shape: &PuzzleShape,
+    candidates: impl IntoIterator<Item = &'static str>,
+) -> Option<Vec<&'static str>> {
+    let mut columns = vec![BTreeSet::new(); shape.heights.len()];
+    let mut words = Vec::new();
+    let filled = |columns: &[BTreeSet<char>]| {
+        zip(columns, &shape.heights).all(|(letters, heights)| letters.len() >= *heights.start())
+    };
+
+    for word in candidates {
+        if filled(&columns) {
+            break;
+        }
+
+        let mut needed = false;
+        let mut fits = true;
+        for ((letters, heights), ch) in zip(&columns, &shape.heights).zip(word.chars()) {
+            if letters.contains(&ch) {
+                continue;
+            }
+            needed |= letters.len() < *heights.start();
+            fits &= letters.len() < *heights.end();
+        }
+
+        if needed && fits {
+            for (letters, ch) in columns.iter_mut().zip(word.chars()) {
+                letters.insert(ch);
+            }
+            words.push(word);
+        }
+    }
+
+    filled(&columns).then_some(words)
+}
+
+/// Like `from_words`, but changes letters of the intended words until they're the puzzle's
+/// only minimal solution. Each change swaps one letter of one word to make another
+/// word of the vocabulary, keeping the first change that leaves fewer minimal solutions.
+/// Each puzzle is checked with `Typeshift::count_solutions`, so none are missed.
+///
+/// Gives up with `GenerateError::NotUnique` after checking `max_attempts` puzzles.
+pub fn unique_from_words(
+    words: &[&str],
+    vocabulary: &Vocabulary,
+    config: &SolverConfig,
+    max_attempts: usize,
+) -> Result<GeneratedPuzzle, GenerateError> {
+    check_vocabulary(words, vocabulary)?;
+    let mut best = dictionary_words(words)?;
+    let mut best_ambiguity = ambiguity(&best, config)?;
+    let mut attempts = 1;
+
+    'improving: while best_ambiguity != UNIQUE {
+        for candidate in substitutions(&best, vocabulary) {
+            if attempts >= max_attempts {
+                break 'improving;
+            }
+            attempts += 1;
+
+            let candidate: BTreeSet<&'static str> = candidate.into_iter().collect();
+            // a candidate that hits the search limits is as good as ambiguous
+            let Ok(candidate_ambiguity) = ambiguity(&candidate, config) else {
+                continue;
+            };
+            if candidate_ambiguity < best_ambiguity {
+                best = candidate;
+                best_ambiguity = candidate_ambiguity;
+                continue 'improving;
+            }
+        }
+
+        break;
+    }
+
+    if best_ambiguity != UNIQUE {
+        return Err(GenerateError::NotUnique { attempts });
+    }
+
+    from_words(&Vec::from_iter(best), config)
+}
+
+/// Like `unique_from_words`, but changes letters of the other words until the puzzle
+/// has a required word, like a word of the day, in every minimal solution. The required
+/// word is added to the words if they don't have it, and is never changed. Each puzzle
+/// is checked by listing every minimal solution, keeping the first change that leaves
+/// fewer without the required word.
+///
+/// Gives up with `GenerateError::NotRequired` after checking `max_attempts` puzzles.
+pub fn with_required_word(
+    words: &[&str],
+    required: &str,
+    vocabulary: &Vocabulary,
+    config: &SolverConfig,
+    max_attempts: usize,
+) -> Result<GeneratedPuzzle, GenerateError> {
+    check_vocabulary(words, vocabulary)?;
+    let mut best = dictionary_words(words)?;
+    let required = *dictionary_words(&[required])?.first().unwrap();
+    best.insert(required);
+    dictionary_words(&Vec::from_iter(best.iter().copied()))?;
+
+    let mut best_missing = missing_required(&best, required, config)?;
+    let mut attempts = 1;
+
+    'improving: while best_missing > 0 {
+        for candidate in substitutions(&best, vocabulary) {
+            if !candidate.contains(&required) {
+                continue;
+            }
+            if attempts >= max_attempts {
+                break 'improving;
+            }
+            attempts += 1;
+
+            let candidate: BTreeSet<&'static str> = candidate.into_iter().collect();
+            // a candidate that hits the search limits is no better
+            let Ok(missing) = missing_required(&candidate, required, config) else {
+                continue;
+            };
+            if missing < best_missing {
+                best = candidate;
+                best_missing = missing;
+                continue 'improving;
+            }
+        }
+
+        break;
+    }
+
+    if best_missing > 0 {
+        let word = required.to_string();
+        return Err(GenerateError::NotRequired { word, attempts });
+    }
+
+    from_words(&Vec::from_iter(best), config)
+}
+
+/// Counts the minimal solutions without the required word, of the puzzle made from some words
+fn missing_required(
+    words: &BTreeSet<&'static str>,
+    required: &str,
+    config: &SolverConfig,
+) -> Result<usize, GenerateError> {
+    let typeshift = Typeshift::from_columns(columns_for(words));
+    let minimal_words = typeshift.count_solutions(config)?.minimal_words;
+
+    let mut missing = 0;
+    typeshift.for_each_cover(minimal_words, config, |solution| {
+        missing += usize::from(!solution.contains(required));
+    })?;
+
+    Ok(missing)
+}
+
+/// A range of search difficulty to generate puzzles in; see `Typeshift::estimate_search`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DifficultyBand {
+    pub estimated_steps: RangeInclusive<usize>,
+    pub branching: RangeInclusive<f64>,
+}
+
+impl Default for DifficultyBand {
+    /// Every difficulty
+    fn default() -> Self {
+        Self {
+            estimated_steps: 0..=usize::MAX,
+            branching: 0.0..=f64::INFINITY,
+        }
+    }
+}
+
+impl DifficultyBand {
+    pub fn contains(&self, estimate: &SearchEstimate) -> bool {
+        self.distance(estimate) == 0.0
+    }
+
+    /// How far an estimate is outside the band, adding the distances for each measure
+    fn distance(&self, estimate: &SearchEstimate) -> f64 {
+        let steps = estimate.estimated_steps as f64;
+        let outside =
+            |range: (f64, f64), value: f64| (range.0 - value).max(value - range.1).max(0.0);
+
+        outside(
+            (
+                *self.estimated_steps.start() as f64,
+                *self.estimated_steps.end() as f64,
+            ),
+            steps,
+        ) + outside(
+            (*self.branching.start(), *self.branching.end()),
+            estimate.branching,
+        )
+    }
+}
+
+/// Like `from_words`, but changes letters of the intended words until the puzzle's
+/// estimated difficulty is in a band. Each change swaps one letter of one word to make
+/// another word of the vocabulary, keeping the first change that moves the estimate closer.
+/// Candidates are judged by their quick estimate alone; only the final puzzle is
+/// searched in full.
+///
+/// Gives up with `GenerateError::OutsideBand` after estimating `max_attempts` puzzles.
+pub fn with_difficulty(
+    words: &[&str],
+    band: &DifficultyBand,
+    vocabulary: &Vocabulary,
+    config: &SolverConfig,
+    max_attempts: usize,
+) -> Result<GeneratedPuzzle, GenerateError> {
+    check_vocabulary(words, vocabulary)?;
+    let mut best = dictionary_words(words)?;
+    let mut best_distance = band.distance(&estimate(&best)?);
+    let mut attempts = 1;
+
+    'closer: while best_distance > 0.0 {
+        for candidate in substitutions(&best, vocabulary) {
+            if attempts >= max_attempts {
+                break 'closer;
+            }
+            attempts += 1;
+
+            let candidate: BTreeSet<&'static str> = candidate.into_iter().collect();
+            let Ok(estimate) = estimate(&candidate) else {
+                continue;
+            };
+            let distance = band.distance(&estimate);
+            if distance < best_distance {
+                best = candidate;
+                best_distance = distance;
+                continue 'closer;
+            }
+        }
+
+        break;
+    }
+
+    if best_distance > 0.0 {
+        return Err(GenerateError::OutsideBand { attempts });
+    }
+
+    let best: Vec<&str> = best.into_iter().collect();
+    from_words(&best, config)
+}
+
+/// The number of puzzles in a week
+pub const WEEK_DAYS: usize = 7;
+
+/// A puzzle picked for a day
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PlannedPuzzle {
+    pub date: Date,
+
+    /// The puzzle columns, leftmost first
+    pub columns: Vec<String>,
+
+    /// The puzzle's `Typeshift::difficulty` score
+    pub score: f64,
+}
+
+/// Picks a week of puzzles from a pool, one a day from `start`, each with a higher
+/// difficulty score than the day before, spread evenly from the pool's easiest puzzle
+/// to its hardest. Puzzles that can't be rated within the configuration's limits, or
+/// that tie an easier puzzle's score, are left out.
+///
+/// Fails with `GenerateError::PoolTooSmall` if fewer than `WEEK_DAYS` puzzles are left.
+#[cfg(feature = "std")]
+pub fn plan_week(
+    pool: &[Vec<String>],
+    start: Date,
+    config: &SolverConfig,
+) -> Result<Vec<PlannedPuzzle>, GenerateError> {
+    let mut rated: Vec<(f64, &Vec<String>)> = (pool.iter())
+        .filter_map(|columns| {
+            let typeshift = Typeshift::from_columns(columns.clone());
+            let difficulty = typeshift.difficulty_with(config).ok()?;
+            Some((difficulty.score, columns))
+        })
+        .collect();
+    rated.sort_by(|a, b| a.0.total_cmp(&b.0));
+    rated.dedup_by(|a, b| a.0 == b.0);
+
+    if rated.len() < WEEK_DAYS {
+        let rated = rated.len();
+        return Err(GenerateError::PoolTooSmall { rated });
+    }
+
+    let mut date = start;
+    let mut week = Vec::new();
+    for day in 0..WEEK_DAYS {
+        let (score, columns) = rated[day * (rated.len() - 1) / (WEEK_DAYS - 1)];
+        week.push(PlannedPuzzle {
+            date,
+            columns: columns.clone(),
+            score,
+        });
+        date = date.succ();
+    }
+
+    Ok(week)
+}
+
+/// Estimates the search difficulty of the puzzle made from some words
+fn estimate(words: &BTreeSet<&'static str>) -> Result<SearchEstimate, GenerateError> {
+    let typeshift = Typeshift::from_columns(columns_for(words));
+
+    Ok(typeshift.estimate_search()?)
+}
+
+/// The ambiguity of words that are their puzzle's only minimal solution
+const UNIQUE: (bool, u64) = (false, 1);
+
+/// Orders the puzzles made from some words by how far the words are from being the only
+/// minimal solution: whether fewer words solve it, then how many minimal solutions it has
+fn ambiguity(
+    words: &BTreeSet<&'static str>,
+    config: &SolverConfig,
+) -> Result<(bool, u64), GenerateError> {
+    let typeshift = Typeshift::from_columns(columns_for(words));
+    let count = typeshift.count_solutions(config)?;
+
+    Ok((count.minimal_words < words.len(), count.solutions))
+}
+
+/// Every set of words made by changing one letter of one word to make another word
+/// of the vocabulary, in alphabetical order of the word changed and then its position
+fn substitutions(
+    words: &BTreeSet<&'static str>,
+    vocabulary: &Vocabulary,
+) -> Vec<Vec<&'static str>> {
+    let mut candidates = Vec::new();
+
+    for &word in words {
+        for position in 0..word.len() {
+            for letter in b'a'..=b'z' {
+                let mut changed = word.as_bytes().to_vec();
+                changed[position] = letter;
+                let Ok(changed) = core::str::from_utf8(&changed) else {
+                    continue;
+                };
+
+                let Ok(index) = dictionary::words().binary_search(&changed) else {
+                    continue;
+                };
+                let changed = dictionary::words()[index];
+                if words.contains(changed) || !vocabulary.contains(changed) {
+                    continue;
+                }
+
+                let mut candidate: Vec<&'static str> = words.iter().copied().collect();
+                candidate.retain(|&other| other != word);
+                candidate.push(changed);
+                candidates.push(candidate);
+            }
+        }
+    }
+
+    candidates
+}
+
+/// Rotates words of the same length into columns, each in alphabetical order
+pub fn columns_for<'a>(words: impl IntoIterator<Item = &'a &'a str>) -> Vec<String> {
+    let mut columns: Vec<BTreeSet<char>> = Vec::new();
+    for word in words {
+        columns.resize_with(columns.len().max(word.len()), BTreeSet::new);
+        for (column, ch) in columns.iter_mut().zip(word.chars()) {
+            column.insert(ch);
+        }
+    }
+
+    columns
+        .into_iter()
+        .map(|letters| letters.into_iter().collect())
+        .collect()
+}
+
+/// Checks that the words to start from are in the vocabulary
+fn check_vocabulary(words: &[&str], vocabulary: &Vocabulary) -> Result<(), GenerateError> {
+    match words.iter().find(|word| !vocabulary.contains(word)) {
+        // words outside the dictionary get a clearer error from `dictionary_words`
+        Some(word) if dictionary::words().binary_search(word).is_ok() => {
+            Err(GenerateError::OutsideVocabulary(word.to_string()))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Looks up each word in the dictionary, checking that they're all the same length
+fn dictionary_words(words: &[&str]) -> Result<BTreeSet<&'static str>, GenerateError> {
+    let Some(length) = words.first().map(|word| word.len()) else {
+        return Err(GenerateError::NoWords);
+    };
+
+    let mut found = BTreeSet::new();
+    for &word in words {
+        if word.len() != length {
+            return Err(GenerateError::MixedLengths {
+                expected: length,
+                word: word.to_string(),
+            });
+        }
+
+        let index = dictionary::words()
+            .binary_search(&word)
+            .map_err(|_| GenerateError::NotAWord(word.to_string()))?;
+        found.insert(dictionary::words()[index]);
+    }
+
+    Ok(found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::typeshift::SolutionCount;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn puzzle_from_words() {
+        let words = ["above", "basic", "study", "wheel", "whups"];
+        let generated = from_words(&words, &SolverConfig::default()).unwrap();
+
+        assert_eq!(
+            generated.columns,
+            ["absw", "abht", "eosu", "deipv", "celsy"]
+        );
+        assert_eq!(generated.intended, BTreeSet::from(words));
+        assert!(generated.minimal);
+        // 16 minimal solutions in all, most of which the trimmed search misses
+        assert_eq!(generated.alternatives.len(), 15);
+        assert!(generated
+            .alternatives
+            .iter()
+            .all(|solution| solution.len() == words.len()));
+        assert!(!generated.is_unique());
+    }
+
+    #[test]
+    fn puzzle_definitions() {
+        let words = ["above", "basic", "study", "wheel", "whups"];
+        let generated = from_words(&words, &SolverConfig::default()).unwrap();
+        let definition = generated.definition(Some("2024-01-01".parse().unwrap()));
+
+        assert_eq!(definition.date.as_deref(), Some("2024-01-01"));
+        assert_eq!(
+            Typeshift::new(&definition.to_input()).input_columns(),
+            generated.columns
+        );
+    }
+
+    #[test]
+    fn unique_puzzle() {
+        let words = ["above", "basic", "study", "wheel", "whups"];
+        let config = SolverConfig::default();
+        let generated = unique_from_words(&words, &Vocabulary::all(), &config, 500).unwrap();
+
+        let expected = ["above", "basic", "jheel", "study", "whaps"];
+        assert_eq!(generated.intended, BTreeSet::from(expected));
+
+        // counted exactly, since the trimmed search can miss solutions
+        let typeshift = generated.typeshift();
+        let count = typeshift.count_solutions(&config).unwrap();
+        assert_eq!(
+            count,
+            SolutionCount {
+                minimal_words: 5,
+                solutions: 1
+            }
+        );
+        let mut solutions = Vec::new();
+        typeshift
+            .for_each_cover(5, &config, |solution| solutions.push(solution.clone()))
+            .unwrap();
+        assert_eq!(solutions, [BTreeSet::from(expected)]);
+    }
+
+    #[test]
+    fn required_word() {
+        let config = SolverConfig::default();
+        let words = ["above", "basic", "study", "whups"];
+        let generated =
+            with_required_word(&words, "wheel", &Vocabulary::all(), &config, 500).unwrap();
+
+        let expected = ["above", "basic", "chaps", "study", "wheel"];
+        assert_eq!(generated.intended, BTreeSet::from(expected));
+
+        let typeshift = generated.typeshift();
+        let minimal_words = typeshift.count_solutions(&config).unwrap().minimal_words;
+        let mut solutions = 0;
+        typeshift
+            .for_each_cover(minimal_words, &config, |solution| {
+                solutions += 1;
+                assert!(solution.contains("wheel"));
+            })
+            .unwrap();
+        assert_eq!(solutions, 1);
+
+        let words = ["above", "basic", "study", "wheel"];
+        assert_eq!(
+            with_required_word(&words, "whups", &Vocabulary::all(), &config, 500),
+            Err(GenerateError::NotRequired {
+                word: "whups".to_string(),
+                attempts: 26
+            })
+        );
+    }
+
+    #[test]
+    fn puzzle_in_a_difficulty_band() {
+        let words = ["above", "basic", "study", "wheel", "whups"];
+        let band = DifficultyBand {
+            estimated_steps: 20..=30,
+            ..Default::default()
+        };
+        let generated = with_difficulty(
+            &words,
+            &band,
+            &Vocabulary::all(),
+            &SolverConfig::default(),
+            500,
+        )
+        .unwrap();
+
+        assert!(band.contains(&generated.estimate));
+        assert_eq!(
+            generated.intended,
+            BTreeSet::from(["apode", "naric", "study", "wheel", "whups"])
+        );
+
+        let impossible = DifficultyBand {
+            branching: 100.0..=100.0,
+            ..Default::default()
+        };
+        assert_eq!(
+            with_difficulty(
+                &words,
+                &impossible,
+                &Vocabulary::all(),
+                &SolverConfig::default(),
+                50
+            ),
+            Err(GenerateError::OutsideBand { attempts: 50 })
+        );
+    }
+
+    #[test]
+    fn puzzle_of_a_shape() {
+        let shape = PuzzleShape::uniform(6, 3..=4);
+        let generated = from_shape(&shape, &Vocabulary::all(), &SolverConfig::default()).unwrap();
+
+        assert!(shape.fits(&generated.columns));
+        assert_eq!(
+            generated.columns,
+            ["abc", "abc", "ahlr", "cilr", "agin", "aghs"]
+        );
+        let words = [
+            "aahing", "aaliis", "aarrgh", "abacas", "acacia", "baaing", "callas",
+        ];
+        assert_eq!(generated.intended, BTreeSet::from(words));
+    }
+
+    #[test]
+    fn common_words_only() {
+        let config = SolverConfig::default();
+        let frequency_list = ["above", "basic", "study", "xyzzy", "wheel", "whups"];
+
+        let common = Vocabulary::common(frequency_list, 5);
+        assert!(common.contains("wheel") && !common.contains("whups"));
+
+        let generated = from_shape(&PuzzleShape::default(), &common, &config).unwrap();
+        assert_eq!(
+            generated.intended,
+            BTreeSet::from(["above", "basic", "study"])
+        );
+
+        let too_few = Vocabulary::common(frequency_list, 2);
+        assert_eq!(
+            from_shape(&PuzzleShape::default(), &too_few, &config),
+            Err(GenerateError::ShapeUnfilled)
+        );
+
+        let words = ["above", "basic", "study", "wheel", "whups"];
+        assert_eq!(
+            unique_from_words(&words, &common, &config, 10),
+            Err(GenerateError::OutsideVocabulary("whups".to_string()))
+        );
+    }
+
+    #[test]
+    fn themed_puzzles() {
+        let animals = [
+            "bison", "camel", "eagle", "horse", "hyena", "koala", "lemur", "llama", "moose",
+            "otter", "panda", "shark", "sheep", "tiger", "whale", "zebra", "aardvark",
+        ];
+        let theme = Vocabulary::themed(animals);
+
+        let generated =
+            generate_random_with(3, &PuzzleShape::default(), &theme, &Default::default()).unwrap();
+        let expected = ["camel", "koala", "moose", "shark", "sheep"];
+        assert_eq!(generated.intended, BTreeSet::from(expected));
+
+        // alternatives come from the whole dictionary
+        let alternative_words = generated.alternatives.iter().flatten();
+        assert!(alternative_words.clone().any(|word| *word == "chark"));
+    }
+
+    #[test]
+    fn random_puzzles_repeat_by_seed() {
+        let shape = PuzzleShape::default();
+        let first = generate_random(7, &shape).unwrap();
+
+        assert!(shape.fits(&first.columns));
+        // pinned, since tournaments and test suites rely on seeds repeating across releases
+        let words = ["manga", "shiur", "slips", "tesla"];
+        assert_eq!(first.intended, BTreeSet::from(words));
+        assert_eq!(generate_random(7, &shape).unwrap(), first);
+        assert_ne!(generate_random(8, &shape).unwrap().columns, first.columns);
+    }
+
+    #[test]
+    fn daily_puzzles_repeat_by_date() {
+        let date: Date = "2024-06-01".parse().unwrap();
+        let puzzle = daily(date).unwrap();
+
+        // pinned, since everyone playing the same day relies on it across releases
+        assert_eq!(puzzle.columns, ["dhjs", "hiop", "aemn", "cek", "dst"]);
+        assert_eq!(daily(date).unwrap(), puzzle);
+        assert_ne!(daily(date.succ()).unwrap().columns, puzzle.columns);
+        let changed = daily("2024-06-06".parse().unwrap()).unwrap();
+        assert_eq!(changed.columns, ["fls", "ahi", "adm", "bgr", "deo"]);
+
+        // including days whose first unique-looking pick had other solutions the trimmed
+        // search missed
+        for date in [
+            "2024-06-01",
+            "2024-06-06",
+            "2024-06-18",
+            "2024-06-23",
+            "2024-07-20",
+        ] {
+            let puzzle = daily(date.parse().unwrap()).unwrap();
+            let count = puzzle
+                .typeshift()
+                .count_solutions(&Default::default())
+                .unwrap();
+            assert_eq!(
+                (count.minimal_words, count.solutions),
+                (puzzle.intended.len(), 1)
+            );
+        }
+    }
+
+    #[test]
+    fn weekly_difficulty_curve() {
+        let config = SolverConfig::default();
+        let pool: Vec<Vec<String>> = (0..12)
+            .map(|seed| {
+                generate_random(seed, &PuzzleShape::default())
+                    .unwrap()
+                    .columns
+            })
+            .collect();
+
+        let start: Date = "2024-01-01".parse().unwrap();
+        let week = plan_week(&pool, start, &config).unwrap();
+
+        assert_eq!(week.len(), WEEK_DAYS);
+        assert_eq!(week[6].date, "2024-01-07".parse().unwrap());
+        assert!(week.windows(2).all(|days| days[0].score < days[1].score));
+
+        assert_eq!(
+            plan_week(&pool[..3], start, &config),
+            Err(GenerateError::PoolTooSmall { rated: 3 })
+        );
+    }
+
+    #[test]
+    fn puzzles_from_letter_frequencies() {
+        let config = SolverConfig::default();
+        let shape = PuzzleShape::default();
+        let generated = from_letter_frequencies(7, &shape, &config, 1000).unwrap();
+
+        assert!(shape.fits(&generated.columns));
+        // pinned, like `generate_random`'s seeds
+        assert_eq!(generated.columns, ["bhsyz", "elo", "inpt", "adet", "dest"]);
+        assert_eq!(columns_for(&generated.intended), generated.columns);
+        assert_eq!(
+            from_letter_frequencies(7, &shape, &config, 1000).unwrap(),
+            generated
+        );
+
+        let frequencies = LetterFrequencies::new(5, &Vocabulary::all());
+        let last = frequencies.counts[4];
+        let most_common = (0..26).max_by_key(|&letter| last[letter]).unwrap();
+        assert_eq!(most_common, usize::from(b's' - b'a'));
+    }
+
+    #[test]
+    fn invalid_shapes() {
+        let config = SolverConfig::default();
+
+        assert_eq!(
+            from_shape(&PuzzleShape::uniform(3, 3..=5), &Vocabulary::all(), &config),
+            Err(GenerateError::ColumnCount(3))
+        );
+
+        let mut shape = PuzzleShape::default();
+        shape.heights[1] = 0..=0;
+        assert_eq!(
+            from_shape(&shape, &Vocabulary::all(), &config),
+            Err(GenerateError::EmptyColumn { column: 2 })
+        );
+
+        let too_tall = PuzzleShape::uniform(7, 26..=26);
+        assert_eq!(
+            from_shape(&too_tall, &Vocabulary::all(), &config),
+            Err(GenerateError::UnsatisfiableColumn {
+                column: 7,
+                letters: 25
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_words() {
+        let config = SolverConfig::default();
+
+        assert_eq!(from_words(&[], &config), Err(GenerateError::NoWords));
+        assert_eq!(
+            from_words(&["above", "cat"], &config),
+            Err(GenerateError::MixedLengths {
+                expected: 5,
+                word: "cat".to_string()
+            })
+        );
+        assert_eq!(
+            from_words(&["above", "xyzzy"], &config),
+            Err(GenerateError::NotAWord("xyzzy".to_string()))
+        );
+    }
+}
@@ -0,0 +1,179 @@
+//! Upright rendering of puzzle grids, optionally colorized with ANSI escapes, or as HTML
+
+use alloc::format;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::iter::zip;
+
+/// Foreground colors assigned to solution words in order, cycling if needed
+const WORD_COLORS: [&str; 6] = ["31", "32", "33", "34", "35", "36"];
+
+/// Bold and inverted; for letters covered by more than one word
+const OVERLAP_STYLE: &str = "1;7";
+
+/// Faint; for letters not covered by any word
+const UNUSED_STYLE: &str = "2";
+
+const RESET: &str = "\x1b[0m";
+
+/// Background colors for solution words in HTML, matching the ANSI colors in order
+const HTML_COLORS: [&str; 6] = [
+    "#f4a6a6", "#a6e3a1", "#f9e2af", "#a6c8f4", "#e0a6f4", "#a6eef4",
+];
+
+/// Renders the puzzle columns upright (one column per grid column, top to bottom),
+/// marking each letter by the solution words that cover it.
+///
+/// With color, each word gets its own color, letters covered by several words are highlighted,
+/// and unused letters are dimmed; a legend of the word colors follows the grid.
+/// Without color, covered letters are uppercase and unused letters lowercase.
+pub fn render_grid(columns: &[String], words: &[String], color: bool) -> String {
+    let height = columns.iter().map(|col| col.len()).max().unwrap_or(0);
+    let mut out = String::new();
+
+    for row in 0..height {
+        let mut cells = Vec::with_capacity(columns.len());
+        for (col, letters) in columns.iter().enumerate() {
+            let Some(ch) = letters.chars().nth(row) else {
+                cells.push(" ".to_string());
+                continue;
+            };
+
+            let covering = covering_words(words, col, ch);
+            cells.push(render_cell(ch, &covering, color));
+        }
+
+        out.push_str(cells.join(" ").trim_end());
+        out.push('\n');
+    }
+
+    if color {
+        let legend: Vec<_> = zip(words, WORD_COLORS.iter().cycle())
+            .map(|(word, style)| paint(word, style))
+            .collect();
+        out.push_str(&legend.join(" "));
+        out.push('\n');
+    }
+
+    out
+}
+
+/// Renders the puzzle columns upright as an HTML table, like `render_grid` with color:
+/// each word's letters get its background color, letters covered by several words
+/// are bold and outlined, and unused letters are grayed out.
+pub fn render_html(columns: &[String], words: &[String]) -> String {
+    let height = columns.iter().map(|col| col.len()).max().unwrap_or(0);
+    let mut out = String::from(
+        "<table style=\"border-collapse: collapse; font-family: monospace; text-align: center\">\n",
+    );
+
+    for row in 0..height {
+        out.push_str("<tr>");
+        for (col, letters) in columns.iter().enumerate() {
+            let Some(ch) = letters.chars().nth(row) else {
+                out.push_str("<td></td>");
+                continue;
+            };
+
+            let style = match covering_words(words, col, ch).as_slice() {
+                [] => "color: #999".to_string(),
+                [word] => format!("background: {}", html_color(*word)),
+                [word, ..] => format!(
+                    "background: {}; font-weight: bold; outline: 2px solid black",
+                    html_color(*word)
+                ),
+            };
+            let ch = ch.to_ascii_uppercase();
+            out.push_str(&format!(
+                "<td style=\"padding: 4px 8px; {style}\">{ch}</td>"
+            ));
+        }
+        out.push_str("</tr>\n");
+    }
+
+    out.push_str("</table>\n");
+    if !words.is_empty() {
+        let legend: Vec<_> = words
+            .iter()
+            .enumerate()
+            .map(|(i, word)| {
+                format!(
+                    "<span style=\"background: {}\">{word}</span>",
+                    html_color(i)
+                )
+            })
+            .collect();
+        out.push_str(&format!("<p>{}</p>\n", legend.join(" ")));
+    }
+
+    out
+}
+
+/// The indexes of the words that use a letter in a column
+pub(crate) fn covering_words(words: &[String], col: usize, ch: char) -> Vec<usize> {
+    words
+        .iter()
+        .enumerate()
+        .filter(|(_i, word)| word.chars().nth(col) == Some(ch))
+        .map(|(i, _word)| i)
+        .collect()
+}
+
+fn render_cell(ch: char, covering: &[usize], color: bool) -> String {
+    match (covering, color) {
+        ([], false) => ch.to_string(),
+        (_, false) => ch.to_ascii_uppercase().to_string(),
+        ([], true) => paint(&ch.to_string(), UNUSED_STYLE),
+        ([word], true) => paint(&ch.to_string(), word_color(*word)),
+        (_, true) => paint(&ch.to_string(), OVERLAP_STYLE),
+    }
+}
+
+fn word_color(word_index: usize) -> &'static str {
+    WORD_COLORS[word_index % WORD_COLORS.len()]
+}
+
+fn html_color(word_index: usize) -> &'static str {
+    HTML_COLORS[word_index % HTML_COLORS.len()]
+}
+
+fn paint(text: &str, style: &str) -> String {
+    format!("\x1b[{style}m{text}{RESET}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn plain_grid() {
+        let columns: Vec<String> = ["wsab", "hbta", "oesu", "dpive", "lceys"]
+            .map(String::from)
+            .to_vec();
+        let words: Vec<String> = ["above", "basic", "study", "whups"]
+            .map(String::from)
+            .to_vec();
+
+        let expected = "\
+W H O D l
+S B e P C
+A T S I E
+B A U V Y
+      e S
+";
+        assert_eq!(render_grid(&columns, &words, false), expected);
+    }
+
+    #[test]
+    fn html_grid() {
+        let columns: Vec<String> = ["ab", "c"].map(String::from).to_vec();
+        let words: Vec<String> = ["ac".to_string()].to_vec();
+
+        let html = render_html(&columns, &words);
+        assert!(html.contains("<td style=\"padding: 4px 8px; background: #f4a6a6\">A</td>"));
+        assert!(html.contains("<td style=\"padding: 4px 8px; color: #999\">B</td><td></td>"));
+        assert!(html.contains("<span style=\"background: #f4a6a6\">ac</span>"));
+    }
+}
@@ -0,0 +1,314 @@
+//! A gRPC service for proto/typeshift.proto, for embedding the solver in tonic servers
+//!
+//! ```ignore
+//! tonic::transport::Server::builder()
+//!     .add_service(SolverServer::new(SolverService::default()))
+//! ```
+
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use tonic::{Request, Response, Status};
+
+use crate::async_solve::{solve_all_async, solve_async};
+use crate::error::{GenerateError, SolveError};
+use crate::generate::{self, PuzzleShape, Vocabulary};
+use crate::{hint, SolverConfig, Strategy, Typeshift};
+
+mod generated {
+    include!(concat!(env!("OUT_DIR"), "/typeshift.Solver.rs"));
+}
+pub use generated::solver_client::SolverClient;
+pub use generated::solver_server::{Solver, SolverServer};
+
+// messages, kept in sync with proto/typeshift.proto by hand
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct SolveRequest {
+    #[prost(string, repeated, tag = "1")]
+    pub columns: Vec<String>,
+    #[prost(bool, tag = "2")]
+    pub greedy: bool,
+    #[prost(uint64, optional, tag = "3")]
+    pub max_steps: Option<u64>,
+    #[prost(uint64, optional, tag = "4")]
+    pub timeout_ms: Option<u64>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct SolveResponse {
+    #[prost(string, repeated, tag = "1")]
+    pub words: Vec<String>,
+    #[prost(uint64, tag = "2")]
+    pub steps: u64,
+    #[prost(uint64, tag = "3")]
+    pub possible_words: u64,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct Solution {
+    #[prost(string, repeated, tag = "1")]
+    pub words: Vec<String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct SolveAllResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub solutions: Vec<Solution>,
+    #[prost(uint64, tag = "2")]
+    pub steps: u64,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct HintRequest {
+    #[prost(string, repeated, tag = "1")]
+    pub columns: Vec<String>,
+    #[prost(string, repeated, tag = "2")]
+    pub played: Vec<String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct HintResponse {
+    #[prost(string, optional, tag = "1")]
+    pub hint: Option<String>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GenerateRequest {
+    #[prost(uint32, tag = "1")]
+    pub word_length: u32,
+    #[prost(uint64, optional, tag = "2")]
+    pub seed: Option<u64>,
+}
+
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct GenerateResponse {
+    #[prost(string, repeated, tag = "1")]
+    pub columns: Vec<String>,
+}
+
+/// The solver service; requests' timeouts are capped by `max_timeout`
+#[derive(Debug, Clone, Copy)]
+pub struct SolverService {
+    pub max_timeout: Duration,
+}
+
+impl Default for SolverService {
+    fn default() -> Self {
+        Self {
+            max_timeout: Duration::from_secs(1),
+        }
+    }
+}
+
+impl SolverService {
+    fn config(&self, request: &SolveRequest) -> SolverConfig {
+        let timeout = request
+            .timeout_ms
+            .map(Duration::from_millis)
+            .map_or(self.max_timeout, |timeout| timeout.min(self.max_timeout));
+        let strategy = if request.greedy {
+            Strategy::Greedy
+        } else {
+            Strategy::BestFirst
+        };
+
+        SolverConfig {
+            strategy,
+            max_steps: request.max_steps.map(|steps| steps as usize),
+            timeout: Some(timeout),
+            ..Default::default()
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl Solver for SolverService {
+    async fn solve(
+        &self,
+        request: Request<SolveRequest>,
+    ) -> Result<Response<SolveResponse>, Status> {
+        let request = request.into_inner();
+        let typeshift = parse(&request.columns)?;
+
+        let (words, steps) = solve_async(typeshift.clone(), self.config(&request))
+            .await
+            .map_err(solve_status)?;
+
+        Ok(Response::new(SolveResponse {
+            words: words.iter().map(|w| w.to_string()).collect(),
+            steps: steps as u64,
+            possible_words: typeshift.size() as u64,
+        }))
+    }
+
+    async fn solve_all(
+        &self,
+        request: Request<SolveRequest>,
+    ) -> Result<Response<SolveAllResponse>, Status> {
+        let request = request.into_inner();
+        let typeshift = parse(&request.columns)?;
+
+        let (solutions, steps) = solve_all_async(typeshift, self.config(&request))
+            .await
+            .map_err(solve_status)?;
+        let solutions = solutions
+            .iter()
+            .map(|words| Solution {
+                words: words.iter().map(|w| w.to_string()).collect(),
+            })
+            .collect();
+
+        Ok(Response::new(SolveAllResponse {
+            solutions,
+            steps: steps as u64,
+        }))
+    }
+
+    async fn hint(&self, request: Request<HintRequest>) -> Result<Response<HintResponse>, Status> {
+        let request = request.into_inner();
+        let typeshift = parse(&request.columns)?;
+        let config = SolverConfig {
+            timeout: Some(self.max_timeout),
+            ..Default::default()
+        };
+
+        let (solution, _steps) = solve_async(typeshift, config).await.map_err(solve_status)?;
+        let played: Vec<&str> = request.played.iter().map(String::as_str).collect();
+        let hint = hint::suggest(&solution, &played).map(String::from);
+
+        Ok(Response::new(HintResponse { hint }))
+    }
+
+    /// Generates a random puzzle with the default column heights;
+    /// without a seed, one is taken from the clock
+    async fn generate(
+        &self,
+        request: Request<GenerateRequest>,
+    ) -> Result<Response<GenerateResponse>, Status> {
+        let request = request.into_inner();
+        let seed = request.seed.unwrap_or_else(|| {
+            let now = SystemTime::now().duration_since(UNIX_EPOCH).unwrap();
+            now.as_nanos() as u64
+        });
+        let shape = PuzzleShape {
+            heights: vec![3..=5; request.word_length as usize],
+        };
+        let config = SolverConfig {
+            timeout: Some(self.max_timeout),
+            ..Default::default()
+        };
+
+        let generated = tokio::task::spawn_blocking(move || {
+            generate::generate_random_with(seed, &shape, &Vocabulary::all(), &config)
+        })
+        .await
+        .map_err(|e| Status::internal(e.to_string()))?
+        .map_err(generate_status)?;
+
+        Ok(Response::new(GenerateResponse {
+            columns: generated.columns,
+        }))
+    }
+}
+
+fn parse(columns: &[String]) -> Result<Arc<Typeshift>, Status> {
+    Typeshift::parse(&columns.join("\n"))
+        .map(Arc::new)
+        .map_err(|e| Status::invalid_argument(e.to_string()))
+}
+
+fn solve_status(e: SolveError) -> Status {
+    match e {
+        SolveError::Unsolvable => Status::failed_precondition(e.to_string()),
+        SolveError::Timeout { .. } => Status::deadline_exceeded(e.to_string()),
+        SolveError::Cancelled { .. } => Status::cancelled(e.to_string()),
+    }
+}
+
+fn generate_status(e: GenerateError) -> Status {
+    match e {
+        GenerateError::Solve(e) => solve_status(e),
+        GenerateError::ShapeUnfilled => Status::failed_precondition(e.to_string()),
+        e => Status::invalid_argument(e.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn solve_and_hint() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let service = SolverService::default();
+        let columns: Vec<String> = ["wsab", "hbta", "oesu", "dpive", "lceys"]
+            .map(String::from)
+            .to_vec();
+
+        let request = SolveRequest {
+            columns: columns.clone(),
+            ..Default::default()
+        };
+        let response = runtime
+            .block_on(service.solve(Request::new(request)))
+            .unwrap()
+            .into_inner();
+        assert_eq!(
+            response.words,
+            ["above", "basic", "study", "wheel", "whups"]
+        );
+        assert_eq!(response.steps, 6);
+
+        let request = HintRequest {
+            columns,
+            played: vec!["above".to_string()],
+        };
+        let response = runtime
+            .block_on(service.hint(Request::new(request)))
+            .unwrap()
+            .into_inner();
+        assert_eq!(response.hint.as_deref(), Some("basic"));
+
+        let request = SolveRequest {
+            columns: vec!["ABC".to_string()],
+            ..Default::default()
+        };
+        let status = runtime
+            .block_on(service.solve(Request::new(request)))
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+
+    #[test]
+    fn generate() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .unwrap();
+        let service = SolverService::default();
+
+        let request = GenerateRequest {
+            word_length: 5,
+            seed: Some(7),
+        };
+        let response = runtime
+            .block_on(service.generate(Request::new(request)))
+            .unwrap()
+            .into_inner();
+        let expected = generate::generate_random(7, &PuzzleShape::default()).unwrap();
+        assert_eq!(response.columns, expected.columns);
+
+        let request = GenerateRequest {
+            word_length: 9,
+            seed: None,
+        };
+        let status = runtime
+            .block_on(service.generate(Request::new(request)))
+            .unwrap_err();
+        assert_eq!(status.code(), tonic::Code::InvalidArgument);
+    }
+}
@@ -0,0 +1,310 @@
+//! Suggestions for a player partway through a puzzle
+
+use alloc::collections::{BTreeMap, BTreeSet};
+use alloc::vec::Vec;
+use core::cmp::Reverse;
+use core::fmt;
+
+use crate::Typeshift;
+
+/// Suggests the first word of a solution, in its order (alphabetical for a set,
+/// or easiest first from `human_order`), that hasn't been played
+/// and would cover a letter the played words don't.
+/// Returns None once every letter the solution covers is covered.
+pub fn suggest<'a>(
+    solution: impl IntoIterator<Item = &'a &'static str>,
+    played: &[&str],
+) -> Option<&'static str> {
+    let covered = |col: usize, ch: char| played.iter().any(|w| w.chars().nth(col) == Some(ch));
+
+    solution.into_iter().copied().find(|word| {
+        !played.contains(word) && word.chars().enumerate().any(|(col, ch)| !covered(col, ch))
+    })
+}
+
+/// Orders a solution's words by how easily a person is likely to find them, easiest first.
+/// Words in the frequency list (most common first) come before words that aren't,
+/// then words whose rarest letter is used more by the puzzle's words; ties stay alphabetical.
+pub fn human_order(
+    typeshift: &Typeshift,
+    solution: &BTreeSet<&'static str>,
+    frequency_list: &[&str],
+) -> Vec<&'static str> {
+    let mut letter_counts = BTreeMap::new();
+    for letter in typeshift.words().iter().flat_map(|word| word.chars()) {
+        *letter_counts.entry(letter).or_insert(0) += 1;
+    }
+    let rarest_letter = |word: &str| word.chars().map(|letter| letter_counts[&letter]).min();
+    let frequency_rank = |word: &str| frequency_list.iter().position(|&listed| listed == word);
+
+    let mut words = Vec::from_iter(solution.iter().copied());
+    words.sort_by_cached_key(|word| {
+        let rank = frequency_rank(word).unwrap_or(usize::MAX);
+        (rank, Reverse(rarest_letter(word)))
+    });
+
+    words
+}
+
+/// How much of a word a hint gives away, least first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HintLevel {
+    /// The word's length and its rarest letter
+    Clue,
+    /// The word's length and first letter
+    FirstLetter,
+    /// The whole word
+    Word,
+}
+
+impl HintLevel {
+    /// The level that gives away more, or None after the whole word
+    pub fn next(self) -> Option<Self> {
+        match self {
+            HintLevel::Clue => Some(HintLevel::FirstLetter),
+            HintLevel::FirstLetter => Some(HintLevel::Word),
+            HintLevel::Word => None,
+        }
+    }
+}
+
+/// A hint about a word to play, redacted to a `HintLevel`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Hint {
+    /// The word's length, and the letter fewest of the puzzle's words use in its column;
+    /// columns are 0-based
+    Clue {
+        length: usize,
+        column: usize,
+        letter: char,
+    },
+    /// The word's length and first letter
+    FirstLetter { length: usize, letter: char },
+    /// The whole word
+    Word(&'static str),
+}
+
+impl Hint {
+    /// Redacts a word of the puzzle, usually a `suggest`ion, to the given level
+    pub fn new(typeshift: &Typeshift, word: &'static str, level: HintLevel) -> Self {
+        let length = word.chars().count();
+        match level {
+            HintLevel::Clue => {
+                let usage = |column: usize, letter: char| {
+                    (typeshift.words().iter())
+                        .filter(|word| word.chars().nth(column) == Some(letter))
+                        .count()
+                };
+                let (column, letter) = (word.chars().enumerate())
+                    .min_by_key(|&(column, letter)| usage(column, letter))
+                    .unwrap();
+                Hint::Clue {
+                    length,
+                    column,
+                    letter,
+                }
+            }
+            HintLevel::FirstLetter => {
+                let letter = word.chars().next().unwrap();
+                Hint::FirstLetter { length, letter }
+            }
+            HintLevel::Word => Hint::Word(word),
+        }
+    }
+}
+
+impl fmt::Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Hint::Clue {
+                length,
+                column,
+                letter,
+            } => {
+                let column = column + 1;
+                write!(
+                    f,
+                    "a {length}-letter word using the {letter} in column {column}"
+                )
+            }
+            Hint::FirstLetter { length, letter } => {
+                write!(f, "a {length}-letter word starting with {letter}")
+            }
+            Hint::Word(word) => write!(f, "{word}"),
+        }
+    }
+}
+
+/// A candidate next word, with the parts of the ranking the solver orders words by,
+/// to show why it likes a word; see `Typeshift::score_words`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct WordScore {
+    pub word: &'static str,
+
+    /// The letters it would cover that the played words don't; more is better
+    pub new_letters: usize,
+
+    /// How many times its rarest letter appears in the puzzle's words; fewer is better
+    pub min_char_freq: usize,
+
+    /// The letters the played words and this one would use more than once;
+    /// the search prefers fewer
+    pub overlaps: usize,
+
+    /// Whether it's tied for the best rank, so the solver would try it next
+    pub best: bool,
+}
+
+impl fmt::Display for WordScore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} (new letters: {}, rarest letter count: {}, overlaps: {})",
+            self.word, self.new_letters, self.min_char_freq, self.overlaps
+        )
+    }
+}
+
+/// A letter no played word covers that only one unplayed word can, so that word
+/// must be played to finish; columns are 0-based
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ForcedLetter {
+    pub column: usize,
+    pub letter: char,
+
+    /// The only word left that covers the letter
+    pub word: &'static str,
+}
+
+impl fmt::Display for ForcedLetter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let column = self.column + 1;
+        write!(
+            f,
+            "only one word left uses the {} in column {column}",
+            self.letter
+        )
+    }
+}
+
+/// Finds the letters the played words leave uncovered that exactly one unplayed word uses,
+/// leftmost column first and alphabetically within a column; the gentlest hint,
+/// since the player has no choice about them
+pub fn forced_letters(typeshift: &Typeshift, played: &[&str]) -> Vec<ForcedLetter> {
+    let uses = |word: &str, column: usize, letter: char| word.chars().nth(column) == Some(letter);
+
+    let mut forced = Vec::new();
+    for (column, letters) in typeshift.input_columns().iter().enumerate() {
+        let mut letters = Vec::from_iter(letters.chars());
+        letters.sort_unstable();
+        for letter in letters {
+            if played.iter().any(|word| uses(word, column, letter)) {
+                continue;
+            }
+
+            let mut candidates = (typeshift.words().iter())
+                .filter(|word| !played.contains(word) && uses(word, column, letter));
+            if let (Some(&word), None) = (candidates.next(), candidates.next()) {
+                forced.push(ForcedLetter {
+                    column,
+                    letter,
+                    word,
+                });
+            }
+        }
+    }
+
+    forced
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn suggestions() {
+        let solution = BTreeSet::from(["above", "basic", "study", "wheel", "whups"]);
+
+        assert_eq!(suggest(&solution, &[]), Some("above"));
+        assert_eq!(suggest(&solution, &["above", "stoic"]), Some("basic"));
+        assert_eq!(
+            suggest(&solution, &["above", "basic", "study", "wheel", "whups"]),
+            None
+        );
+
+        let in_order = ["wheel", "above"];
+        assert_eq!(suggest(&in_order, &[]), Some("wheel"));
+    }
+
+    #[test]
+    fn human_ordering() {
+        let typeshift = Typeshift::new(include_str!("../files/puzzles/2023-11-16.txt"));
+        let solution = BTreeSet::from(["above", "basic", "study", "wheel", "whups"]);
+
+        let by_letters = human_order(&typeshift, &solution, &[]);
+        assert_eq!(by_letters, ["wheel", "whups", "above", "study", "basic"]);
+        let frequency_list = ["the", "study", "wheel"];
+        assert_eq!(
+            human_order(&typeshift, &solution, &frequency_list)[..2],
+            ["study", "wheel"]
+        );
+        assert_eq!(suggest(&by_letters, &["whups"]), Some("wheel"));
+    }
+
+    #[test]
+    fn tiered_hints() {
+        let typeshift = Typeshift::new(include_str!("../files/puzzles/2023-11-16.txt"));
+
+        assert_eq!(
+            Hint::new(&typeshift, "above", HintLevel::Clue),
+            Hint::Clue {
+                length: 5,
+                column: 1,
+                letter: 'b'
+            }
+        );
+        assert_eq!(
+            Hint::new(&typeshift, "above", HintLevel::Clue).to_string(),
+            "a 5-letter word using the b in column 2"
+        );
+        assert_eq!(
+            Hint::new(&typeshift, "above", HintLevel::FirstLetter).to_string(),
+            "a 5-letter word starting with a"
+        );
+        assert_eq!(
+            Hint::new(&typeshift, "above", HintLevel::Word),
+            Hint::Word("above")
+        );
+
+        assert_eq!(HintLevel::Clue.next(), Some(HintLevel::FirstLetter));
+        assert_eq!(HintLevel::Word.next(), None);
+    }
+
+    #[test]
+    fn forced_letter_hints() {
+        let typeshift = Typeshift::new(include_str!("../files/puzzles/2023-11-27.txt"));
+
+        let forced = forced_letters(&typeshift, &[]);
+        assert_eq!(
+            forced,
+            [ForcedLetter {
+                column: 4,
+                letter: 'f',
+                word: "thief"
+            }]
+        );
+        assert_eq!(
+            forced[0].to_string(),
+            "only one word left uses the f in column 5"
+        );
+        assert_eq!(forced_letters(&typeshift, &["thief"]), []);
+
+        let none_forced = Typeshift::new(include_str!("../files/puzzles/2023-11-16.txt"));
+        assert_eq!(forced_letters(&none_forced, &[]), []);
+    }
+}
@@ -0,0 +1,209 @@
+//! A personal log of solved puzzles, with streaks and averages
+//!
+//! The history file has one tab-separated line per solve:
+//! the date, the puzzle's hash, the optimal word count, and the player's word count (if given).
+
+use alloc::collections::BTreeSet;
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+#[cfg(feature = "std")]
+use std::io::{self, Write};
+#[cfg(feature = "std")]
+use std::path::Path;
+
+use crate::date::Date;
+use crate::puzzle;
+
+/// One solved puzzle
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct HistoryEntry {
+    /// The date of the solve
+    pub date: Date,
+
+    /// Identifies the puzzle across solves
+    pub puzzle_hash: String,
+
+    /// The number of words in a minimal solution
+    pub optimal_words: usize,
+
+    /// The number of words the player used, if they said
+    pub player_words: Option<usize>,
+}
+
+#[cfg(feature = "std")]
+impl HistoryEntry {
+    fn to_line(&self) -> String {
+        let player_words = self.player_words.map(|n| n.to_string()).unwrap_or_default();
+
+        format!(
+            "{}\t{}\t{}\t{player_words}\n",
+            self.date, self.puzzle_hash, self.optimal_words
+        )
+    }
+
+    fn parse_line(line: &str) -> Option<Self> {
+        let mut fields = line.split('\t');
+        let date = fields.next()?.parse().ok()?;
+        let puzzle_hash = fields.next()?.to_string();
+        let optimal_words = fields.next()?.parse().ok()?;
+        let player_words = fields.next().and_then(|n| n.parse().ok());
+
+        Some(Self {
+            date,
+            puzzle_hash,
+            optimal_words,
+            player_words,
+        })
+    }
+}
+
+/// Appends an entry to the history file, creating it if needed
+#[cfg(feature = "std")]
+pub fn append(path: impl AsRef<Path>, entry: &HistoryEntry) -> io::Result<()> {
+    let mut file = std::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?;
+
+    file.write_all(entry.to_line().as_bytes())
+}
+
+/// Reads every entry in the history file; a missing file is an empty history.
+/// Malformed lines are skipped.
+#[cfg(feature = "std")]
+pub fn load(path: impl AsRef<Path>) -> io::Result<Vec<HistoryEntry>> {
+    let contents = match std::fs::read_to_string(path) {
+        Ok(contents) => contents,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(e),
+    };
+
+    Ok(contents
+        .lines()
+        .filter_map(HistoryEntry::parse_line)
+        .collect())
+}
+
+/// The puzzle's canonical hash in hex, for recognizing repeat solves
+pub fn puzzle_hash(columns: &[String]) -> String {
+    let hash = puzzle::canonical_hash(columns.iter().map(String::as_str));
+    format!("{hash:016x}")
+}
+
+/// Streaks and averages over a history
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct HistoryStats {
+    pub solves: usize,
+
+    /// Consecutive days with a solve, ending today or yesterday
+    pub current_streak: usize,
+
+    /// The most consecutive days with a solve
+    pub longest_streak: usize,
+
+    pub average_optimal_words: Option<f64>,
+
+    /// Averaged over solves where the player gave a word count
+    pub average_player_words: Option<f64>,
+
+    /// Solves where the player matched the optimal word count
+    pub optimal_solves: usize,
+}
+
+impl HistoryStats {
+    pub fn new(entries: &[HistoryEntry], today: Date) -> Self {
+        let days: BTreeSet<i64> = entries.iter().map(|e| e.date.to_days()).collect();
+
+        let mut longest_streak = 0;
+        let mut streak = 0;
+        let mut previous = None;
+        for &day in &days {
+            streak = if previous == Some(day - 1) {
+                streak + 1
+            } else {
+                1
+            };
+            longest_streak = longest_streak.max(streak);
+            previous = Some(day);
+        }
+
+        let today = today.to_days();
+        let current_streak = match previous {
+            Some(last) if last == today || last == today - 1 => streak,
+            _ => 0,
+        };
+
+        let optimal: Vec<usize> = entries.iter().map(|e| e.optimal_words).collect();
+        let player: Vec<usize> = entries.iter().filter_map(|e| e.player_words).collect();
+        let optimal_solves = entries
+            .iter()
+            .filter(|e| e.player_words == Some(e.optimal_words))
+            .count();
+
+        Self {
+            solves: entries.len(),
+            current_streak,
+            longest_streak,
+            average_optimal_words: average(&optimal),
+            average_player_words: average(&player),
+            optimal_solves,
+        }
+    }
+}
+
+fn average(values: &[usize]) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+
+    Some(values.iter().sum::<usize>() as f64 / values.len() as f64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    fn entry(date: &str, optimal_words: usize, player_words: Option<usize>) -> HistoryEntry {
+        HistoryEntry {
+            date: date.parse().unwrap(),
+            puzzle_hash: "0".to_string(),
+            optimal_words,
+            player_words,
+        }
+    }
+
+    #[test]
+    fn entries_round_trip() {
+        let with_count = entry("2023-11-19", 5, Some(6));
+        let without_count = entry("2023-11-20", 4, None);
+
+        for entry in [with_count, without_count] {
+            let line = entry.to_line();
+            assert_eq!(HistoryEntry::parse_line(line.trim_end()), Some(entry));
+        }
+    }
+
+    #[test]
+    fn streaks_and_averages() {
+        let entries = [
+            entry("2023-11-16", 5, Some(5)),
+            entry("2023-11-17", 5, Some(7)),
+            entry("2023-11-18", 4, None),
+            entry("2023-11-20", 4, Some(4)),
+            entry("2023-11-21", 4, None),
+        ];
+        let stats = HistoryStats::new(&entries, "2023-11-22".parse().unwrap());
+
+        assert_eq!(stats.solves, 5);
+        assert_eq!(stats.current_streak, 2);
+        assert_eq!(stats.longest_streak, 3);
+        assert_eq!(stats.average_optimal_words, Some(4.4));
+        assert_eq!(stats.average_player_words, Some(16.0 / 3.0));
+        assert_eq!(stats.optimal_solves, 2);
+    }
+}
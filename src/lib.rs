@@ -1,4 +1,52 @@
+//! Solves Typeshift puzzles.
+//!
+//! The solver builds with `no_std` and `alloc` when the default `std` feature is off;
+//! file IO, timeouts, and the integrations with other crates need `std`.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod accessible;
+pub mod analysis;
+pub mod animation;
+pub mod archive;
+#[cfg(feature = "async")]
+pub mod async_solve;
+#[cfg(feature = "std")]
+pub mod bench_support;
+pub mod bot;
+pub mod cache;
+#[cfg(feature = "std")]
+pub mod calibrate;
+pub mod chat;
+pub mod coverage;
+#[cfg(feature = "std")]
+pub mod dataset;
+pub mod date;
+#[cfg(not(feature = "compact-dict"))]
 mod dict;
+pub mod dictionary;
+pub mod error;
+#[cfg(feature = "evcxr")]
+mod evcxr;
+pub mod flashcards;
+pub mod game;
+pub mod generate;
+pub mod grid;
+#[cfg(feature = "grpc")]
+pub mod grpc;
+pub mod hint;
+pub mod history;
+pub mod minimize;
+pub mod puzzle;
+mod random;
+pub mod report;
+pub mod score;
+pub mod sheet;
+#[cfg(feature = "test-support")]
+pub mod test_support;
+pub mod trace;
 pub mod typeshift;
 
-pub use typeshift::Typeshift;
+pub use typeshift::{RankWeights, SolverConfig, SolverStepper, StepOutcome, Strategy, Typeshift};
@@ -1,21 +1,132 @@
-use std::time::Instant;
+use clap::{Parser, Subcommand};
 
-use typeshift_solver::Typeshift;
+use typeshift_solver::report::OutputFormat;
 
-fn main() {
-    let start = Instant::now();
+mod commands;
+
+#[cfg(feature = "profile-allocations")]
+#[global_allocator]
+static ALLOCATOR: commands::profile::CountingAllocator = commands::profile::CountingAllocator;
+
+/// A solver for the word game Typeshift
+#[derive(Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+
+    /// The output format, for results and errors
+    #[arg(long, global = true, value_enum, default_value_t)]
+    format: OutputFormat,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve a single puzzle
+    Solve(commands::solve::SolveArgs),
+
+    /// List, show, add, and re-solve archived puzzles
+    Archive(commands::archive::ArchiveArgs),
+
+    /// Show the grid filling in as a solution's words are added, as text frames
+    Animate(commands::animate::AnimateArgs),
+
+    /// Find the fewest words to finish a puzzle from the letters already covered,
+    /// without knowing which words covered them
+    Assist(commands::assist::AssistArgs),
+
+    /// Play a puzzle as a computer opponent of adjustable skill, printing its words in order
+    Bot(commands::bot::BotArgs),
+
+    /// Compare two solver strategies or rankings on the same puzzles
+    Compare(commands::compare::CompareArgs),
+
+    /// Merge several players' progress on the same puzzle,
+    /// and find the fewest words that finish it together
+    Coop(commands::coop::CoopArgs),
+
+    /// Write which of a puzzle's words use which of its letters, as CSV, or find bottlenecks
+    Coverage(commands::coverage::CoverageArgs),
+
+    /// Build a puzzle from the words meant to solve it
+    Generate(commands::generate::GenerateArgs),
 
-    let input = include_str!("../files/puzzles/2023-11-19.txt");
-    let typeshift = Typeshift::new(input);
-    let prep_ts = Instant::now();
+    /// Step through a search recorded by `solve --record`
+    Replay(commands::replay::ReplayArgs),
 
-    let (solution, steps) = typeshift.find_first_solution();
-    let end_ts = Instant::now();
+    /// Compare a finished game to the fewest words possible, and suggest improvements
+    Review(commands::review::ReviewArgs),
+
+    /// Predict the in-game score of a line of play
+    Score(commands::score::ScoreArgs),
+
+    /// Remove letters from a puzzle while it stays slow or the solver stays inconsistent,
+    /// for a small reproducer
+    Shrink(commands::shrink::ShrinkArgs),
+
+    /// Check the solver and dictionary against puzzles with known answers
+    Selftest,
+
+    /// Summarize the solve history recorded by `solve --history`
+    Stats(commands::stats::StatsArgs),
+
+    /// Plan a week of puzzles that get harder each day
+    Week(commands::week::WeekArgs),
+
+    /// Run as a long-lived JSON-RPC worker, keeping the dictionary loaded between puzzles
+    Worker(commands::worker::WorkerArgs),
+
+    /// Download a daily puzzle into the puzzle directory
+    #[cfg(feature = "fetch")]
+    Fetch(commands::fetch::FetchArgs),
+
+    /// Serve solves and hints over HTTP, taking and returning JSON
+    #[cfg(feature = "serve")]
+    Serve(commands::serve::ServeArgs),
+
+    /// Serve the gRPC service defined in proto/typeshift.proto
+    #[cfg(feature = "grpc")]
+    Grpc(commands::grpc::GrpcArgs),
+
+    /// Play a puzzle interactively in the terminal, with hints on demand
+    #[cfg(feature = "tui")]
+    Play(commands::play::PlayArgs),
+}
+
+/// Exits with a distinct code per kind of failure; see `commands::ErrorKind`
+fn main() {
+    let cli = Cli::parse();
+    let format = cli.format;
 
-    let prep_time = prep_ts.duration_since(start);
-    let solve_time = end_ts.duration_since(prep_ts);
-    let total_time = end_ts.duration_since(start);
-    let size = typeshift.size();
+    let result = match cli.command {
+        Command::Solve(args) => commands::solve::run(args, format),
+        Command::Archive(args) => commands::archive::run(args, format),
+        Command::Animate(args) => commands::animate::run(args, format),
+        Command::Assist(args) => commands::assist::run(args, format),
+        Command::Bot(args) => commands::bot::run(args, format),
+        Command::Compare(args) => commands::compare::run(args, format),
+        Command::Coop(args) => commands::coop::run(args, format),
+        Command::Coverage(args) => commands::coverage::run(args, format),
+        Command::Generate(args) => commands::generate::run(args, format),
+        Command::Replay(args) => commands::replay::run(args, format),
+        Command::Review(args) => commands::review::run(args, format),
+        Command::Score(args) => commands::score::run(args, format),
+        Command::Shrink(args) => commands::shrink::run(args, format),
+        Command::Selftest => commands::selftest::run(format),
+        Command::Stats(args) => commands::stats::run(args, format),
+        Command::Week(args) => commands::week::run(args, format),
+        Command::Worker(args) => commands::worker::run(args),
+        #[cfg(feature = "fetch")]
+        Command::Fetch(args) => commands::fetch::run(args, format),
+        #[cfg(feature = "serve")]
+        Command::Serve(args) => commands::serve::run(args),
+        #[cfg(feature = "grpc")]
+        Command::Grpc(args) => commands::grpc::run(args),
+        #[cfg(feature = "tui")]
+        Command::Play(args) => commands::play::run(args),
+    };
 
-    dbg!(size, solution, steps, total_time, prep_time, solve_time);
+    if let Err(error) = result {
+        error.report(format);
+        std::process::exit(error.kind.exit_code());
+    }
 }
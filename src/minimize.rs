@@ -0,0 +1,79 @@
+//! Shrinking puzzles that trigger a bug or a slow search into small reproducers,
+//! like a fuzzer's test case minimizer
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// Removes letters from the puzzle columns one at a time, keeping each removal that leaves
+/// the puzzle interesting, until removing any one more letter would make it uninteresting.
+/// Columns always keep at least one letter. `interesting` is called with candidate columns
+/// and should be deterministic; step limits are better than timings for slow searches.
+pub fn minimize(
+    mut columns: Vec<String>,
+    mut interesting: impl FnMut(&[String]) -> bool,
+) -> Vec<String> {
+    let mut removed_any = true;
+    while removed_any {
+        removed_any = false;
+
+        for column in 0..columns.len() {
+            let mut position = 0;
+            while position < columns[column].len() && columns[column].len() > 1 {
+                let mut candidate = columns.clone();
+                candidate[column].remove(position);
+
+                if interesting(&candidate) {
+                    columns = candidate;
+                    removed_any = true;
+                } else {
+                    position += 1;
+                }
+            }
+        }
+    }
+
+    columns
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use alloc::string::ToString;
+    use pretty_assertions::assert_eq;
+
+    use crate::Typeshift;
+
+    #[test]
+    fn keeps_only_needed_letters() {
+        let columns = Vec::from(["abc", "de", "fgh"].map(str::to_string));
+        let minimized = minimize(columns, |columns| {
+            columns[0].contains('b') && columns[2].len() >= 2
+        });
+
+        assert_eq!(minimized, ["b", "e", "gh"]);
+    }
+
+    #[test]
+    fn shrinks_slow_searches() {
+        let typeshift = Typeshift::new(include_str!("../files/puzzles/2023-11-19.txt"));
+        let slow = |columns: &[String]| {
+            let typeshift = Typeshift::from_columns(columns.to_vec());
+            typeshift
+                .solve_with(&Default::default())
+                .is_ok_and(|(_, steps)| steps >= 20)
+        };
+
+        let minimized = minimize(typeshift.input_columns().to_vec(), slow);
+        assert_eq!(minimized, ["cf", "ior", "iaf", "sevmn", "rpe", "rsy"]);
+
+        // removing any one more letter makes the search fast
+        for column in 0..minimized.len() {
+            for position in 0..minimized[column].len() {
+                let mut smaller = minimized.clone();
+                smaller[column].remove(position);
+                assert!(!slow(&smaller));
+            }
+        }
+    }
+}
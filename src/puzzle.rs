@@ -0,0 +1,85 @@
+//! The JSON puzzle format, for exchanging puzzles with other tools
+
+use alloc::collections::BTreeSet;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+/// A puzzle definition, convertible to and from the plain input format
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PuzzleDefinition {
+    /// The date the puzzle was published, if known
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    pub date: Option<String>,
+
+    /// The puzzle columns, leftmost first
+    pub columns: Vec<String>,
+}
+
+impl PuzzleDefinition {
+    /// Reads the plain input format: one column per line, leftmost first
+    pub fn from_input(input: &str) -> Self {
+        Self {
+            date: None,
+            columns: input.lines().map(String::from).collect(),
+        }
+    }
+
+    /// Writes the plain input format, as expected by `Typeshift::new`
+    pub fn to_input(&self) -> String {
+        let mut input = self.columns.join("\n");
+        input.push('\n');
+        input
+    }
+
+    /// Identifies the puzzle regardless of its date; see `canonical_hash`
+    pub fn canonical_hash(&self) -> u64 {
+        canonical_hash(self.columns.iter().map(|column| column.trim()))
+    }
+}
+
+/// A stable identity for a puzzle, from each column's set of letters, leftmost first.
+/// The order and repeats of letters within a column don't change a puzzle's words,
+/// so they don't change the hash either; the order of the columns does.
+pub fn canonical_hash<'a>(columns: impl IntoIterator<Item = &'a str>) -> u64 {
+    // FNV-1a; stable across platforms and releases, unlike std's hasher
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for column in columns {
+        let letters: BTreeSet<u8> = column.bytes().collect();
+        for byte in letters.into_iter().chain([b'\n']) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+
+    hash
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn round_trips_plain_input() {
+        let input = include_str!("../files/puzzles/2023-11-16.txt");
+        let puzzle = PuzzleDefinition::from_input(input);
+
+        assert_eq!(puzzle.columns, ["wsab", "hbta", "oesu", "dpive", "lceys"]);
+        assert_eq!(puzzle.to_input(), input);
+    }
+
+    #[test]
+    fn canonical_hash_ignores_letter_order() {
+        let puzzle = PuzzleDefinition::from_input("wsab\nhbta\noesu\ndpive\nlceys");
+        let shuffled = PuzzleDefinition::from_input("bwsa\nhtab\nuoes\nvdpei\nlceyss");
+        let reversed = PuzzleDefinition::from_input("lceys\ndpive\noesu\nhbta\nwsab");
+
+        assert_eq!(puzzle.canonical_hash(), shuffled.canonical_hash());
+        assert_ne!(puzzle.canonical_hash(), reversed.canonical_hash());
+    }
+}
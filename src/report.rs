@@ -0,0 +1,192 @@
+//! Stable, serializable summaries of solver results
+
+use alloc::collections::BTreeSet;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+use crate::error::SolveError;
+use crate::trace::SearchObserver;
+use crate::{SolverConfig, Typeshift};
+
+/// The stable summary of a solved puzzle
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SolutionReport {
+    /// The puzzle input columns, leftmost first
+    pub columns: Vec<String>,
+
+    /// The number of possible words (and size of the solution space)
+    pub possible_words: usize,
+
+    /// The number of partial solutions touched before finding the first solution
+    pub steps: usize,
+
+    /// The first solution found, in alphabetical order
+    /// unless reordered (see `hint::human_order`)
+    pub solution: Vec<String>,
+
+    /// The smallest solutions the search finds, if they were requested;
+    /// usually but not always every minimal one
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub all_solutions: Option<Vec<Vec<String>>>,
+}
+
+impl SolutionReport {
+    /// Solves the puzzle, optionally also finding the smallest solutions the search finds
+    pub fn new(typeshift: &Typeshift, include_all: bool) -> Self {
+        Self::with_config(typeshift, include_all, &SolverConfig::default()).unwrap()
+    }
+
+    /// Like `new`, but solving with the given configuration
+    pub fn with_config(
+        typeshift: &Typeshift,
+        include_all: bool,
+        config: &SolverConfig,
+    ) -> Result<Self, SolveError> {
+        Self::with_observer(typeshift, include_all, config, &mut ())
+    }
+
+    /// Like `with_config`, but reporting the search for the first solution to an observer
+    pub fn with_observer(
+        typeshift: &Typeshift,
+        include_all: bool,
+        config: &SolverConfig,
+        observer: &mut impl SearchObserver,
+    ) -> Result<Self, SolveError> {
+        let (solution, steps) = typeshift.solve_observed(config, observer)?;
+
+        let all_solutions = if include_all {
+            Some(typeshift.solve_all_with(config)?.0)
+        } else {
+            None
+        };
+
+        Ok(Self::from_solutions(
+            typeshift,
+            &solution,
+            steps,
+            all_solutions.as_ref(),
+        ))
+    }
+
+    /// Builds a report from solver results, for callers that run the searches themselves
+    pub fn from_solutions(
+        typeshift: &Typeshift,
+        solution: &BTreeSet<&'static str>,
+        steps: usize,
+        all_solutions: Option<&BTreeSet<BTreeSet<&'static str>>>,
+    ) -> Self {
+        Self {
+            columns: typeshift.input_columns().to_vec(),
+            possible_words: typeshift.size(),
+            steps,
+            solution: words_to_strings(solution),
+            all_solutions: all_solutions.map(|all| all.iter().map(words_to_strings).collect()),
+        }
+    }
+}
+
+impl fmt::Display for SolutionReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "possible words: {}", self.possible_words)?;
+        writeln!(f, "steps: {}", self.steps)?;
+        writeln!(f, "solution: {}", self.solution.join(" "))?;
+
+        if let Some(all_solutions) = &self.all_solutions {
+            writeln!(f, "all solutions: {}", all_solutions.len())?;
+            for solution in all_solutions {
+                writeln!(f, "  {}", solution.join(" "))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn words_to_strings(words: &BTreeSet<&'static str>) -> Vec<String> {
+    words.iter().map(|w| w.to_string()).collect()
+}
+
+/// The summary used for the crate's puzzle snapshot tests, for archives kept elsewhere
+/// to generate identical snapshots
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SolutionSnapshot {
+    pub possible_words: usize,
+    pub steps_to_first_solution: usize,
+    pub first_solution: BTreeSet<&'static str>,
+
+    /// The number of minimal solutions, counted exactly; see `Typeshift::count_solutions`
+    pub possible_solutions: u64,
+}
+
+/// Solves a puzzle for its snapshot, finding the first solution and counting the minimal ones.
+///
+/// Panics on invalid input, like `Typeshift::new`.
+pub fn snapshot_for(input: &str) -> SolutionSnapshot {
+    let typeshift = Typeshift::new(input);
+    let (first_solution, steps_to_first_solution) = typeshift.find_first_solution();
+    let count = typeshift.count_solutions(&Default::default()).unwrap();
+
+    SolutionSnapshot {
+        possible_words: typeshift.size(),
+        steps_to_first_solution,
+        first_solution,
+        possible_solutions: count.solutions,
+    }
+}
+
+/// The output formats supported by the command line tools
+#[cfg(feature = "cli")]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum OutputFormat {
+    /// Human-readable text
+    #[default]
+    Plain,
+    /// Pretty-printed JSON
+    Json,
+    /// YAML
+    Yaml,
+    /// Markdown for Discord or Slack; solutions get a spoiler-tagged message
+    /// (see `chat::render_chat`), and other output goes in a code block
+    Chat,
+    /// Plain-language text for screen readers; solutions describe the grid column by column
+    /// and spell out each word (see `accessible::render_accessible`), and other output is plain
+    Accessible,
+}
+
+#[cfg(feature = "cli")]
+impl OutputFormat {
+    /// Renders a report in this format
+    pub fn render<T: serde::Serialize + fmt::Display>(self, report: &T) -> String {
+        match self {
+            OutputFormat::Plain => report.to_string(),
+            OutputFormat::Json => serde_json::to_string_pretty(report).unwrap() + "\n",
+            OutputFormat::Yaml => serde_yaml::to_string(report).unwrap(),
+            OutputFormat::Chat => format!("```\n{report}```\n"),
+            OutputFormat::Accessible => report.to_string(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn plain_report() {
+        let input = include_str!("../files/puzzles/2023-11-16.txt");
+        let typeshift = Typeshift::new(input);
+        let report = SolutionReport::new(&typeshift, false);
+
+        let expected = "\
+possible words: 31
+steps: 6
+solution: above basic study wheel whups
+";
+        assert_eq!(report.to_string(), expected);
+    }
+}
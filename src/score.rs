@@ -0,0 +1,129 @@
+//! Predicting the in-game score of a line of play
+
+use alloc::vec::Vec;
+
+use crate::error::PlayError;
+use crate::game::GameState;
+use crate::Typeshift;
+
+/// Points awarded by the game. Puzzmo doesn't publish its scoring, so the defaults
+/// estimate it, and each rule can be adjusted.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ScoringRules {
+    /// Points for each letter of a played word
+    pub letter_points: u32,
+
+    /// Extra points for each letter a word is the first to cover
+    pub new_letter_points: u32,
+
+    /// Points for covering every letter of the puzzle
+    pub completion_bonus: u32,
+
+    /// Points for a complete game that uses as few words as possible
+    pub efficiency_bonus: u32,
+
+    /// How much less the efficiency bonus is for each word beyond the fewest possible
+    pub extra_word_penalty: u32,
+}
+
+impl Default for ScoringRules {
+    fn default() -> Self {
+        Self {
+            letter_points: 10,
+            new_letter_points: 10,
+            completion_bonus: 500,
+            efficiency_bonus: 500,
+            extra_word_penalty: 100,
+        }
+    }
+}
+
+/// The points a line of play earns, by rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Score {
+    /// The points for each played word, in the order they were played
+    pub word_points: Vec<u32>,
+
+    /// The completion bonus, if every letter was covered
+    pub completion_bonus: u32,
+
+    /// The efficiency bonus, if every letter was covered
+    pub efficiency_bonus: u32,
+
+    /// The sum of the points above
+    pub total: u32,
+}
+
+impl ScoringRules {
+    /// Scores playing the words in order, given the fewest words that cover every letter.
+    /// Fails on the first word that can't be played, as `GameState::play` would.
+    pub fn score<'a>(
+        &self,
+        typeshift: &Typeshift,
+        words: impl IntoIterator<Item = &'a str>,
+        minimal_words: usize,
+    ) -> Result<Score, PlayError> {
+        let mut game = GameState::new(typeshift);
+        let mut word_points = Vec::new();
+        for word in words {
+            let newly_covered = game.play(word)? as u32;
+            let letters = word.chars().count() as u32;
+            word_points.push(letters * self.letter_points + newly_covered * self.new_letter_points);
+        }
+
+        let (completion_bonus, efficiency_bonus) = if game.is_solved() {
+            let extra_words = game.played().len().saturating_sub(minimal_words) as u32;
+            let penalty = extra_words.saturating_mul(self.extra_word_penalty);
+            let efficiency_bonus = self.efficiency_bonus.saturating_sub(penalty);
+            (self.completion_bonus, efficiency_bonus)
+        } else {
+            (0, 0)
+        };
+
+        let total = word_points.iter().sum::<u32>() + completion_bonus + efficiency_bonus;
+        Ok(Score {
+            word_points,
+            completion_bonus,
+            efficiency_bonus,
+            total,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn scoring() {
+        let typeshift = Typeshift::new(include_str!("../files/puzzles/2023-11-16.txt"));
+        let rules = ScoringRules::default();
+
+        let minimal = ["above", "basic", "study", "wheel", "whups"];
+        assert_eq!(
+            rules.score(&typeshift, minimal, 5).unwrap(),
+            Score {
+                word_points: vec![100, 100, 100, 100, 70],
+                completion_bonus: 500,
+                efficiency_bonus: 500,
+                total: 1470,
+            }
+        );
+
+        // an extra word earns its own points, but costs more of the efficiency bonus
+        let longer = ["above", "stoic", "basic", "study", "wheel", "whups"];
+        let score = rules.score(&typeshift, longer, 5).unwrap();
+        assert_eq!(score.word_points, [100, 90, 80, 80, 100, 70]);
+        assert_eq!((score.efficiency_bonus, score.total), (400, 1420));
+
+        let incomplete = rules.score(&typeshift, ["above"], 5).unwrap();
+        assert_eq!((incomplete.completion_bonus, incomplete.total), (0, 100));
+        assert_eq!(
+            rules.score(&typeshift, ["above", "above"], 5),
+            Err(PlayError::AlreadyPlayed("above".into()))
+        );
+    }
+}
@@ -0,0 +1,136 @@
+//! Printable puzzle sheets, for playing generated puzzles on paper
+//!
+//! A sheet has the upright grid, one blank answer line per word of a minimal solution,
+//! and the answers, either upside down at the bottom or on a page of their own.
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::grid::render_grid;
+
+/// Where a sheet's answers go
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+pub enum SheetAnswers {
+    /// Upside down at the bottom of the sheet, so they can't be read at a glance
+    #[default]
+    UpsideDown,
+    /// The right way up, after a page break
+    Appendix,
+}
+
+/// Renders a puzzle as a plain text sheet to print, with answer lines for a solution's
+/// words, and the solution placed as `answers` says
+pub fn render_sheet(columns: &[String], solution: &[String], answers: SheetAnswers) -> String {
+    let grid = render_grid(columns, &[], false).to_uppercase();
+    let answer_line = blanks(columns.len());
+
+    let mut out = String::from("TYPESHIFT\n\n");
+    out.push_str(&grid);
+    out.push_str(&format!(
+        "\nFind {} words that use every letter:\n\n",
+        solution.len()
+    ));
+    for number in 1..=solution.len() {
+        out.push_str(&format!("{number}. {answer_line}\n\n"));
+    }
+
+    let solution = solution.join(" ");
+    match answers {
+        SheetAnswers::UpsideDown => {
+            out.push_str(&format!("\nAnswers: {}\n", upside_down(&solution)));
+        }
+        // a form feed starts a new page when printed
+        SheetAnswers::Appendix => out.push_str(&format!("\x0cAnswers: {solution}\n")),
+    }
+
+    out
+}
+
+/// One blank per letter of a word
+fn blanks(letters: usize) -> String {
+    Vec::from_iter((0..letters).map(|_| "_")).join(" ")
+}
+
+/// Turns text upside down: reversed, with each letter swapped for one that looks like it
+/// rotated half a turn
+fn upside_down(text: &str) -> String {
+    text.chars()
+        .rev()
+        .map(|ch| match ch {
+            'a' => 'ɐ',
+            'b' => 'q',
+            'c' => 'ɔ',
+            'd' => 'p',
+            'e' => 'ǝ',
+            'f' => 'ɟ',
+            'g' => 'ƃ',
+            'h' => 'ɥ',
+            'i' => 'ᴉ',
+            'j' => 'ɾ',
+            'k' => 'ʞ',
+            'm' => 'ɯ',
+            'n' => 'u',
+            'p' => 'd',
+            'q' => 'b',
+            'r' => 'ɹ',
+            't' => 'ʇ',
+            'u' => 'n',
+            'v' => 'ʌ',
+            'w' => 'ʍ',
+            'y' => 'ʎ',
+            // l, o, s, x, z, and spaces look the same
+            other => other,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn printable_sheet() {
+        let columns: Vec<String> = ["wsab", "hbta", "oesu", "dpive", "lceys"]
+            .map(String::from)
+            .to_vec();
+        let solution: Vec<String> = ["above", "basic", "study", "wheel", "whups"]
+            .map(String::from)
+            .to_vec();
+
+        let expected = "\
+TYPESHIFT
+
+W H O D L
+S B E P C
+A T S I E
+B A U V Y
+      E S
+
+Find 5 words that use every letter:
+
+1. _ _ _ _ _
+
+2. _ _ _ _ _
+
+3. _ _ _ _ _
+
+4. _ _ _ _ _
+
+5. _ _ _ _ _
+
+
+Answers: sdnɥʍ lǝǝɥʍ ʎpnʇs ɔᴉsɐq ǝʌoqɐ
+";
+        assert_eq!(
+            render_sheet(&columns, &solution, SheetAnswers::UpsideDown),
+            expected
+        );
+
+        let appendix = render_sheet(&columns, &solution, SheetAnswers::Appendix);
+        assert!(appendix.ends_with("_ _\n\n\x0cAnswers: above basic study wheel whups\n"));
+    }
+}
@@ -0,0 +1,275 @@
+//! proptest strategies for generating puzzles, for property tests here and downstream,
+//! an exhaustive reference solver to check the optimized one against,
+//! and a guard against changes that make the search take more steps
+
+use std::collections::BTreeSet;
+use std::fmt::Write;
+use std::ops::RangeInclusive;
+
+use proptest::prelude::*;
+use proptest::sample::subsequence;
+
+use crate::archive::PuzzleArchive;
+use crate::typeshift::SolutionCount;
+use crate::{dictionary, generate, Typeshift};
+
+/// A generated puzzle, with the words it was built from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SolvablePuzzle {
+    /// The puzzle columns, leftmost first, each in alphabetical order
+    pub columns: Vec<String>,
+
+    /// The dictionary words whose letters make up the columns;
+    /// together they're a solution, though not necessarily a minimal one
+    pub words: Vec<&'static str>,
+}
+
+impl SolvablePuzzle {
+    /// Rotates words of the same length into columns
+    pub fn from_words(words: Vec<&'static str>) -> Self {
+        let columns = generate::columns_for(&words);
+
+        Self { columns, words }
+    }
+
+    pub fn typeshift(&self) -> Typeshift {
+        Typeshift::from_columns(self.columns.clone())
+    }
+}
+
+/// Puzzles of 2 to 5 dictionary words, each 4 to 6 letters long
+pub fn solvable_puzzle() -> impl Strategy<Value = SolvablePuzzle> {
+    solvable_puzzle_with(4..=6, 2..=5)
+}
+
+/// Puzzles built from a number of dictionary words in `word_counts`,
+/// with a length in `word_lengths`.
+///
+/// Panics when generating if the dictionary has fewer words of a length than required.
+pub fn solvable_puzzle_with(
+    word_lengths: RangeInclusive<usize>,
+    word_counts: RangeInclusive<usize>,
+) -> impl Strategy<Value = SolvablePuzzle> {
+    word_lengths
+        .prop_flat_map(move |length| {
+            let words: Vec<&'static str> = dictionary::words()
+                .iter()
+                .copied()
+                .filter(|word| word.len() == length)
+                .collect();
+
+            subsequence(words, word_counts.clone())
+        })
+        .prop_map(SolvablePuzzle::from_words)
+}
+
+/// Every minimal solution, found by trying every set of one word, then every set of two,
+/// and so on until some set uses every letter; empty for an unsolvable puzzle.
+/// With no heuristics it's slow, so it's only for small puzzles, of a few dozen words.
+pub fn reference_solutions(typeshift: &Typeshift) -> BTreeSet<BTreeSet<&'static str>> {
+    let words = typeshift.words();
+
+    let mut solutions = BTreeSet::new();
+    for size in 1..=words.len() {
+        let mut chosen = Vec::with_capacity(size);
+        for_each_combination(words.len(), size, &mut chosen, &mut |chosen| {
+            let chosen = chosen.iter().map(|&word| words[word]);
+            if uses_every_letter(typeshift.input_columns(), chosen.clone()) {
+                solutions.insert(chosen.collect());
+            }
+        });
+        if !solutions.is_empty() {
+            break;
+        }
+    }
+
+    solutions
+}
+
+/// Whether the words together use every letter of the puzzle's columns
+fn uses_every_letter<'a>(columns: &[String], words: impl Iterator<Item = &'a str> + Clone) -> bool {
+    columns.iter().enumerate().all(|(column, letters)| {
+        letters
+            .chars()
+            .all(|letter| (words.clone()).any(|word| word.as_bytes()[column] == letter as u8))
+    })
+}
+
+/// Calls `visit` with every increasing sequence of `size` indices below `len`
+fn for_each_combination(
+    len: usize,
+    size: usize,
+    chosen: &mut Vec<usize>,
+    visit: &mut impl FnMut(&[usize]),
+) {
+    if chosen.len() == size {
+        visit(chosen);
+        return;
+    }
+
+    let next = chosen.last().map_or(0, |last| last + 1);
+    // leaving room for the rest of the indices
+    for index in next..=len - (size - chosen.len()) {
+        chosen.push(index);
+        for_each_combination(len, size, chosen, visit);
+        chosen.pop();
+    }
+}
+
+/// The optimized solver's answers for a puzzle beside the reference solver's,
+/// for differential tests
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReferenceComparison {
+    /// The puzzle's columns, for checking that solutions use every letter
+    pub columns: Vec<String>,
+
+    /// Every minimal solution, from `reference_solutions`
+    pub reference: BTreeSet<BTreeSet<&'static str>>,
+
+    /// The best-first search's first solution
+    pub first: BTreeSet<&'static str>,
+
+    /// The minimal solutions `Typeshift::find_all_solutions` lists
+    pub listed: BTreeSet<BTreeSet<&'static str>>,
+
+    /// The exact count from `Typeshift::count_solutions`
+    pub counted: SolutionCount,
+}
+
+impl ReferenceComparison {
+    /// Solves a solvable puzzle every way; panics if any solver fails
+    pub fn new(typeshift: &Typeshift) -> Self {
+        Self {
+            columns: typeshift.input_columns().to_vec(),
+            reference: reference_solutions(typeshift),
+            first: typeshift.find_first_solution().0,
+            listed: typeshift.find_all_solutions().0,
+            counted: typeshift.count_solutions(&Default::default()).unwrap(),
+        }
+    }
+
+    /// The minimal solutions the listing missed, from the trimming in the search's
+    /// choice of next words
+    pub fn missed(&self) -> BTreeSet<&BTreeSet<&'static str>> {
+        self.reference.difference(&self.listed).collect()
+    }
+
+    /// Whether the first solution has more words than a minimal one,
+    /// which the same trimming can cause
+    pub fn first_is_longer(&self) -> bool {
+        self.reference
+            .first()
+            .is_some_and(|minimal| self.first.len() > minimal.len())
+    }
+
+    /// The disagreements no trimming explains: a first or listed solution that leaves
+    /// letters unused, a listed solution shorter than minimal, or a wrong exact count
+    pub fn errors(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        let minimal_words = self.reference.first().map_or(0, BTreeSet::len);
+        let solves = |solution: &BTreeSet<&'static str>| {
+            uses_every_letter(&self.columns, solution.iter().copied())
+        };
+
+        if !solves(&self.first) {
+            errors.push(format!(
+                "first solution {:?} leaves letters unused",
+                self.first
+            ));
+        }
+        for solution in &self.listed {
+            if !solves(solution) {
+                errors.push(format!("listed {solution:?}, which leaves letters unused"));
+            } else if solution.len() < minimal_words {
+                errors.push(format!(
+                    "listed {solution:?}, which is shorter than minimal"
+                ));
+            }
+        }
+        let reference_count = SolutionCount {
+            minimal_words,
+            solutions: self.reference.len() as u64,
+        };
+        if self.counted != reference_count {
+            errors.push(format!(
+                "counted {:?}, but there are {reference_count:?}",
+                self.counted
+            ));
+        }
+
+        errors
+    }
+}
+
+/// The steps each embedded archived puzzle's first solution took when last recorded,
+/// by date; update these when a change to the search is meant to change them
+pub const RECORDED_STEPS: [(&str, usize); 17] = [
+    ("2023-11-16", 6),
+    ("2023-11-17", 9),
+    ("2023-11-18", 10),
+    ("2023-11-19", 61),
+    ("2023-11-20", 6),
+    ("2023-11-21", 14),
+    ("2023-11-22", 17),
+    ("2023-11-23", 53),
+    ("2023-11-24", 8),
+    ("2023-11-25", 46),
+    ("2023-11-26", 7),
+    ("2023-11-27", 15),
+    ("2023-11-28", 34),
+    ("2023-11-29", 23),
+    ("2023-11-30", 62),
+    ("2023-12-01", 12),
+    ("2023-12-05", 10),
+];
+
+/// How far above the recorded steps searches may go before `assert_step_counts` fails
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct StepTolerance {
+    /// The most times its recorded steps any one puzzle may take
+    pub per_puzzle: f64,
+
+    /// The most times the recorded total all the puzzles together may take
+    pub total: f64,
+}
+
+impl Default for StepTolerance {
+    /// Loose enough for a puzzle to land on a different tie, but not for the search
+    /// to blow up
+    fn default() -> Self {
+        Self {
+            per_puzzle: 2.0,
+            total: 1.25,
+        }
+    }
+}
+
+/// Solves every embedded archived puzzle, and panics listing the puzzles over their
+/// recorded steps by more than the tolerance, and the total if it is,
+/// or any puzzle without a recorded count
+pub fn assert_step_counts(recorded: &[(&str, usize)], tolerance: StepTolerance) {
+    let mut failures = String::new();
+    let (mut total, mut recorded_total) = (0, 0);
+    for puzzle in PuzzleArchive::embedded().iter() {
+        let date = puzzle.date.to_string();
+        let Some(&(_, expected)) = recorded.iter().find(|(recorded, _)| *recorded == date) else {
+            writeln!(failures, "{date}: no recorded steps").unwrap();
+            continue;
+        };
+
+        let (_solution, steps) = Typeshift::new(&puzzle.input).find_first_solution();
+        if steps as f64 > expected as f64 * tolerance.per_puzzle {
+            writeln!(failures, "{date}: {steps} steps, recorded {expected}").unwrap();
+        }
+        total += steps;
+        recorded_total += expected;
+    }
+
+    if total as f64 > recorded_total as f64 * tolerance.total {
+        writeln!(failures, "total: {total} steps, recorded {recorded_total}").unwrap();
+    }
+    assert!(
+        failures.is_empty(),
+        "searches took too many steps:\n{failures}"
+    );
+}
@@ -0,0 +1,447 @@
+//! Recording the solver's search, for debugging the ranking heuristics
+//!
+//! A trace file starts with a magic header and the puzzle columns,
+//! followed by one record per event. Words are stored as indexes into the puzzle's
+//! reduced dictionary, so reading a trace rebuilds the puzzle from its columns.
+
+use alloc::collections::BTreeSet;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+#[cfg(feature = "std")]
+use std::io::{self, Read, Write};
+
+use crate::Typeshift;
+
+#[cfg(feature = "std")]
+const MAGIC: &[u8; 8] = b"TSTRACE1";
+
+/// Receives events from the search as it runs.
+/// Every method does nothing by default; `()` ignores everything.
+pub trait SearchObserver {
+    /// A partial solution was taken off the frontier to be expanded
+    fn popped(&mut self, _step: usize, _words: &BTreeSet<&'static str>, _rank: NodeRank) {}
+
+    /// The popped partial solution ranked `candidates` next words,
+    /// keeping the `kept` best and dropping the rest as outranked
+    fn expanded(&mut self, _candidates: usize, _kept: usize) {}
+
+    /// A child partial solution was added to the frontier
+    fn pushed(&mut self, _words: &BTreeSet<&'static str>, _rank: NodeRank) {}
+
+    /// A child partial solution was dropped, because the same set of words was already queued
+    fn skipped(&mut self, _words: &BTreeSet<&'static str>) {}
+}
+
+impl SearchObserver for () {}
+
+/// An absent observer ignores everything
+impl<O: SearchObserver> SearchObserver for Option<O> {
+    fn popped(&mut self, step: usize, words: &BTreeSet<&'static str>, rank: NodeRank) {
+        if let Some(observer) = self {
+            observer.popped(step, words, rank);
+        }
+    }
+
+    fn expanded(&mut self, candidates: usize, kept: usize) {
+        if let Some(observer) = self {
+            observer.expanded(candidates, kept);
+        }
+    }
+
+    fn pushed(&mut self, words: &BTreeSet<&'static str>, rank: NodeRank) {
+        if let Some(observer) = self {
+            observer.pushed(words, rank);
+        }
+    }
+
+    fn skipped(&mut self, words: &BTreeSet<&'static str>) {
+        if let Some(observer) = self {
+            observer.skipped(words);
+        }
+    }
+}
+
+/// A pair of observers both receive every event
+impl<A: SearchObserver, B: SearchObserver> SearchObserver for (A, B) {
+    fn popped(&mut self, step: usize, words: &BTreeSet<&'static str>, rank: NodeRank) {
+        self.0.popped(step, words, rank);
+        self.1.popped(step, words, rank);
+    }
+
+    fn expanded(&mut self, candidates: usize, kept: usize) {
+        self.0.expanded(candidates, kept);
+        self.1.expanded(candidates, kept);
+    }
+
+    fn pushed(&mut self, words: &BTreeSet<&'static str>, rank: NodeRank) {
+        self.0.pushed(words, rank);
+        self.1.pushed(words, rank);
+    }
+
+    fn skipped(&mut self, words: &BTreeSet<&'static str>) {
+        self.0.skipped(words);
+        self.1.skipped(words);
+    }
+}
+
+/// The priority of a partial solution in the search; see `RankedSolution`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct NodeRank {
+    /// Every letter is used
+    pub solved: bool,
+
+    /// The number of letters used more than once
+    pub overlaps: usize,
+
+    /// The number of words in the partial solution
+    pub words: usize,
+}
+
+impl fmt::Display for NodeRank {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let solved = if self.solved { "yes" } else { "no" };
+        write!(
+            f,
+            "solved: {solved}, overlaps: {}, words: {}",
+            self.overlaps, self.words
+        )
+    }
+}
+
+/// One recorded search event
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "event", rename_all = "snake_case"))]
+pub enum TraceEvent {
+    Popped {
+        step: usize,
+        words: Vec<&'static str>,
+        rank: NodeRank,
+    },
+    Pushed {
+        words: Vec<&'static str>,
+        rank: NodeRank,
+    },
+    Skipped {
+        words: Vec<&'static str>,
+    },
+}
+
+impl fmt::Display for TraceEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let show = |words: &[&str]| {
+            if words.is_empty() {
+                "(empty)".to_string()
+            } else {
+                words.join(" ")
+            }
+        };
+
+        match self {
+            TraceEvent::Popped { step, words, rank } => {
+                write!(f, "step {step}: expand {} ({rank})", show(words))
+            }
+            TraceEvent::Pushed { words, rank } => {
+                write!(f, "  push {} ({rank})", show(words))
+            }
+            TraceEvent::Skipped { words } => {
+                write!(f, "  skip {} (already queued)", show(words))
+            }
+        }
+    }
+}
+
+/// A recorded search over one puzzle; record one by passing it to `Typeshift::solve_observed`
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Trace {
+    /// The puzzle input columns, leftmost first
+    pub columns: Vec<String>,
+
+    /// The events in the order they happened
+    pub events: Vec<TraceEvent>,
+
+    /// The puzzle's reduced dictionary, for encoding words as indexes
+    #[cfg_attr(feature = "serde", serde(skip))]
+    words: Vec<&'static str>,
+}
+
+impl Trace {
+    /// An empty trace for a search over this puzzle
+    pub fn new(typeshift: &Typeshift) -> Self {
+        Self {
+            columns: typeshift.input_columns().to_vec(),
+            events: Vec::new(),
+            words: typeshift.words().to_vec(),
+        }
+    }
+
+    /// Writes the trace in its binary format
+    #[cfg(feature = "std")]
+    pub fn write_to(&self, mut w: impl Write) -> io::Result<()> {
+        w.write_all(MAGIC)?;
+
+        write_varint(&mut w, self.columns.len())?;
+        for column in &self.columns {
+            write_varint(&mut w, column.len())?;
+            w.write_all(column.as_bytes())?;
+        }
+
+        for event in &self.events {
+            match event {
+                TraceEvent::Popped { step, words, rank } => {
+                    w.write_all(&[0])?;
+                    write_varint(&mut w, *step)?;
+                    write_rank(&mut w, rank)?;
+                    self.write_words(&mut w, words)?;
+                }
+                TraceEvent::Pushed { words, rank } => {
+                    w.write_all(&[1])?;
+                    write_rank(&mut w, rank)?;
+                    self.write_words(&mut w, words)?;
+                }
+                TraceEvent::Skipped { words } => {
+                    w.write_all(&[2])?;
+                    self.write_words(&mut w, words)?;
+                }
+            }
+        }
+
+        w.flush()
+    }
+
+    /// Reads a trace written by `write_to`
+    #[cfg(feature = "std")]
+    pub fn read_from(mut r: impl Read) -> io::Result<Self> {
+        let mut bytes = Vec::new();
+        r.read_to_end(&mut bytes)?;
+        let mut input = bytes.as_slice();
+
+        if input.get(..MAGIC.len()) != Some(MAGIC.as_slice()) {
+            return Err(invalid("not a trace file"));
+        }
+        input = &input[MAGIC.len()..];
+
+        let column_count = read_varint(&mut input)?;
+        let mut columns = Vec::with_capacity(column_count);
+        for _ in 0..column_count {
+            let len = read_varint(&mut input)?;
+            let column = take(&mut input, len)?;
+            let column = String::from_utf8(column.to_vec()).map_err(|_| invalid("bad column"))?;
+            columns.push(column);
+        }
+
+        let typeshift = Typeshift::parse(&columns.join("\n"))
+            .map_err(|e| invalid(&format!("bad puzzle: {e}")))?;
+        let mut trace = Trace::new(&typeshift);
+
+        while let Some((&tag, rest)) = input.split_first() {
+            input = rest;
+            let event = match tag {
+                0 => {
+                    let step = read_varint(&mut input)?;
+                    let (solved, overlaps) = read_rank(&mut input)?;
+                    let words = trace.read_words(&mut input)?;
+                    let rank = NodeRank {
+                        solved,
+                        overlaps,
+                        words: words.len(),
+                    };
+                    TraceEvent::Popped { step, words, rank }
+                }
+                1 => {
+                    let (solved, overlaps) = read_rank(&mut input)?;
+                    let words = trace.read_words(&mut input)?;
+                    let rank = NodeRank {
+                        solved,
+                        overlaps,
+                        words: words.len(),
+                    };
+                    TraceEvent::Pushed { words, rank }
+                }
+                2 => {
+                    let words = trace.read_words(&mut input)?;
+                    TraceEvent::Skipped { words }
+                }
+                _ => return Err(invalid("unknown event")),
+            };
+            trace.events.push(event);
+        }
+
+        Ok(trace)
+    }
+
+    #[cfg(feature = "std")]
+    fn write_words(&self, w: &mut impl Write, words: &[&'static str]) -> io::Result<()> {
+        write_varint(w, words.len())?;
+        for word in words {
+            // the reduced dictionary keeps the sorted order of the full one
+            let index = self
+                .words
+                .binary_search(word)
+                .map_err(|_| invalid("word is not in the puzzle"))?;
+            write_varint(w, index)?;
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "std")]
+    fn read_words(&self, input: &mut &[u8]) -> io::Result<Vec<&'static str>> {
+        let count = read_varint(input)?;
+        (0..count)
+            .map(|_| {
+                let index = read_varint(input)?;
+                self.words
+                    .get(index)
+                    .copied()
+                    .ok_or_else(|| invalid("word index out of range"))
+            })
+            .collect()
+    }
+
+    fn record(&mut self, event: TraceEvent) {
+        self.events.push(event);
+    }
+}
+
+impl SearchObserver for Trace {
+    fn popped(&mut self, step: usize, words: &BTreeSet<&'static str>, rank: NodeRank) {
+        let words = words.iter().copied().collect();
+        self.record(TraceEvent::Popped { step, words, rank });
+    }
+
+    fn pushed(&mut self, words: &BTreeSet<&'static str>, rank: NodeRank) {
+        let words = words.iter().copied().collect();
+        self.record(TraceEvent::Pushed { words, rank });
+    }
+
+    fn skipped(&mut self, words: &BTreeSet<&'static str>) {
+        let words = words.iter().copied().collect();
+        self.record(TraceEvent::Skipped { words });
+    }
+}
+
+#[cfg(feature = "std")]
+fn write_rank(w: &mut impl Write, rank: &NodeRank) -> io::Result<()> {
+    w.write_all(&[rank.solved as u8])?;
+    write_varint(w, rank.overlaps)
+}
+
+#[cfg(feature = "std")]
+fn read_rank(input: &mut &[u8]) -> io::Result<(bool, usize)> {
+    let solved = take(input, 1)?[0] != 0;
+    let overlaps = read_varint(input)?;
+
+    Ok((solved, overlaps))
+}
+
+/// LEB128: seven bits per byte, low bits first, high bit set on all but the last byte
+#[cfg(feature = "std")]
+fn write_varint(w: &mut impl Write, mut n: usize) -> io::Result<()> {
+    loop {
+        let byte = (n & 0x7f) as u8;
+        n >>= 7;
+        if n == 0 {
+            return w.write_all(&[byte]);
+        }
+        w.write_all(&[byte | 0x80])?;
+    }
+}
+
+#[cfg(feature = "std")]
+fn read_varint(input: &mut &[u8]) -> io::Result<usize> {
+    let mut n: usize = 0;
+    for shift in (0..usize::BITS).step_by(7) {
+        let byte = take(input, 1)?[0];
+        n |= usize::from(byte & 0x7f) << shift;
+        if byte & 0x80 == 0 {
+            return Ok(n);
+        }
+    }
+
+    Err(invalid("varint too long"))
+}
+
+#[cfg(feature = "std")]
+fn take<'a>(input: &mut &'a [u8], len: usize) -> io::Result<&'a [u8]> {
+    if input.len() < len {
+        return Err(invalid("unexpected end of trace"));
+    }
+    let (taken, rest) = input.split_at(len);
+    *input = rest;
+
+    Ok(taken)
+}
+
+#[cfg(feature = "std")]
+fn invalid(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    use crate::SolverConfig;
+
+    #[test]
+    fn traces_round_trip() {
+        let input = include_str!("../files/puzzles/2023-11-16.txt");
+        let typeshift = Typeshift::new(input);
+        let mut trace = Trace::new(&typeshift);
+        let (_solution, steps) = typeshift
+            .solve_observed(&SolverConfig::default(), &mut trace)
+            .unwrap();
+
+        let popped = trace
+            .events
+            .iter()
+            .filter(|e| matches!(e, TraceEvent::Popped { .. }))
+            .count();
+        assert_eq!(popped, steps);
+
+        let mut bytes = Vec::new();
+        trace.write_to(&mut bytes).unwrap();
+        let read = Trace::read_from(bytes.as_slice()).unwrap();
+        assert_eq!(read, trace);
+
+        assert!(Trace::read_from(&bytes[..bytes.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn combined_observers() {
+        /// Counts expansions, and the candidates they dropped as outranked
+        #[derive(Default)]
+        struct Expansions(usize, usize);
+
+        impl SearchObserver for Expansions {
+            fn expanded(&mut self, candidates: usize, kept: usize) {
+                self.0 += 1;
+                self.1 += candidates - kept;
+            }
+        }
+
+        let input = include_str!("../files/puzzles/2023-11-16.txt");
+        let typeshift = Typeshift::new(input);
+        let mut observer = (Trace::new(&typeshift), Some(Expansions::default()));
+        typeshift
+            .solve_observed(&SolverConfig::default(), &mut observer)
+            .unwrap();
+
+        let (trace, expansions) = observer;
+        let Expansions(expanded, outranked) = expansions.unwrap();
+        let popped = trace
+            .events
+            .iter()
+            .filter(|e| matches!(e, TraceEvent::Popped { .. }))
+            .count();
+        // every popped partial solution but the solved one is expanded
+        assert_eq!(expanded, popped - 1);
+        assert!(outranked > 0);
+    }
+}
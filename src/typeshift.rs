@@ -1,38 +1,148 @@
-use std::cmp::{Ordering, Reverse};
-use std::collections::{BTreeSet, BinaryHeap};
-use std::iter::zip;
+use alloc::collections::{BTreeSet, BinaryHeap};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use alloc::{format, vec};
+use core::cmp::{Ordering, Reverse};
+use core::iter::zip;
+use core::sync::atomic::{AtomicBool, Ordering as AtomicOrdering};
+#[cfg(feature = "std")]
+use std::time::{Duration, Instant};
 
-use crate::dict::DICT;
+use crate::cache::{CachedSolution, SolutionCache};
+use crate::dictionary;
+use crate::error::{InvalidWord, ParseError, PlayError, SolveError};
+use crate::hint::WordScore;
+use crate::puzzle;
+use crate::random::SplitMix64;
+use crate::trace::{NodeRank, SearchObserver};
 
 mod collections;
 use collections::*;
 
-/// An unsolved Typeshift puzzle
+mod count;
+
+#[cfg(feature = "serde")]
+mod prepared;
+mod stepper;
+
+pub use count::{CoverCounts, SolutionCount, SolutionCountEstimate};
+pub use stepper::{SolverStepper, StepOutcome};
+
+/// An unsolved Typeshift puzzle.
+/// Nothing changes once it's built, and it's `Send + Sync`, so one puzzle can be shared
+/// behind an `Arc` by concurrent solves and hints.
 #[derive(Debug)]
 pub struct Typeshift {
     /// The rotated or inverted puzzle input columns
     /// The first inner set is the leftmost column of the puzzle.
     columns: Vec<LetterSet>,
 
+    /// The puzzle input columns as given, for display
+    input_columns: Vec<String>,
+
     /// A dictionary of usable words, reduced to only words spellable from the input
     words: Vec<&'static str>,
 
     /// The total frequencies of characters in the reduced problem dictionary
     char_freqs: LetterCounts,
+
+    /// The fingerprint of the dictionary the words came from, if not the embedded one
+    dictionary_fingerprint: Option<u64>,
 }
 
+// keeps Typeshift shareable between threads; see its docs
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Typeshift>();
+};
+
 impl Typeshift {
     /// Returns a new filtered dictionary from a puzzle input.
     /// Includes only (and all) words that can be made with the puzzle input columns.
     /// Expects input as a rotated or inverted set of lines:
     /// The leftmost column of the puzzle should be the first line of input.
+    ///
+    /// Panics on invalid input; see `parse`.
     pub fn new(input: &str) -> Self {
-        let columns: Vec<_> = input
+        Self::parse(input).unwrap()
+    }
+
+    /// Like `new`, but returns an error for input that isn't lines of lowercase ascii letters.
+    /// Surrounding whitespace on each line, and blank lines at the end, are ignored.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        Ok(Self::from_columns(Self::parse_columns(input)?))
+    }
+
+    /// The validation half of `parse`: splits the input into columns without
+    /// filtering the dictionary
+    pub fn parse_columns(input: &str) -> Result<Vec<String>, ParseError> {
+        let input_columns: Vec<String> = input
+            .trim_end()
             .lines()
+            .map(|l| l.trim().to_string())
+            .collect();
+
+        if input_columns.is_empty() {
+            return Err(ParseError::Empty);
+        }
+        for (i, col) in input_columns.iter().enumerate() {
+            let column = i + 1;
+            if col.is_empty() {
+                return Err(ParseError::EmptyColumn { column });
+            }
+            if let Some(letter) = col.chars().find(|ch| !ch.is_ascii_lowercase()) {
+                return Err(ParseError::InvalidLetter { column, letter });
+            }
+        }
+
+        Ok(input_columns)
+    }
+
+    /// The dictionary filtering half of `parse`, for columns already validated by
+    /// `parse_columns`.
+    ///
+    /// Panics on columns with anything other than lowercase ascii letters.
+    pub fn from_columns(input_columns: Vec<String>) -> Self {
+        Self::filter(input_columns, dictionary::words(), None)
+    }
+
+    /// Like `from_columns`, but spelling words from the given dictionary instead of the
+    /// embedded one, for trying out changes to the dictionary.
+    /// The words can be in any order, and repeated; the puzzle, its solutions, and the
+    /// steps to find them are the same as for the words sorted and deduplicated.
+    ///
+    /// Solution caches keep such puzzles apart by the dictionary's fingerprint,
+    /// and they can't be serialized, since prepared puzzles refer to embedded words.
+    pub fn with_dictionary(input_columns: Vec<String>, words: &[&'static str]) -> Self {
+        let mut sorted = Vec::new();
+        let words = if words.is_sorted_by(|a, b| a < b) {
+            words
+        } else {
+            sorted.extend_from_slice(words);
+            sorted.sort_unstable();
+            sorted.dedup();
+            &sorted
+        };
+
+        let fingerprint = dictionary::fingerprint_of(words);
+        Self::filter(input_columns, words, Some(fingerprint))
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(columns = input_columns.len()))
+    )]
+    fn filter(
+        input_columns: Vec<String>,
+        dictionary_words: &[&'static str],
+        dictionary_fingerprint: Option<u64>,
+    ) -> Self {
+        let columns: Vec<_> = input_columns
+            .iter()
             .map(|l| LetterSet::from_iter(l.chars()))
             .collect();
 
-        let words: Vec<&'static str> = DICT
+        let words: Vec<&'static str> = dictionary_words
             .iter()
             .filter(|word| word.len() == columns.len())
             .filter(|word| zip(word.chars(), columns.iter()).all(|(ch, col)| col.contains(ch)))
@@ -41,10 +151,15 @@ impl Typeshift {
 
         let char_freqs = LetterCounts::from_iter(words.iter().flat_map(|word| word.chars()));
 
+        #[cfg(feature = "tracing")]
+        tracing::debug!(words = words.len(), "filtered dictionary");
+
         Self {
             columns,
+            input_columns,
             words,
             char_freqs,
+            dictionary_fingerprint,
         }
     }
 
@@ -53,34 +168,413 @@ impl Typeshift {
         self.words.len()
     }
 
-    /// Returns the first minimal solution found,
+    /// The reduced dictionary of words spellable from the puzzle columns
+    pub fn words(&self) -> &[&'static str] {
+        &self.words
+    }
+
+    /// The puzzle input columns as given, leftmost first
+    pub fn input_columns(&self) -> &[String] {
+        &self.input_columns
+    }
+
+    /// Returns true if the word is one of the puzzle's words
+    pub fn is_valid_word(&self, word: &str) -> bool {
+        self.words.binary_search(&word).is_ok()
+    }
+
+    /// Like `is_valid_word`, but explaining why not: a word of the wrong length,
+    /// a letter missing from its column (the leftmost one), or a word not in the dictionary
+    pub fn check_word(&self, word: &str) -> Result<(), InvalidWord> {
+        let found = word.chars().count();
+        if found != self.input_columns.len() {
+            let expected = self.input_columns.len();
+            return Err(InvalidWord::WrongLength { expected, found });
+        }
+
+        let missing = zip(&self.input_columns, word.chars())
+            .position(|(letters, letter)| !letters.contains(letter));
+        if let Some(column) = missing {
+            let letter = word.chars().nth(column).unwrap();
+            return Err(InvalidWord::NotInColumn {
+                column: column + 1,
+                letter,
+            });
+        }
+
+        if !self.is_valid_word(word) {
+            return Err(InvalidWord::NotInDictionary);
+        }
+
+        Ok(())
+    }
+
+    /// Returns the first solution the best-first search finds,
     /// and the number of intermediate partial solutions touched along the way.
+    /// It's usually minimal, but not always: the search only tries the words tied for best
+    /// at each step, and can trim away every minimal solution; `count_solutions` has the
+    /// exact minimal size.
     pub fn find_first_solution(&self) -> (BTreeSet<&'static str>, usize) {
-        let (mut solutions, steps) = self.solve(SolveMode::FindFirst);
-        (solutions.pop_first().unwrap(), steps)
+        self.solve_with(&SolverConfig::default()).unwrap()
+    }
+
+    /// Returns the first solution found using the given solver configuration,
+    /// and the number of intermediate partial solutions touched along the way.
+    /// Neither strategy guarantees a minimal solution; see `find_first_solution`.
+    /// Checks the configuration's cache before searching, and fills it after,
+    /// unless the configuration has ranking weights or a tie seed.
+    pub fn solve_with(
+        &self,
+        config: &SolverConfig,
+    ) -> Result<(BTreeSet<&'static str>, usize), SolveError> {
+        self.solve_cached(config, || self.solve_observed(config, &mut ()))
+    }
+
+    /// Identifies the puzzle across formats and archives; see `puzzle::canonical_hash`
+    pub fn canonical_hash(&self) -> u64 {
+        puzzle::canonical_hash(self.input_columns.iter().map(String::as_str))
+    }
+
+    /// Identifies this puzzle's solutions with a strategy and the built-in dictionary
+    pub fn cache_key(&self, strategy: Strategy) -> String {
+        let strategy = match strategy {
+            Strategy::BestFirst => "best-first",
+            Strategy::Greedy => "greedy",
+        };
+
+        format!(
+            "{:016x}-{:016x}-{strategy}",
+            self.canonical_hash(),
+            self.dictionary_fingerprint
+                .unwrap_or_else(dictionary::fingerprint)
+        )
+    }
+
+    /// Like `solve_with`, but reporting each step of the search to an observer,
+    /// such as a `trace::Trace`
+    pub fn solve_observed(
+        &self,
+        config: &SolverConfig,
+        observer: &mut impl SearchObserver,
+    ) -> Result<(BTreeSet<&'static str>, usize), SolveError> {
+        let limits = Limits::new(config, None);
+        self.search(&limits, config.strategy, Ranking::of(config), observer)
+    }
+
+    /// Like `solve_with`, but giving up with `SolveError::Cancelled` once `cancel` is set,
+    /// for searches running on another thread
+    pub fn solve_cancellable(
+        &self,
+        config: &SolverConfig,
+        cancel: &AtomicBool,
+    ) -> Result<(BTreeSet<&'static str>, usize), SolveError> {
+        self.solve_cached(config, || {
+            let limits = Limits::new(config, Some(cancel));
+            self.search(&limits, config.strategy, Ranking::of(config), &mut ())
+        })
+    }
+
+    /// Returns the fewest words that finish a game with the played words,
+    /// not counting the played words themselves; see `complete_with`
+    pub fn complete(&self, played: &[&str]) -> Result<BTreeSet<&'static str>, PlayError> {
+        let (words, _steps) = self.complete_with(played, &SolverConfig::default())?;
+        Ok(words)
+    }
+
+    /// Like `complete`, but respecting the configuration's limits,
+    /// and also returning the number of partial solutions touched.
+    /// The search starts from the played words, as if they were its first choices;
+    /// the strategy is ignored, and finishing a game is always best-first.
+    pub fn complete_with(
+        &self,
+        played: &[&str],
+        config: &SolverConfig,
+    ) -> Result<(BTreeSet<&'static str>, usize), PlayError> {
+        let start = self.played_solution(played)?;
+        let played = start.used_words.clone();
+        let limits = Limits::new(config, None);
+        let (mut solutions, steps) = self.solve(start, SolveMode::FindFirst, &limits, &mut ())?;
+        let mut words = solutions.pop_first().unwrap();
+        words.retain(|word| !played.contains(word));
+
+        Ok((words, steps))
+    }
+
+    /// The words not played yet, in the order the search ranks them as the next word,
+    /// best first, for bots and trainers that follow the solver's ordering;
+    /// `score_words` also says why each word ranks where it does
+    pub fn ranked_words(&self, played: &[&str]) -> Result<Vec<&'static str>, PlayError> {
+        let partial_solution = self.played_solution(played)?;
+        let ranked_words = (partial_solution.rank_words().into_iter())
+            .map(|(word, _rank)| word)
+            .filter(|word| !partial_solution.used_words.contains(word))
+            .collect();
+
+        Ok(ranked_words)
+    }
+
+    /// Scores every word not played yet as the next word, the way the search ranks them,
+    /// best first; the words tied for best are the ones the search would try next
+    pub fn score_words(&self, played: &[&str]) -> Result<Vec<WordScore>, PlayError> {
+        let partial_solution = self.played_solution(played)?;
+        let mut ranked_words = partial_solution.rank_words();
+        ranked_words.retain(|(word, _rank)| !partial_solution.used_words.contains(word));
+        let best_rank = ranked_words.first().map(|(_word, rank)| *rank);
+
+        let scores = (ranked_words.into_iter())
+            .map(|(word, rank)| {
+                let mut next = partial_solution.clone();
+                next.add_word(word);
+                WordScore {
+                    word,
+                    new_letters: partial_solution.new_letters(word),
+                    min_char_freq: partial_solution.min_char_freq(word),
+                    overlaps: next.overlaps(),
+                    best: Some(rank) == best_rank,
+                }
+            })
+            .collect();
+
+        Ok(scores)
+    }
+
+    /// A partial solution of the played words, checking that each is in the puzzle once
+    fn played_solution(&self, played: &[&str]) -> Result<PartialSolution<'_>, PlayError> {
+        let mut partial_solution = PartialSolution::empty(self);
+        for &word in played {
+            let Ok(index) = self.words.binary_search(&word) else {
+                return Err(PlayError::NotInPuzzle(word.to_string()));
+            };
+            if partial_solution.used_words.contains(word) {
+                return Err(PlayError::AlreadyPlayed(word.to_string()));
+            }
+            partial_solution.add_word(self.words[index]);
+        }
+
+        Ok(partial_solution)
+    }
+
+    /// Returns the fewest words that use every letter but the given (column, letter) cells,
+    /// for players who can see which letters are covered but not which words covered them;
+    /// see `complete_cells_with`
+    pub fn complete_cells(
+        &self,
+        covered: &[(usize, char)],
+    ) -> Result<BTreeSet<&'static str>, SolveError> {
+        let (words, _steps) = self.complete_cells_with(covered, &SolverConfig::default())?;
+        Ok(words)
+    }
+
+    /// Like `complete_cells`, but respecting the configuration's limits,
+    /// and also returning the number of partial solutions touched.
+    /// Columns count from zero, and cells that aren't in the puzzle are ignored.
+    /// Like `complete_with`, the search is always best-first.
+    pub fn complete_cells_with(
+        &self,
+        covered: &[(usize, char)],
+        config: &SolverConfig,
+    ) -> Result<(BTreeSet<&'static str>, usize), SolveError> {
+        let mut start = PartialSolution::empty(self);
+        for &(column, letter) in covered {
+            let in_puzzle =
+                (self.columns.get(column)).is_some_and(|letters| letters.contains(letter));
+            if in_puzzle && start.char_usages[column].get(letter) == 0 {
+                start.char_usages[column].add(letter);
+            }
+        }
+
+        let limits = Limits::new(config, None);
+        let (mut solutions, steps) = self.solve(start, SolveMode::FindFirst, &limits, &mut ())?;
+        Ok((solutions.pop_first().unwrap(), steps))
     }
 
-    /// Returns the set of all minimal solutions,
+    /// Returns the smallest solutions the best-first search finds,
     /// and the number of intermediate partial solutions touched along the way.
+    /// Like `find_first_solution`, the search trims words, so it can miss minimal
+    /// solutions, or find only longer ones; `for_each_cover` lists them exactly.
     pub fn find_all_solutions(&self) -> (BTreeSet<BTreeSet<&'static str>>, usize) {
-        self.solve(SolveMode::FindAll)
+        self.solve_all_with(&SolverConfig::default()).unwrap()
+    }
+
+    /// Like `find_all_solutions`, but respecting the configuration's limits.
+    /// The search strategy is ignored; finding all solutions is always best-first.
+    pub fn solve_all_with(
+        &self,
+        config: &SolverConfig,
+    ) -> Result<(BTreeSet<BTreeSet<&'static str>>, usize), SolveError> {
+        let limits = Limits::new(config, None);
+        self.solve(
+            PartialSolution::empty(self),
+            SolveMode::FindAll,
+            &limits,
+            &mut (),
+        )
+    }
+
+    /// Like `solve_all_with`, but giving up with `SolveError::Cancelled` once `cancel` is set
+    pub fn solve_all_cancellable(
+        &self,
+        config: &SolverConfig,
+        cancel: &AtomicBool,
+    ) -> Result<(BTreeSet<BTreeSet<&'static str>>, usize), SolveError> {
+        let limits = Limits::new(config, Some(cancel));
+        self.solve(
+            PartialSolution::empty(self),
+            SolveMode::FindAll,
+            &limits,
+            &mut (),
+        )
+    }
+
+    /// Estimates how hard the puzzle is to search from one greedy pass,
+    /// far faster than a best-first search
+    pub fn estimate_search(&self) -> Result<SearchEstimate, SolveError> {
+        if !self.solvable() {
+            return Err(SolveError::Unsolvable);
+        }
+
+        let mut partial_solution = PartialSolution::empty(self);
+        let mut tied = Vec::new();
+        let mut buffer = RankBuffer::default();
+        while !partial_solution.solved() {
+            // the greedy choice is the first of the words tied for best
+            let next_words = partial_solution.next_words(&mut buffer);
+            tied.push(next_words.len());
+            partial_solution.add_word(next_words[0]);
+        }
+
+        let estimated_steps = tied.iter().sum();
+        Ok(SearchEstimate {
+            greedy_words: tied.len(),
+            branching: estimated_steps as f64 / tied.len() as f64,
+            estimated_steps,
+        })
+    }
+
+    /// The number of first words tied for best, each of which a best-first search expands
+    #[cfg(feature = "std")]
+    pub(crate) fn root_branching(&self) -> usize {
+        let mut buffer = RankBuffer::default();
+        PartialSolution::empty(self).next_words(&mut buffer).len()
+    }
+
+    /// Returns true if every letter is used by at least one word,
+    /// which is all it takes for a solution to exist.
+    fn solvable(&self) -> bool {
+        let mut everything = PartialSolution::empty(self);
+        for &word in &self.words {
+            everything.add_word(word);
+        }
+
+        everything.solved()
+    }
+
+    /// Runs a search unless the configuration's cache has its solution,
+    /// adding the solution to the cache if it searched
+    fn solve_cached(
+        &self,
+        config: &SolverConfig,
+        search: impl FnOnce() -> Result<(BTreeSet<&'static str>, usize), SolveError>,
+    ) -> Result<(BTreeSet<&'static str>, usize), SolveError> {
+        // reranked searches can find other solutions, in other numbers of steps
+        let Some(cache) = config.cache.filter(|_| Ranking::of(config).is_usual()) else {
+            return search();
+        };
+
+        let key = self.cache_key(config.strategy);
+        if let Some(hit) = cache
+            .get(&key)
+            .and_then(|cached| self.check_cached(config, cached))
+        {
+            return Ok(hit);
+        }
+
+        let (solution, steps) = search()?;
+        let words = solution.iter().map(|w| w.to_string()).collect();
+        cache.put(&key, CachedSolution { words, steps });
+
+        Ok((solution, steps))
+    }
+
+    /// Checks a cached solution against this puzzle,
+    /// returning None if it's stale or the search would have hit the step limit
+    fn check_cached(
+        &self,
+        config: &SolverConfig,
+        cached: CachedSolution,
+    ) -> Option<(BTreeSet<&'static str>, usize)> {
+        if config.max_steps.is_some_and(|max| cached.steps > max) {
+            return None;
+        }
+
+        let mut solution = PartialSolution::empty(self);
+        for word in &cached.words {
+            let index = self.words.binary_search(&word.as_str()).ok()?;
+            solution.add_word(self.words[index]);
+        }
+
+        solution
+            .solved()
+            .then_some((solution.used_words, cached.steps))
+    }
+
+    fn search(
+        &self,
+        limits: &Limits,
+        strategy: Strategy,
+        ranking: Ranking,
+        observer: &mut impl SearchObserver,
+    ) -> Result<(BTreeSet<&'static str>, usize), SolveError> {
+        let start = PartialSolution::ranked_by(self, ranking);
+        match strategy {
+            Strategy::BestFirst => {
+                let (mut solutions, steps) =
+                    self.solve(start, SolveMode::FindFirst, limits, observer)?;
+                Ok((solutions.pop_first().unwrap(), steps))
+            }
+            Strategy::Greedy => self.solve_greedy(start, limits, observer),
+        }
     }
 
-    fn solve(&self, mode: SolveMode) -> (BTreeSet<BTreeSet<&'static str>>, usize) {
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, fields(?mode), err(level = "debug"))
+    )]
+    fn solve(
+        &self,
+        start: PartialSolution<'_>,
+        mode: SolveMode,
+        limits: &Limits,
+        observer: &mut impl SearchObserver,
+    ) -> Result<(BTreeSet<BTreeSet<&'static str>>, usize), SolveError> {
+        if !self.solvable() {
+            return Err(SolveError::Unsolvable);
+        }
+
         let mut steps: usize = 0;
-        let mut to_check = BinaryHeap::from_iter([RankedSolution(PartialSolution::empty(self))]);
+        let mut frontier = Frontier::new(start);
         let mut complete: BTreeSet<BTreeSet<&'static str>> = Default::default();
-        let mut attempted: BTreeSet<BTreeSet<&'static str>> = Default::default();
 
-        while let Some(RankedSolution(mut partial_solution)) = to_check.pop() {
+        while let Some(partial_solution) = frontier.pop() {
             steps += 1;
+            limits.check(steps)?;
+            #[cfg(feature = "tracing")]
+            tracing::trace!(steps, words = ?partial_solution.used_words, "popped");
+            observer.popped(
+                steps,
+                &partial_solution.used_words,
+                partial_solution.node_rank(),
+            );
 
             if partial_solution.solved() {
                 let words = partial_solution.used_words;
 
+                #[cfg(feature = "tracing")]
+                tracing::debug!(steps, ?words, "found a solution");
+
                 match mode {
                     SolveMode::FindFirst => {
-                        return (BTreeSet::from_iter([words]), steps);
+                        return Ok((BTreeSet::from_iter([words]), steps));
                     }
                     SolveMode::FindAll => {
                         complete.insert(words);
@@ -89,19 +583,7 @@ impl Typeshift {
                 }
             }
 
-            let mut next_words = partial_solution.next_words();
-            while let Some(next_word) = next_words.pop() {
-                let mut partial_solution = partial_solution.clone();
-
-                partial_solution.add_word(next_word);
-                if attempted.contains(&partial_solution.used_words) {
-                    continue;
-                }
-
-                to_check.push(RankedSolution(partial_solution));
-            }
-
-            attempted.insert(partial_solution.used_words);
+            frontier.expand(partial_solution, observer);
         }
 
         let minimum_size = complete
@@ -115,38 +597,307 @@ impl Typeshift {
             .filter(|sol| sol.len() == minimum_size)
             .collect();
 
-        (all_smallest, steps)
+        #[cfg(feature = "tracing")]
+        tracing::debug!(steps, solutions = all_smallest.len(), "found all solutions");
+
+        Ok((all_smallest, steps))
+    }
+
+    #[cfg_attr(
+        feature = "tracing",
+        tracing::instrument(level = "debug", skip_all, err(level = "debug"))
+    )]
+    fn solve_greedy(
+        &self,
+        mut partial_solution: PartialSolution<'_>,
+        limits: &Limits,
+        observer: &mut impl SearchObserver,
+    ) -> Result<(BTreeSet<&'static str>, usize), SolveError> {
+        if !self.solvable() {
+            return Err(SolveError::Unsolvable);
+        }
+
+        let mut steps: usize = 0;
+        let mut buffer = RankBuffer::default();
+
+        while !partial_solution.solved() {
+            steps += 1;
+            limits.check(steps)?;
+            #[cfg(feature = "tracing")]
+            tracing::trace!(steps, words = ?partial_solution.used_words, "popped");
+            observer.popped(
+                steps,
+                &partial_solution.used_words,
+                partial_solution.node_rank(),
+            );
+
+            let best_word = partial_solution.next_words(&mut buffer)[0];
+            observer.expanded(self.words.len(), 1);
+            partial_solution.add_word(best_word);
+            observer.pushed(&partial_solution.used_words, partial_solution.node_rank());
+        }
+
+        #[cfg(feature = "tracing")]
+        tracing::debug!(steps, words = ?partial_solution.used_words, "found a solution");
+
+        Ok((partial_solution.used_words, steps))
+    }
+}
+
+/// A quick estimate of a puzzle's search difficulty; see `Typeshift::estimate_search`
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct SearchEstimate {
+    /// The words in the greedy solution, at least as many as in a minimal solution
+    pub greedy_words: usize,
+
+    /// The mean number of next words tied for best along the greedy solution's path,
+    /// each of which a best-first search expands
+    pub branching: f64,
+
+    /// Roughly the steps a best-first search takes:
+    /// the words tied for best, summed along the greedy solution's path
+    pub estimated_steps: usize,
+}
+
+/// Options for how the solver searches
+#[derive(Default, Debug, Clone, Copy)]
+pub struct SolverConfig {
+    pub strategy: Strategy,
+
+    /// Give up after touching this many partial solutions
+    pub max_steps: Option<usize>,
+
+    /// Give up after searching for this long
+    #[cfg(feature = "std")]
+    pub timeout: Option<Duration>,
+
+    /// Reuse first solutions from this cache, and store new ones in it;
+    /// consulted by `solve_with` and `solve_cancellable`
+    pub cache: Option<&'static dyn SolutionCache>,
+
+    /// Rank with weighted sums instead of the usual order of precedence, for tuning;
+    /// only used when finding a first solution, which may then not be minimal.
+    /// Weighted solutions aren't cached.
+    pub weights: Option<RankWeights>,
+
+    /// Break ties in the rankings in a random order from this seed, instead of
+    /// alphabetically, for sampling different minimal solutions repeatably;
+    /// only used when finding a first solution. Seeded solutions aren't cached.
+    pub tie_seed: Option<u64>,
+}
+
+/// Weights for the parts of the search's rankings, replacing their order of precedence
+/// with weighted sums; see `SolverConfig::weights`. Higher sums rank better.
+///
+/// Next words score their new letters times `new_letters`, less their rarest letter's
+/// count times `min_char_freq`. Partial solutions score their words times `depth`,
+/// less their letters used more than once times `overlaps`; a finished solution
+/// still comes first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RankWeights {
+    pub new_letters: i64,
+    pub min_char_freq: i64,
+    pub overlaps: i64,
+    pub depth: i64,
+}
+
+/// How a search ranks next words and partial solutions, from a config
+#[derive(Default, Debug, Clone, Copy)]
+struct Ranking {
+    weights: Option<RankWeights>,
+    tie_seed: Option<u64>,
+}
+
+impl Ranking {
+    fn of(config: &SolverConfig) -> Self {
+        Self {
+            weights: config.weights,
+            tie_seed: config.tie_seed,
+        }
+    }
+
+    /// Whether this is the usual ranking, with its usual solutions and step counts
+    fn is_usual(&self) -> bool {
+        self.weights.is_none() && self.tie_seed.is_none()
+    }
+
+    /// Orders ties between words or sets of words by their keys, randomly for a tie seed;
+    /// without one, every key gets 0, leaving ties alphabetical
+    fn tie_break(&self, key: u64) -> u64 {
+        self.tie_seed
+            .map_or(0, |seed| SplitMix64(seed ^ key).next())
+    }
+}
+
+/// The search limits from a config, and an optional cancellation flag, checked once per step
+struct Limits<'a> {
+    max_steps: Option<usize>,
+    #[cfg(feature = "std")]
+    deadline: Option<Instant>,
+    cancel: Option<&'a AtomicBool>,
+}
+
+impl<'a> Limits<'a> {
+    fn new(config: &SolverConfig, cancel: Option<&'a AtomicBool>) -> Self {
+        Self {
+            max_steps: config.max_steps,
+            #[cfg(feature = "std")]
+            deadline: config.timeout.map(|timeout| Instant::now() + timeout),
+            cancel,
+        }
+    }
+
+    fn check(&self, steps: usize) -> Result<(), SolveError> {
+        if self
+            .cancel
+            .is_some_and(|cancel| cancel.load(AtomicOrdering::Relaxed))
+        {
+            return Err(SolveError::Cancelled { steps });
+        }
+
+        if self.max_steps.is_some_and(|max| steps > max) || self.over_time() {
+            return Err(SolveError::Timeout { steps });
+        }
+
+        Ok(())
+    }
+
+    #[cfg(feature = "std")]
+    fn over_time(&self) -> bool {
+        self.deadline
+            .is_some_and(|deadline| Instant::now() > deadline)
+    }
+
+    /// Without `std` there's no clock, so configs have no timeout
+    #[cfg(not(feature = "std"))]
+    fn over_time(&self) -> bool {
+        false
+    }
+}
+
+/// The search algorithm used to find a solution
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "cli", derive(clap::ValueEnum))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Strategy {
+    /// Best-first search over partial solutions; usually finds a minimal solution,
+    /// though trimming the words it tries at each step can lose them all
+    #[default]
+    BestFirst,
+    /// Always adds the single best-ranked word, without backtracking;
+    /// fast, but the solution may not be minimal
+    Greedy,
+}
+
+/// The keys of the sets of words a search has queued; see `PartialSolution::key`
+#[cfg(feature = "std")]
+type Attempted = std::collections::HashSet<WordSetKey>;
+
+/// Without `std` there's no seeded hasher, so the keys are kept in order instead
+#[cfg(not(feature = "std"))]
+type Attempted = BTreeSet<WordSetKey>;
+
+/// The partial solutions waiting in a best-first search, and the sets of words
+/// ever queued, so that each set is only queued (and expanded) once
+#[derive(Debug)]
+struct Frontier<'a> {
+    to_check: BinaryHeap<RankedSolution<'a>>,
+    attempted: Attempted,
+    rank_buffer: RankBuffer,
+}
+
+impl<'a> Frontier<'a> {
+    /// Starts a search from a partial solution, usually an empty one
+    fn new(start: PartialSolution<'a>) -> Self {
+        Self {
+            attempted: Attempted::from_iter([start.key.clone()]),
+            to_check: BinaryHeap::from_iter([RankedSolution(start)]),
+            rank_buffer: RankBuffer::default(),
+        }
+    }
+
+    /// Takes the best-ranked partial solution
+    fn pop(&mut self) -> Option<PartialSolution<'a>> {
+        self.to_check
+            .pop()
+            .map(|RankedSolution(partial_solution)| partial_solution)
+    }
+
+    /// Pushes each partial solution one next word away that hasn't been queued before
+    fn expand(
+        &mut self,
+        partial_solution: PartialSolution<'a>,
+        observer: &mut impl SearchObserver,
+    ) {
+        let mut next_words = partial_solution.next_words(&mut self.rank_buffer);
+        observer.expanded(partial_solution.typeshift.words.len(), next_words.len());
+        while let Some(next_word) = next_words.pop() {
+            let mut partial_solution = partial_solution.clone();
+
+            partial_solution.add_word(next_word);
+            if !self.attempted.insert(partial_solution.key.clone()) {
+                observer.skipped(&partial_solution.used_words);
+                continue;
+            }
+
+            observer.pushed(&partial_solution.used_words, partial_solution.node_rank());
+            self.to_check.push(RankedSolution(partial_solution));
+        }
     }
 }
 
-/// Whether to find the first minimal solution or all minimal solutions
+/// Whether to stop at the first solution or find every smallest one the search reaches;
+/// neither is guaranteed minimal, since `next_words` trims
 #[derive(Default, Debug, Clone, Copy)]
 enum SolveMode {
-    /// Find the first minimal solution
+    /// Stop at the first solution
     #[default]
     FindFirst,
-    /// Find all minimal solutions
+    /// Find every solution the search reaches, keeping the smallest
     FindAll,
 }
 
 /// A sortable wrapper for comparing the quality of partial solutions
+#[derive(Debug)]
 struct RankedSolution<'a>(PartialSolution<'a>);
 
 impl<'a> RankedSolution<'a> {
     /// Returns a tuple for sorting solutions by priority when solving
-    /// For use in a max-heap; higher is better
+    /// For use in a max-heap; higher is better.
+    /// Ties are broken by `cmp`, so that the order doesn't depend on when each was pushed.
     fn rank(&self) -> impl Ord + Copy {
-        (
-            self.0.solved(),            // a finished solution comes first
-            Reverse(self.0.overlaps()), // more efficient solutions rank more highly
-            self.0.used_words.len(),    // efficient solutions closer to completion rank more highly
-        )
+        let overlaps = self.0.overlaps() as i64;
+        let depth = self.0.used_words.len() as i64;
+        match self.0.ranking.weights {
+            None => (
+                self.0.solved(), // a finished solution comes first
+                -overlaps,       // more efficient solutions rank more highly
+                depth,           // efficient solutions closer to completion rank more highly
+            ),
+            Some(weights) => (
+                self.0.solved(),
+                depth * weights.depth - overlaps * weights.overlaps,
+                0,
+            ),
+        }
     }
 }
 
 impl<'a> Ord for RankedSolution<'a> {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.rank().cmp(&other.rank())
+    fn cmp(&self, other: &Self) -> core::cmp::Ordering {
+        let ranking = self.0.ranking;
+        self.rank()
+            .cmp(&other.rank())
+            // between equals, the lowest tie breaks come out first...
+            .then_with(|| {
+                ranking
+                    .tie_break(other.0.key.fold())
+                    .cmp(&ranking.tie_break(self.0.key.fold()))
+            })
+            // ...then the alphabetically first words
+            .then_with(|| other.0.used_words.cmp(&self.0.used_words))
     }
 }
 
@@ -158,7 +909,7 @@ impl<'a> PartialOrd for RankedSolution<'a> {
 
 impl<'a> PartialEq for RankedSolution<'a> {
     fn eq(&self, other: &Self) -> bool {
-        self.rank().eq(&other.rank())
+        self.cmp(other) == Ordering::Equal
     }
 }
 
@@ -171,13 +922,20 @@ struct PartialSolution<'a> {
     /// The words in the solution so far
     used_words: BTreeSet<&'static str>,
 
+    /// Which words are used, so equal sets of words have equal keys
+    /// however they were built up
+    key: WordSetKey,
+
     /// The current total usages of a positional character from the input grid
     char_usages: Vec<LetterCounts>,
+
+    /// Weights replacing the rankings' order of precedence, and a seed for their ties
+    ranking: Ranking,
 }
 
 // deliberately omitting the word list just to make output shorter
-impl<'a> std::fmt::Debug for PartialSolution<'a> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl<'a> core::fmt::Debug for PartialSolution<'a> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_struct("PartialSolution")
             .field("used_words", &self.used_words)
             .field("char_usages", &self.char_usages)
@@ -187,20 +945,28 @@ impl<'a> std::fmt::Debug for PartialSolution<'a> {
 
 impl<'a> PartialSolution<'a> {
     fn empty(typeshift: &'a Typeshift) -> Self {
+        Self::ranked_by(typeshift, Ranking::default())
+    }
+
+    fn ranked_by(typeshift: &'a Typeshift, ranking: Ranking) -> Self {
         Self {
             typeshift,
             used_words: Default::default(),
+            key: WordSetKey::empty(typeshift.words.len()),
             char_usages: vec![LetterCounts::new(); typeshift.columns.len()],
+            ranking,
         }
     }
 
-    /// Ranks all words, and returns all tied for best.
-    fn next_words(&mut self) -> Vec<&'static str> {
-        let ranked_words = self.rank_words();
-        let best_rank = ranked_words.first().unwrap().1;
+    /// Ranks all words, and returns all tied for best of those that cover a new letter;
+    /// the usual ranking always puts those first, but weighted ones might not.
+    fn next_words(&self, buffer: &mut RankBuffer) -> Vec<&'static str> {
+        let mut ranked_words = (self.rank_words_into(buffer))
+            .filter(|&(word, _rank)| self.new_letters(word) > 0)
+            .peekable();
+        let best_rank = ranked_words.peek().unwrap().1;
 
         ranked_words
-            .into_iter()
             // TODO this overtrims and can fail to find all possible solutions
             .take_while(|(_word, rank)| *rank == best_rank)
             .map(|(word, _rank)| word)
@@ -210,23 +976,48 @@ impl<'a> PartialSolution<'a> {
     /// Rank all possible words for usage as the next word in the solution (best first),
     /// by how many unused characters they would use,
     /// and the rarity of their rarest letter.
-    fn rank_words(&self) -> Vec<(&'static str, impl Ord + Copy)> {
-        let mut ranked_words = Vec::new();
-        for &word in &self.typeshift.words {
+    fn rank_words(&self) -> Vec<(&'static str, WordRank)> {
+        self.rank_words_into(&mut RankBuffer::default()).collect()
+    }
+
+    /// Like `rank_words`, but ranking into a buffer kept between calls,
+    /// for searches that rank the words again at every step
+    fn rank_words_into<'b>(
+        &self,
+        buffer: &'b mut RankBuffer,
+    ) -> impl Iterator<Item = (&'static str, WordRank)> + 'b {
+        let ranked_words = &mut buffer.0;
+        ranked_words.clear();
+        for (index, &word) in self.typeshift.words.iter().enumerate() {
+            let new_letters = self.new_letters(word) as i64;
+            let min_char_freq = self.min_char_freq(word) as i64;
             // for sorting; lower is better
-            let rank = (
-                // using more new letters is better
-                Reverse(self.new_letters(word)),
-                // a rarest letter with fewer usages is better
-                self.min_char_freq(word),
-            );
+            let rank = match self.ranking.weights {
+                None => (
+                    // using more new letters is better
+                    Reverse(new_letters),
+                    // a rarest letter with fewer usages is better
+                    min_char_freq,
+                ),
+                Some(weights) => (
+                    Reverse(
+                        new_letters * weights.new_letters - min_char_freq * weights.min_char_freq,
+                    ),
+                    0,
+                ),
+            };
 
-            ranked_words.push((word, rank));
+            let tie_break = match self.ranking.tie_seed {
+                None => 0,
+                Some(_) => self.ranking.tie_break(index as u64),
+            };
+            ranked_words.push((word, rank, tie_break));
         }
 
-        ranked_words.sort_by_key(|(_word, rank)| *rank);
+        // ties are in tie break order, then alphabetical, as the words are
+        ranked_words.sort_by_key(|&(word, rank, tie_break)| (rank, tie_break, word));
 
-        ranked_words
+        (ranked_words.iter()).map(|&(word, rank, _tie_break)| (word, rank))
     }
 
     /// Returns the number of unused letters the word would use
@@ -251,7 +1042,19 @@ impl<'a> PartialSolution<'a> {
             self.char_usages[col].add(word_ch);
         }
 
-        self.used_words.insert(word);
+        if self.used_words.insert(word) {
+            let index = self.typeshift.words.binary_search(&word).unwrap();
+            self.key.insert(index);
+        }
+    }
+
+    /// The solution's priority, as reported to search observers
+    fn node_rank(&self) -> NodeRank {
+        NodeRank {
+            solved: self.solved(),
+            overlaps: self.overlaps(),
+            words: self.used_words.len(),
+        }
     }
 
     /// Returns true if all characters are used at least once
@@ -271,12 +1074,60 @@ impl<'a> PartialSolution<'a> {
     }
 }
 
+/// A word's rank as a next word, for sorting; lower is better
+type WordRank = (Reverse<i64>, i64);
+
+/// The words as last ranked, with their ranks and tie breaks;
+/// reused by a search so that expanding a partial solution doesn't allocate a new ranking
+#[derive(Debug, Default)]
+struct RankBuffer(Vec<(&'static str, WordRank, u64)>);
+
+/// A set of a puzzle's words, as one bit for each index of its words
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum WordSetKey {
+    /// For puzzles of up to 128 words, which is nearly all of them
+    Small(u128),
+    /// For larger puzzles, 64 words to a block
+    Large(Vec<u64>),
+}
+
+impl WordSetKey {
+    /// No words, out of a puzzle with the given number of them
+    fn empty(words: usize) -> Self {
+        if words <= 128 {
+            Self::Small(0)
+        } else {
+            Self::Large(vec![0; words.div_ceil(64)])
+        }
+    }
+
+    fn insert(&mut self, index: usize) {
+        match self {
+            Self::Small(bits) => *bits |= 1 << index,
+            Self::Large(blocks) => blocks[index / 64] |= 1 << (index % 64),
+        }
+    }
+
+    /// Folds the set down to 64 bits, for seeding its tie break;
+    /// unlike the key itself, different sets can share a fold
+    fn fold(&self) -> u64 {
+        match self {
+            Self::Small(bits) => SplitMix64(*bits as u64).next() ^ (bits >> 64) as u64,
+            Self::Large(blocks) => {
+                (blocks.iter()).fold(0, |fold, &block| SplitMix64(fold).next() ^ block)
+            }
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
     use std::collections::BTreeSet;
 
+    use crate::cache::MemoryCache;
+
     use pretty_assertions::assert_eq;
 
     /// A small input that should stay fast
@@ -284,7 +1135,7 @@ mod tests {
     fn small_example() {
         let input = include_str!("../files/puzzles/2023-11-16.txt");
         let solution = ["above", "basic", "study", "wheel", "whups"];
-        let steps = 8;
+        let steps = 6;
 
         test_input(input, solution, steps);
     }
@@ -295,11 +1146,376 @@ mod tests {
     fn large_example() {
         let input = include_str!("../files/puzzles/2023-11-19.txt");
         let solution = ["chumps", "corves", "fifers", "granny", "poiser"];
-        let steps = 67;
+        let steps = 61;
 
         test_input(input, solution, steps);
     }
 
+    /// Greedy solving is fast, but not always minimal
+    #[test]
+    fn greedy_example() {
+        let input = include_str!("../files/puzzles/2023-11-19.txt");
+        let typeshift = Typeshift::new(input);
+        let config = SolverConfig {
+            strategy: Strategy::Greedy,
+            ..Default::default()
+        };
+        let (solution, steps) = typeshift.solve_with(&config).unwrap();
+
+        assert_eq!(steps, 7);
+        assert_eq!(solution.len(), 7);
+    }
+
+    #[test]
+    fn dictionary_order_independence() {
+        let input = include_str!("../files/puzzles/2023-11-19.txt");
+        let columns = Typeshift::parse_columns(input).unwrap();
+        let sorted = Typeshift::new(input);
+
+        let mut words = dictionary::words().to_vec();
+        words.reverse();
+        words.extend_from_slice(&dictionary::words()[..1000]);
+        let reordered = Typeshift::with_dictionary(columns, &words);
+
+        assert_eq!(reordered.words(), sorted.words());
+        assert_eq!(
+            reordered.find_first_solution(),
+            sorted.find_first_solution()
+        );
+        assert_eq!(reordered.find_all_solutions(), sorted.find_all_solutions());
+    }
+
+    #[test]
+    fn word_set_keys() {
+        let typeshift = Typeshift::new(include_str!("../files/puzzles/2023-11-16.txt"));
+        let with_words = |words: &[&'static str]| {
+            let mut partial_solution = PartialSolution::empty(&typeshift);
+            for &word in words {
+                partial_solution.add_word(word);
+            }
+            partial_solution.key
+        };
+
+        let key = with_words(&["above", "basic", "study"]);
+        assert_eq!(with_words(&["study", "above", "basic", "above"]), key);
+        assert_ne!(with_words(&["above", "basic"]), key);
+        assert_eq!(with_words(&[]), WordSetKey::Small(0));
+
+        let mut large = WordSetKey::empty(200);
+        for index in [0, 64, 199] {
+            large.insert(index);
+        }
+        assert_eq!(large, WordSetKey::Large(vec![1, 1, 0, 1 << 7]));
+    }
+
+    #[test]
+    fn reused_rank_buffers() {
+        let typeshift = Typeshift::new(include_str!("../files/puzzles/2023-11-16.txt"));
+        let mut partial_solution = PartialSolution::empty(&typeshift);
+        let mut buffer = RankBuffer::default();
+
+        for word in ["above", "basic", "study"] {
+            let ranked = Vec::from_iter(partial_solution.rank_words_into(&mut buffer));
+            assert_eq!(ranked, partial_solution.rank_words());
+            partial_solution.add_word(word);
+        }
+        assert_eq!(buffer.0.len(), typeshift.words.len());
+    }
+
+    #[test]
+    fn search_estimates() {
+        let slow = Typeshift::new(include_str!("../files/puzzles/2023-11-19.txt"));
+        let fast = Typeshift::new(include_str!("../files/puzzles/2023-11-16.txt"));
+
+        let slow = slow.estimate_search().unwrap();
+        let fast = fast.estimate_search().unwrap();
+        assert_eq!(slow.greedy_words, 7);
+        assert_eq!((slow.estimated_steps, fast.estimated_steps), (20, 10));
+        assert!(slow.branching > fast.branching);
+        assert_eq!(
+            Typeshift::new("xq\nzz").estimate_search(),
+            Err(SolveError::Unsolvable)
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(Typeshift::parse("").unwrap_err(), ParseError::Empty);
+        assert_eq!(
+            Typeshift::parse("abc\n\ndef").unwrap_err(),
+            ParseError::EmptyColumn { column: 2 }
+        );
+        assert_eq!(
+            Typeshift::parse("abc\ndEf").unwrap_err(),
+            ParseError::InvalidLetter {
+                column: 2,
+                letter: 'E'
+            }
+        );
+    }
+
+    #[test]
+    fn unsolvable_and_limited_searches() {
+        // no dictionary word ends in 'q'
+        let unsolvable = Typeshift::parse("wsab\nhbta\noesu\ndpive\nlceysq").unwrap();
+        let result = unsolvable.solve_with(&SolverConfig::default());
+        assert_eq!(result.unwrap_err(), SolveError::Unsolvable);
+
+        let input = include_str!("../files/puzzles/2023-11-19.txt");
+        let typeshift = Typeshift::new(input);
+        let config = SolverConfig {
+            max_steps: Some(10),
+            ..Default::default()
+        };
+        let result = typeshift.solve_with(&config);
+        assert_eq!(result.unwrap_err(), SolveError::Timeout { steps: 11 });
+
+        let cancelled = AtomicBool::new(true);
+        let result = typeshift.solve_cancellable(&SolverConfig::default(), &cancelled);
+        assert_eq!(result.unwrap_err(), SolveError::Cancelled { steps: 1 });
+    }
+
+    #[test]
+    fn weighted_searches() {
+        let typeshift = Typeshift::new(include_str!("../files/puzzles/2023-11-19.txt"));
+        let weights = RankWeights {
+            new_letters: 100,
+            min_char_freq: 1,
+            overlaps: 10,
+            depth: 1,
+        };
+        let config = SolverConfig {
+            weights: Some(weights),
+            ..Default::default()
+        };
+        assert_eq!(
+            typeshift.solve_with(&config).unwrap(),
+            typeshift.find_first_solution()
+        );
+
+        // weights that rank nothing still cover a new letter with every word
+        let config = SolverConfig {
+            strategy: Strategy::Greedy,
+            weights: Some(RankWeights {
+                new_letters: 0,
+                min_char_freq: 0,
+                overlaps: 0,
+                depth: 0,
+            }),
+            ..Default::default()
+        };
+        let (solution, steps) = typeshift.solve_with(&config).unwrap();
+        assert_eq!((solution.len(), steps), (14, 14));
+    }
+
+    #[test]
+    fn seeded_tie_breaks() {
+        let typeshift = Typeshift::new(include_str!("../files/puzzles/2023-11-27.txt"));
+        let (all_solutions, _steps) = typeshift.find_all_solutions();
+        let seeded = |tie_seed| {
+            let config = SolverConfig {
+                tie_seed: Some(tie_seed),
+                ..Default::default()
+            };
+            typeshift.solve_with(&config).unwrap().0
+        };
+
+        // each seed finds a minimal solution, the same one every time
+        let mut sampled = BTreeSet::new();
+        for tie_seed in 0..8 {
+            let solution = seeded(tie_seed);
+            assert!(all_solutions.contains(&solution));
+            assert_eq!(seeded(tie_seed), solution);
+            sampled.insert(solution);
+        }
+        assert_eq!((sampled.len(), all_solutions.len()), (4, 5));
+    }
+
+    #[test]
+    fn cached_solves() {
+        let input = include_str!("../files/puzzles/2023-11-19.txt");
+        let typeshift = Typeshift::new(input);
+        let cache: &'static MemoryCache = Box::leak(Box::default());
+        let config = SolverConfig {
+            cache: Some(cache),
+            ..Default::default()
+        };
+
+        let key = typeshift.cache_key(Strategy::BestFirst);
+        let first = typeshift.solve_with(&config).unwrap();
+        assert_eq!(cache.get(&key).map(|cached| cached.steps), Some(61));
+        assert_eq!(typeshift.solve_with(&config).unwrap(), first);
+
+        // a stale entry that doesn't solve the puzzle is searched again
+        let stale = CachedSolution {
+            words: vec!["chumps".to_string()],
+            steps: 1,
+        };
+        cache.put(&key, stale);
+        assert_eq!(typeshift.solve_with(&config).unwrap(), first);
+    }
+
+    #[test]
+    fn custom_dictionaries() {
+        let columns = Typeshift::parse_columns("b\nao\nr\nex").unwrap();
+        let embedded = Typeshift::from_columns(columns.clone());
+        let custom = Typeshift::with_dictionary(columns, &["bare", "bore", "borx"]);
+
+        assert_eq!(embedded.words(), ["bare", "bore"]);
+        assert_eq!(custom.words(), ["bare", "bore", "borx"]);
+        assert_eq!(
+            custom.find_first_solution(),
+            (BTreeSet::from(["bare", "borx"]), 3)
+        );
+        assert_ne!(
+            custom.cache_key(Strategy::BestFirst),
+            embedded.cache_key(Strategy::BestFirst)
+        );
+    }
+
+    #[test]
+    fn checking_words() {
+        let typeshift = Typeshift::new("b\nao\nr\nex");
+
+        assert!(typeshift.is_valid_word("bare"));
+        assert!(!typeshift.is_valid_word("borx"));
+        assert_eq!(typeshift.check_word("bore"), Ok(()));
+        assert_eq!(
+            typeshift.check_word("bar"),
+            Err(InvalidWord::WrongLength {
+                expected: 4,
+                found: 3
+            })
+        );
+        assert_eq!(
+            typeshift.check_word("bire"),
+            Err(InvalidWord::NotInColumn {
+                column: 2,
+                letter: 'i'
+            })
+        );
+        assert_eq!(
+            typeshift.check_word("borx"),
+            Err(InvalidWord::NotInDictionary)
+        );
+    }
+
+    #[test]
+    fn scoring_words() {
+        let typeshift = Typeshift::new(include_str!("../files/puzzles/2023-11-16.txt"));
+        let scores = typeshift.score_words(&["above"]).unwrap();
+        assert_eq!(scores.len(), typeshift.size() - 1);
+        assert_eq!(
+            scores[0],
+            WordScore {
+                word: "basic",
+                new_letters: 5,
+                min_char_freq: 2,
+                overlaps: 0,
+                best: true,
+            }
+        );
+        // tied on new letters, but its rarest letter is more common
+        assert_eq!(
+            (scores[1].word, scores[1].min_char_freq, scores[1].best),
+            ("study", 3, false)
+        );
+        assert!(scores.iter().all(|score| score.word != "above"));
+        let words = Vec::from_iter(scores.iter().map(|score| score.word));
+        assert_eq!(typeshift.ranked_words(&["above"]), Ok(words));
+
+        assert_eq!(
+            typeshift.score_words(&["xyzzy"]),
+            Err(PlayError::NotInPuzzle("xyzzy".to_string()))
+        );
+    }
+
+    #[test]
+    fn completing_games() {
+        let typeshift = Typeshift::new(include_str!("../files/puzzles/2023-11-16.txt"));
+
+        assert_eq!(
+            typeshift.complete(&[]).unwrap(),
+            typeshift.find_first_solution().0
+        );
+        // four more words finish after stoic, as after any word of a minimal solution
+        assert_eq!(
+            typeshift.complete_with(&["stoic"], &SolverConfig::default()),
+            Ok((BTreeSet::from(["above", "bauds", "sheel", "waspy"]), 5))
+        );
+        assert_eq!(
+            typeshift.complete(&["above", "stoic"]),
+            Ok(BTreeSet::from(["bauds", "sheel", "waspy"]))
+        );
+        assert_eq!(
+            typeshift.complete(&["above", "basic", "study", "wheel", "whups"]),
+            Ok(BTreeSet::new())
+        );
+        assert_eq!(
+            typeshift.complete(&["above", "xyzzy"]),
+            Err(PlayError::NotInPuzzle("xyzzy".to_string()))
+        );
+        let config = SolverConfig {
+            max_steps: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(
+            typeshift.complete_with(&["stoic"], &config),
+            Err(PlayError::Solve(SolveError::Timeout { steps: 2 }))
+        );
+    }
+
+    #[test]
+    fn completing_from_cells() {
+        let typeshift = Typeshift::new(include_str!("../files/puzzles/2023-11-16.txt"));
+        let cells_of = |words: &[&str]| -> Vec<(usize, char)> {
+            (words.iter())
+                .flat_map(|word| word.chars().enumerate())
+                .collect()
+        };
+
+        let covered = cells_of(&["above", "stoic"]);
+        assert_eq!(
+            typeshift.complete_cells(&covered),
+            Ok(BTreeSet::from(["bauds", "sheel", "waspy"]))
+        );
+
+        // cells outside the puzzle are ignored
+        let mut covered = cells_of(&["above", "basic", "study", "wheel"]);
+        covered.extend([(0, 'z'), (9, 'a')]);
+        assert_eq!(
+            typeshift.complete_cells_with(&covered, &SolverConfig::default()),
+            Ok((BTreeSet::from(["whups"]), 2))
+        );
+
+        let everything = cells_of(&["above", "basic", "study", "wheel", "whups"]);
+        assert_eq!(typeshift.complete_cells(&everything), Ok(BTreeSet::new()));
+    }
+
+    #[test]
+    fn shared_between_threads() {
+        let input = include_str!("../files/puzzles/2023-11-16.txt");
+        let typeshift = std::sync::Arc::new(Typeshift::new(input));
+        let expected = typeshift.find_first_solution();
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let typeshift = std::sync::Arc::clone(&typeshift);
+                std::thread::spawn(move || {
+                    let solution = typeshift.find_first_solution();
+                    let hint = crate::hint::suggest(&solution.0, &[]);
+                    (solution, hint)
+                })
+            })
+            .collect();
+
+        for handle in handles {
+            let (solution, hint) = handle.join().unwrap();
+            assert_eq!(solution, expected);
+            assert_eq!(hint, expected.0.first().copied());
+        }
+    }
+
     fn test_input(
         input: &str,
         expected_solution: impl Into<BTreeSet<&'static str>>,
@@ -39,8 +39,8 @@ impl LetterSet {
     }
 }
 
-impl std::fmt::Debug for LetterSet {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for LetterSet {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         f.debug_set().entries(self.iter()).finish()
     }
 }
@@ -71,10 +71,16 @@ impl LetterCounts {
     pub fn get(&self, ch: char) -> usize {
         self.0.get(ch)
     }
+
+    /// The counts for 'a' through 'z'
+    #[cfg(feature = "serde")]
+    pub fn to_array(&self) -> [usize; 26] {
+        self.0 .0
+    }
 }
 
-impl std::fmt::Debug for LetterCounts {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+impl core::fmt::Debug for LetterCounts {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
         let entries = self.0.entries().filter(|(_ch, &count)| count > 0);
         f.debug_map().entries(entries).finish()
     }
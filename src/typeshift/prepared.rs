@@ -0,0 +1,200 @@
+//! Serializing a constructed puzzle, so servers can skip the dictionary filtering
+//! for puzzles they see repeatedly
+//!
+//! Words are stored as ids into the embedded dictionary, along with the dictionary's
+//! fingerprint; restoring checks everything against the columns without rescanning
+//! the dictionary.
+
+use std::iter::zip;
+
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{LetterCounts, LetterSet, Typeshift};
+use crate::dictionary;
+use crate::error::RestoreError;
+
+/// The serialized form of a `Typeshift`
+#[derive(Serialize, Deserialize)]
+struct PreparedPuzzle {
+    dictionary: u64,
+    columns: Vec<String>,
+    word_ids: Vec<u32>,
+    char_freqs: [usize; 26],
+}
+
+impl From<&Typeshift> for PreparedPuzzle {
+    fn from(typeshift: &Typeshift) -> Self {
+        let dict = dictionary::words();
+        let word_ids = typeshift
+            .words
+            .iter()
+            .map(|word| dict.binary_search(word).unwrap() as u32)
+            .collect();
+
+        Self {
+            dictionary: dictionary::fingerprint(),
+            columns: typeshift.input_columns.clone(),
+            word_ids,
+            char_freqs: typeshift.char_freqs.to_array(),
+        }
+    }
+}
+
+impl TryFrom<PreparedPuzzle> for Typeshift {
+    type Error = RestoreError;
+
+    fn try_from(prepared: PreparedPuzzle) -> Result<Self, Self::Error> {
+        if prepared.dictionary != dictionary::fingerprint() {
+            return Err(RestoreError::DictionaryMismatch);
+        }
+
+        let input_columns =
+            Typeshift::parse_columns(&prepared.columns.join("\n")).map_err(RestoreError::Parse)?;
+        let columns: Vec<_> = input_columns
+            .iter()
+            .map(|l| LetterSet::from_iter(l.chars()))
+            .collect();
+
+        let dict = dictionary::words();
+        let mut words = Vec::with_capacity(prepared.word_ids.len());
+        let mut previous = None;
+        for id in prepared.word_ids {
+            let invalid = RestoreError::InvalidWord { id };
+            // the filtered dictionary keeps the embedded dictionary's order
+            if previous.is_some_and(|previous| id <= previous) {
+                return Err(invalid);
+            }
+            let word = *dict.get(id as usize).ok_or(invalid.clone())?;
+            let spellable = word.len() == columns.len()
+                && zip(word.chars(), columns.iter()).all(|(ch, col)| col.contains(ch));
+            if !spellable {
+                return Err(invalid);
+            }
+
+            words.push(word);
+            previous = Some(id);
+        }
+
+        let char_freqs = LetterCounts::from_iter(words.iter().flat_map(|word| word.chars()));
+        if char_freqs.to_array() != prepared.char_freqs {
+            return Err(RestoreError::InvalidFrequencies);
+        }
+
+        Ok(Self {
+            columns,
+            input_columns,
+            words,
+            char_freqs,
+            dictionary_fingerprint: None,
+        })
+    }
+}
+
+impl Serialize for Typeshift {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if self.dictionary_fingerprint.is_some() {
+            let message = "only puzzles using the embedded dictionary can be serialized";
+            return Err(serde::ser::Error::custom(message));
+        }
+
+        PreparedPuzzle::from(self).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Typeshift {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let prepared = PreparedPuzzle::deserialize(deserializer)?;
+        Typeshift::try_from(prepared).map_err(serde::de::Error::custom)
+    }
+}
+
+impl Typeshift {
+    /// Serializes the prepared puzzle with bincode.
+    ///
+    /// Panics for a puzzle built `with_dictionary`.
+    #[cfg(feature = "bincode")]
+    pub fn to_bincode(&self) -> Vec<u8> {
+        bincode::serde::encode_to_vec(self, bincode::config::standard()).unwrap()
+    }
+
+    /// Restores a puzzle written by `to_bincode`
+    #[cfg(feature = "bincode")]
+    pub fn from_bincode(bytes: &[u8]) -> Result<Self, RestoreError> {
+        let (prepared, _len): (PreparedPuzzle, _) =
+            bincode::serde::decode_from_slice(bytes, bincode::config::standard())
+                .map_err(|e| RestoreError::Decode(e.to_string()))?;
+
+        Self::try_from(prepared)
+    }
+
+    /// Serializes the prepared puzzle as CBOR.
+    ///
+    /// Panics for a puzzle built `with_dictionary`.
+    #[cfg(feature = "cbor")]
+    pub fn to_cbor(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+        ciborium::into_writer(self, &mut bytes).unwrap();
+        bytes
+    }
+
+    /// Restores a puzzle written by `to_cbor`
+    #[cfg(feature = "cbor")]
+    pub fn from_cbor(bytes: &[u8]) -> Result<Self, RestoreError> {
+        let prepared: PreparedPuzzle =
+            ciborium::from_reader(bytes).map_err(|e| RestoreError::Decode(e.to_string()))?;
+
+        Self::try_from(prepared)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn prepared_round_trip() {
+        let input = include_str!("../../files/puzzles/2023-11-19.txt");
+        let typeshift = Typeshift::new(input);
+
+        let restored = Typeshift::try_from(PreparedPuzzle::from(&typeshift)).unwrap();
+        assert_eq!(restored.words(), typeshift.words());
+        assert_eq!(restored.input_columns(), typeshift.input_columns());
+        assert_eq!(
+            restored.find_first_solution(),
+            typeshift.find_first_solution()
+        );
+
+        let mut tampered = PreparedPuzzle::from(&typeshift);
+        tampered.word_ids.swap(0, 1);
+        let id = tampered.word_ids[1];
+        assert_eq!(
+            Typeshift::try_from(tampered).unwrap_err(),
+            RestoreError::InvalidWord { id }
+        );
+
+        let mut tampered = PreparedPuzzle::from(&typeshift);
+        tampered.dictionary ^= 1;
+        assert_eq!(
+            Typeshift::try_from(tampered).unwrap_err(),
+            RestoreError::DictionaryMismatch
+        );
+    }
+
+    #[cfg(feature = "bincode")]
+    #[test]
+    fn bincode_round_trip() {
+        let typeshift = Typeshift::new(include_str!("../../files/puzzles/2023-11-16.txt"));
+        let restored = Typeshift::from_bincode(&typeshift.to_bincode()).unwrap();
+        assert_eq!(restored.words(), typeshift.words());
+    }
+
+    #[cfg(feature = "cbor")]
+    #[test]
+    fn cbor_round_trip() {
+        let typeshift = Typeshift::new(include_str!("../../files/puzzles/2023-11-16.txt"));
+        let restored = Typeshift::from_cbor(&typeshift.to_cbor()).unwrap();
+        assert_eq!(restored.words(), typeshift.words());
+    }
+}
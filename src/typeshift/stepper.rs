@@ -0,0 +1,140 @@
+//! A best-first search that advances a few steps at a time,
+//! for hosts like game loops that can't block on a whole solve
+
+use alloc::collections::BTreeSet;
+
+use super::{Frontier, PartialSolution, Typeshift};
+
+/// Where a stepped search stands after a call to `SolverStepper::step`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum StepOutcome {
+    /// The budget ran out before a solution; step again to continue
+    Searching { steps: usize },
+    /// The first solution, and the steps the whole search took
+    Solved {
+        words: BTreeSet<&'static str>,
+        steps: usize,
+    },
+    /// Some letter can't be covered by any word in the dictionary
+    Unsolvable,
+}
+
+/// A best-first search for a puzzle's first solution, run in increments.
+/// It finds the same solution in the same number of steps as `Typeshift::solve_with`
+/// with the default configuration, however the steps are divided.
+#[derive(Debug)]
+pub struct SolverStepper<'a> {
+    frontier: Frontier<'a>,
+    steps: usize,
+
+    /// The partial solution taken most recently, for showing the search's progress
+    current: BTreeSet<&'static str>,
+
+    /// Set once the search is over, and returned by every later step
+    outcome: Option<StepOutcome>,
+}
+
+impl Typeshift {
+    /// Starts a search that advances only when stepped; see `SolverStepper`
+    pub fn stepper(&self) -> SolverStepper<'_> {
+        SolverStepper {
+            frontier: Frontier::new(PartialSolution::empty(self)),
+            steps: 0,
+            current: BTreeSet::new(),
+            outcome: (!self.solvable()).then_some(StepOutcome::Unsolvable),
+        }
+    }
+}
+
+impl<'a> SolverStepper<'a> {
+    /// Advances the search by at most `budget_steps` partial solutions
+    pub fn step(&mut self, budget_steps: usize) -> StepOutcome {
+        if let Some(outcome) = &self.outcome {
+            return outcome.clone();
+        }
+
+        for _ in 0..budget_steps {
+            let Some(partial_solution) = self.frontier.pop() else {
+                // unreachable for a solvable puzzle, which has a solution to find
+                return self.finish(StepOutcome::Unsolvable);
+            };
+            self.steps += 1;
+            self.current = partial_solution.used_words.clone();
+
+            if partial_solution.solved() {
+                let words = partial_solution.used_words;
+                let steps = self.steps;
+                return self.finish(StepOutcome::Solved { words, steps });
+            }
+
+            self.frontier.expand(partial_solution, &mut ());
+        }
+
+        StepOutcome::Searching { steps: self.steps }
+    }
+
+    /// The number of partial solutions taken so far
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// The words of the partial solution taken most recently,
+    /// or the solution once one is found
+    pub fn current(&self) -> &BTreeSet<&'static str> {
+        &self.current
+    }
+
+    /// Returns true once a step has returned a solution or found there is none
+    pub fn is_finished(&self) -> bool {
+        self.outcome.is_some()
+    }
+
+    fn finish(&mut self, outcome: StepOutcome) -> StepOutcome {
+        self.outcome = Some(outcome.clone());
+        outcome
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn steps_match_a_full_solve() {
+        let input = include_str!("../../files/puzzles/2023-11-19.txt");
+        let typeshift = Typeshift::new(input);
+        let (expected, expected_steps) = typeshift.find_first_solution();
+
+        let mut stepper = typeshift.stepper();
+        let mut frames = 0;
+        let outcome = loop {
+            frames += 1;
+            match stepper.step(5) {
+                StepOutcome::Searching { steps } => assert_eq!(steps, frames * 5),
+                outcome => break outcome,
+            }
+        };
+
+        let solved = StepOutcome::Solved {
+            words: expected.clone(),
+            steps: expected_steps,
+        };
+        assert_eq!(outcome, solved);
+        assert_eq!(frames, expected_steps.div_ceil(5));
+        assert_eq!(stepper.current(), &expected);
+        assert!(stepper.is_finished());
+        assert_eq!(stepper.step(5), solved);
+    }
+
+    #[test]
+    fn unsolvable_without_stepping() {
+        let typeshift = Typeshift::new("xq\nzz");
+        let mut stepper = typeshift.stepper();
+
+        assert!(stepper.is_finished());
+        assert_eq!(stepper.step(10), StepOutcome::Unsolvable);
+        assert_eq!(stepper.steps(), 0);
+    }
+}
@@ -0,0 +1,84 @@
+use std::collections::BTreeSet;
+
+use proptest::prelude::*;
+
+use typeshift_solver::test_support::{
+    solvable_puzzle, solvable_puzzle_with, ReferenceComparison, SolvablePuzzle,
+};
+use typeshift_solver::{SolverConfig, Strategy, Typeshift};
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    #[test]
+    fn generating_words_are_possible_words(puzzle in solvable_puzzle()) {
+        let typeshift = puzzle.typeshift();
+
+        for word in &puzzle.words {
+            prop_assert!(typeshift.words().contains(word));
+        }
+    }
+
+    // best-first solutions aren't always minimal (see the TODO in `next_words`),
+    // so only their validity is checked
+    #[test]
+    fn solutions_cover_every_letter(puzzle in solvable_puzzle()) {
+        let typeshift = puzzle.typeshift();
+
+        for strategy in [Strategy::BestFirst, Strategy::Greedy] {
+            let config = SolverConfig {
+                strategy,
+                ..Default::default()
+            };
+            let (solution, _steps) = typeshift.solve_with(&config).unwrap();
+
+            for word in &solution {
+                prop_assert!(typeshift.words().contains(word));
+            }
+            prop_assert!(covers(&puzzle, solution.iter().copied()));
+        }
+    }
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    // small enough for the exhaustive reference solver
+    #[test]
+    fn solvers_agree_with_the_reference(puzzle in solvable_puzzle_with(4..=5, 2..=3)) {
+        let comparison = ReferenceComparison::new(&puzzle.typeshift());
+
+        prop_assert_eq!(comparison.errors(), Vec::<String>::new());
+    }
+
+    #[test]
+    #[ignore = "next_words trims minimal solutions; see the TODO there"]
+    fn search_finds_every_minimal_solution(puzzle in solvable_puzzle_with(4..=5, 2..=3)) {
+        let comparison = ReferenceComparison::new(&puzzle.typeshift());
+
+        prop_assert_eq!(comparison.missed(), BTreeSet::new());
+        prop_assert!(!comparison.first_is_longer());
+    }
+}
+
+// a reproducer `shrink --mismatch` found, where trimming the next words
+// loses a minimal solution
+#[test]
+fn reference_finds_trimmed_solutions() {
+    let typeshift = Typeshift::new("wap\noum\nmuto\nats\nse");
+    let comparison = ReferenceComparison::new(&typeshift);
+
+    assert_eq!(comparison.errors(), Vec::<String>::new());
+    assert_eq!(comparison.reference.len(), 2);
+    let missed = BTreeSet::from(["amuse", "potts", "pumas", "woose"]);
+    assert_eq!(comparison.missed(), BTreeSet::from([&missed]));
+    assert!(!comparison.first_is_longer());
+}
+
+fn covers<'a>(puzzle: &SolvablePuzzle, words: impl Iterator<Item = &'a str> + Clone) -> bool {
+    puzzle.columns.iter().enumerate().all(|(col, letters)| {
+        letters
+            .chars()
+            .all(|ch| words.clone().any(|word| word.chars().nth(col) == Some(ch)))
+    })
+}
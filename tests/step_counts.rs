@@ -0,0 +1,6 @@
+use typeshift_solver::test_support::{assert_step_counts, StepTolerance, RECORDED_STEPS};
+
+#[test]
+fn archived_step_counts() {
+    assert_step_counts(&RECORDED_STEPS, StepTolerance::default());
+}